@@ -48,6 +48,11 @@ pub struct Config {
     #[arg(short, long)]
     format: Option<Format>,
 
+    /// Memory in MB of the cache of pages computed by `lazy` datasets, `0` to disable it, default: `0`, saved
+    #[serde(default, deserialize_with = "default_on_error")]
+    #[arg(long, value_name = "MB")]
+    lazy_cache: Option<usize>,
+
     /// Activate fetching prices from exchanges APIs and the computation of all related datasets, default: true, saved
     #[serde(default, deserialize_with = "default_on_error")]
     #[arg(short = 'F', long, value_name = "BOOL")]
@@ -102,6 +107,11 @@ pub struct Config {
     #[serde(default, deserialize_with = "default_on_error")]
     #[arg(long, value_name = "BOOL")]
     check_collisions: Option<bool>,
+
+    /// Compact the indexer's stores before processing, can take a while, default: false, not saved
+    #[serde(skip)]
+    #[arg(long, value_name = "BOOL")]
+    compact_stores: Option<bool>,
 }
 
 impl Config {
@@ -116,6 +126,8 @@ impl Config {
 
         let mut config_saved = Self::read(&path);
 
+        let mut compact_stores = None;
+
         if let Some(mut config_args) = config_args {
             if let Some(bitcoindir) = config_args.bitcoindir.take() {
                 config_saved.bitcoindir = Some(bitcoindir);
@@ -145,6 +157,10 @@ impl Config {
                 config_saved.format = Some(format);
             }
 
+            if let Some(lazy_cache) = config_args.lazy_cache.take() {
+                config_saved.lazy_cache = Some(lazy_cache);
+            }
+
             if let Some(website) = config_args.website.take() {
                 config_saved.website = Some(website);
             }
@@ -185,18 +201,22 @@ impl Config {
                 config_saved.watch = Some(watch);
             }
 
+            compact_stores = config_args.compact_stores.take();
+
             if config_args != Config::default() {
                 dbg!(config_args);
                 panic!("Didn't consume the full config")
             }
         }
 
-        let config = config_saved;
+        let mut config = config_saved;
 
         config.check();
 
         config.write(&path)?;
 
+        config.compact_stores = compact_stores;
+
         Ok(config)
     }
 
@@ -362,6 +382,11 @@ impl Config {
         self.format.unwrap_or_default()
     }
 
+    /// In bytes
+    pub fn lazy_cache_size(&self) -> usize {
+        self.lazy_cache.unwrap_or_default() * 1024 * 1024
+    }
+
     pub fn check_collisions(&self) -> bool {
         self.check_collisions.is_some_and(|b| b)
     }
@@ -373,4 +398,8 @@ impl Config {
     pub fn watch(&self) -> bool {
         self.watch.is_some_and(|b| b)
     }
+
+    pub fn compact_stores(&self) -> bool {
+        self.compact_stores.is_some_and(|b| b)
+    }
 }
@@ -5,6 +5,7 @@ use brk_computer::Computer;
 use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_server::Server;
+use brk_vec::PageCache;
 use log::info;
 
 use crate::config::Config;
@@ -20,8 +21,22 @@ pub fn run() -> color_eyre::Result<()> {
 
     let format = config.format();
 
+    PageCache::global().set_max_size(config.lazy_cache_size());
+
     let mut indexer = Indexer::forced_import(&config.outputsdir())?;
 
+    if config.compact_stores() {
+        let stores = indexer.stores.as_ref().unwrap();
+        info!("Compacting stores...");
+        stores.compact()?;
+        stores.stats()?.iter().for_each(|stats| {
+            info!(
+                "{}: {} keys, {} bytes in {} segments",
+                stats.name, stats.len, stats.partition.disk_bytes, stats.partition.segment_count
+            )
+        });
+    }
+
     let wait_for_synced_node = || -> color_eyre::Result<()> {
         let is_synced = || -> color_eyre::Result<bool> {
             let info = rpc.get_blockchain_info()?;
@@ -9,7 +9,9 @@ use brk_core::Version;
 use brk_exit::Exit;
 use brk_fetcher::Fetcher;
 use brk_indexer::Indexer;
-use brk_vec::{Computation, Format};
+use brk_vec::{Computation, Format, ImportMode};
+use color_eyre::eyre::ContextCompat;
+use rayon::prelude::*;
 
 mod stores;
 mod utils;
@@ -17,13 +19,14 @@ mod vecs;
 
 use log::info;
 use stores::Stores;
-use vecs::Vecs;
+use vecs::{Vecs, pools::Pools, stateful::urpd::UrpdBuckets};
 
 #[derive(Clone)]
 pub struct Computer {
     fetcher: Option<Fetcher>,
     pub vecs: Vecs,
-    pub stores: Stores,
+    /// None when imported read-only
+    pub stores: Option<Stores>,
 }
 
 const VERSION: Version = Version::ONE;
@@ -44,18 +47,62 @@ impl Computer {
                 VERSION + Version::ZERO,
                 indexer,
                 fetcher.is_some(),
+                Pools::import(outputs_dir)?,
+                UrpdBuckets::import(outputs_dir)?,
                 computation,
-                format,
+                ImportMode::read_write(format),
             )?,
-            stores: Stores::import(
+            stores: Some(Stores::import(
                 // TODO: Give self.path, join inside import
                 &outputs_dir.join("stores"),
                 VERSION + Version::ZERO,
-                &indexer.stores.keyspace,
-            )?,
+                &indexer
+                    .stores
+                    .as_ref()
+                    .context("Can't import a computer with a read-only indexer")?
+                    .backend,
+            )?),
             fetcher,
         })
     }
+
+    /// Imports the vecs of a computer running in another process without ever writing to them, call `refresh` to follow its progress
+    ///
+    /// `computation`, `fetch` and `format` need to be the same as the ones of the other process
+    pub fn import_read_only(
+        outputs_dir: &Path,
+        indexer: &Indexer,
+        computation: Computation,
+        fetch: bool,
+        format: Format,
+    ) -> color_eyre::Result<Self> {
+        Ok(Self {
+            vecs: Vecs::import(
+                &outputs_dir.join("vecs/computed"),
+                VERSION + Version::ZERO,
+                indexer,
+                fetch,
+                Pools::import(outputs_dir)?,
+                UrpdBuckets::import(outputs_dir)?,
+                computation,
+                ImportMode::read_only(format),
+            )?,
+            stores: None,
+            fetcher: None,
+        })
+    }
+
+    /// Picks up the data computed by another process since the last refresh, returns whether there was any
+    pub fn refresh(&self) -> color_eyre::Result<bool> {
+        Ok(self
+            .vecs
+            .vecs()
+            .into_par_iter()
+            .map(|vec| vec.refresh())
+            .collect::<brk_core::Result<Vec<_>>>()?
+            .into_iter()
+            .any(|refreshed| refreshed))
+    }
 }
 
 impl Computer {
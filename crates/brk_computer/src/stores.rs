@@ -5,8 +5,7 @@ use brk_core::{
     P2PKHAddressIndex, P2SHAddressIndex, P2TRAddressIndex, P2WPKHAddressIndex, P2WSHAddressIndex,
     Version,
 };
-use brk_store::Store;
-use fjall::TransactionalKeyspace;
+use brk_store::{FjallBackend, Store};
 use jiff::Unit;
 
 const _VERSION: Version = Version::ZERO;
@@ -60,7 +59,7 @@ pub struct Stores {
 }
 
 impl Stores {
-    pub fn import(_: &Path, _: Version, _: &TransactionalKeyspace) -> color_eyre::Result<Self> {
+    pub fn import(_: &Path, _: Version, _: &FjallBackend) -> color_eyre::Result<Self> {
         // let address_to_utxos_received = Store::import(
         //     keyspace.clone(),
         //     path,
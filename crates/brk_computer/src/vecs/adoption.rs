@@ -0,0 +1,420 @@
+use std::path::Path;
+
+use brk_core::{GroupedBySpendableType, InputIndex, Result, Sats, StoredF64, StoredUsize, Version};
+use brk_exit::Exit;
+use brk_indexer::Indexer;
+use brk_vec::{
+    AnyCollectableVec, AnyIterableVec, AnyVec, Computation, EagerVec, EagerVecGroup, ImportMode,
+};
+
+use super::{
+    Indexes, fetched,
+    grouped::{ComputedValueVecsFromHeight, ComputedVecsFromHeight, StorableVecGeneatorOptions},
+    indexes, transactions,
+};
+
+const VERSION: Version = Version::ZERO;
+
+/// What is actually spent, as opposed to what outputs are created
+#[derive(Clone)]
+pub struct Vecs {
+    pub by_type: GroupedBySpendableType<SpentTypeVecs>,
+    /// Non coinbase transactions with a witness, whether native or nested
+    pub indexes_to_segwit_tx_count: ComputedVecsFromHeight<StoredUsize>,
+    /// Percentage of the non coinbase transactions spending at least one SegWit input
+    pub indexes_to_segwit_tx_share: ComputedVecsFromHeight<StoredF64>,
+    pub indexes_to_taproot_key_path_spend_count: ComputedVecsFromHeight<StoredUsize>,
+    pub indexes_to_taproot_script_path_spend_count: ComputedVecsFromHeight<StoredUsize>,
+    /// Percentage of the P2TR inputs spent through a script path
+    pub indexes_to_taproot_script_path_share: ComputedVecsFromHeight<StoredF64>,
+}
+
+/// Inputs spending outputs of a given type
+#[derive(Clone)]
+pub struct SpentTypeVecs {
+    pub indexes_to_spent_count: ComputedVecsFromHeight<StoredUsize>,
+    pub indexes_to_spent_value: ComputedValueVecsFromHeight,
+    /// Percentage of the value spent in the block
+    pub indexes_to_spent_value_share: ComputedVecsFromHeight<StoredF64>,
+}
+
+impl SpentTypeVecs {
+    fn forced_import(
+        path: &Path,
+        outputtype: &str,
+        version: Version,
+        mode: ImportMode,
+        compute_dollars: bool,
+    ) -> color_eyre::Result<Self> {
+        Ok(Self {
+            indexes_to_spent_count: ComputedVecsFromHeight::forced_import(
+                path,
+                &format!("{outputtype}_spent_count"),
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            indexes_to_spent_value: ComputedValueVecsFromHeight::forced_import(
+                path,
+                &format!("{outputtype}_spent_value"),
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+                compute_dollars,
+            )?,
+            indexes_to_spent_value_share: ComputedVecsFromHeight::forced_import(
+                path,
+                &format!("{outputtype}_spent_value_share"),
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_average(),
+            )?,
+        })
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.indexes_to_spent_count.vecs(),
+            self.indexes_to_spent_value.vecs(),
+            self.indexes_to_spent_value_share.vecs(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        _computation: Computation,
+        mode: ImportMode,
+        fetched: Option<&fetched::Vecs>,
+    ) -> color_eyre::Result<Self> {
+        let compute_dollars = fetched.is_some();
+
+        let import = |outputtype: &str| {
+            SpentTypeVecs::forced_import(
+                path,
+                outputtype,
+                version + VERSION + Version::ZERO,
+                mode,
+                compute_dollars,
+            )
+        };
+
+        Ok(Self {
+            by_type: GroupedBySpendableType {
+                p2pk65: import("p2pk65")?,
+                p2pk33: import("p2pk33")?,
+                p2pkh: import("p2pkh")?,
+                p2ms: import("p2ms")?,
+                p2sh: import("p2sh")?,
+                p2wpkh: import("p2wpkh")?,
+                p2wsh: import("p2wsh")?,
+                p2tr: import("p2tr")?,
+                p2a: import("p2a")?,
+                unknown: import("unknown")?,
+                empty: import("empty")?,
+            },
+            indexes_to_segwit_tx_count: ComputedVecsFromHeight::forced_import(
+                path,
+                "segwit_tx_count",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            indexes_to_segwit_tx_share: ComputedVecsFromHeight::forced_import(
+                path,
+                "segwit_tx_share",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_average(),
+            )?,
+            indexes_to_taproot_key_path_spend_count: ComputedVecsFromHeight::forced_import(
+                path,
+                "taproot_key_path_spend_count",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            indexes_to_taproot_script_path_spend_count: ComputedVecsFromHeight::forced_import(
+                path,
+                "taproot_script_path_spend_count",
+                false,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            indexes_to_taproot_script_path_share: ComputedVecsFromHeight::forced_import(
+                path,
+                "taproot_script_path_share",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_average(),
+            )?,
+        })
+    }
+
+    pub fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        transactions: &transactions::Vecs,
+        starting_indexes: &Indexes,
+        fetched: Option<&fetched::Vecs>,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        self.compute_spent_by_type(indexer, transactions, starting_indexes, exit)?;
+
+        self.by_type
+            .as_mut_vec()
+            .into_iter()
+            .try_for_each(|vecs| -> color_eyre::Result<()> {
+                vecs.indexes_to_spent_count.compute_rest(
+                    indexes,
+                    starting_indexes,
+                    exit,
+                    None as Option<&EagerVec<_, _>>,
+                )?;
+                vecs.indexes_to_spent_value.compute_rest(
+                    indexer,
+                    indexes,
+                    fetched,
+                    starting_indexes,
+                    exit,
+                    None as Option<&EagerVec<_, _>>,
+                )?;
+                vecs.indexes_to_spent_value_share.compute_rest(
+                    indexes,
+                    starting_indexes,
+                    exit,
+                    None as Option<&EagerVec<_, _>>,
+                )
+            })?;
+
+        self.indexes_to_segwit_tx_count.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |v, indexer, _, starting_indexes, exit| {
+                let mut txindex_to_is_coinbase_iter = transactions.txindex_to_is_coinbase.iter();
+                let mut txindex_to_base_size_iter = indexer.vecs.txindex_to_base_size.iter();
+                let mut txindex_to_total_size_iter = indexer.vecs.txindex_to_total_size.iter();
+                v.compute_filtered_count_from_indexes(
+                    starting_indexes.height,
+                    &indexer.vecs.height_to_first_txindex,
+                    &indexer.vecs.txindex_to_txid,
+                    |txindex| {
+                        !txindex_to_is_coinbase_iter.unwrap_get_inner(txindex)
+                            && txindex_to_base_size_iter.unwrap_get_inner(txindex)
+                                != txindex_to_total_size_iter.unwrap_get_inner(txindex)
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        self.indexes_to_segwit_tx_share.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |v, _, _, starting_indexes, exit| {
+                let mut tx_count_iter = transactions
+                    .indexes_to_tx_count
+                    .height
+                    .as_ref()
+                    .unwrap()
+                    .iter();
+                v.compute_transform(
+                    starting_indexes.height,
+                    self.indexes_to_segwit_tx_count.height.as_ref().unwrap(),
+                    |(h, segwit_tx_count, ..)| {
+                        let tx_count = *tx_count_iter.unwrap_get_inner(h) - 1;
+                        let share = if tx_count == 0 {
+                            0.0
+                        } else {
+                            *segwit_tx_count as f64 / tx_count as f64 * 100.0
+                        };
+                        (h, StoredF64::from(share))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        self.indexes_to_taproot_script_path_spend_count
+            .compute_rest(
+                indexes,
+                starting_indexes,
+                exit,
+                Some(&indexer.vecs.height_to_taproot_control_block_count),
+            )?;
+
+        let p2tr_spent_count = self
+            .by_type
+            .p2tr
+            .indexes_to_spent_count
+            .height
+            .as_ref()
+            .unwrap();
+
+        self.indexes_to_taproot_key_path_spend_count.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |v, indexer, _, starting_indexes, exit| {
+                let mut script_path_iter =
+                    indexer.vecs.height_to_taproot_control_block_count.iter();
+                v.compute_transform(
+                    starting_indexes.height,
+                    p2tr_spent_count,
+                    |(h, spent_count, ..)| {
+                        let script_path = script_path_iter.unwrap_get_inner(h);
+                        (
+                            h,
+                            StoredUsize::from(spent_count.saturating_sub(*script_path)),
+                        )
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        self.indexes_to_taproot_script_path_share.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |v, indexer, _, starting_indexes, exit| {
+                let mut spent_count_iter = p2tr_spent_count.iter();
+                v.compute_transform(
+                    starting_indexes.height,
+                    &indexer.vecs.height_to_taproot_control_block_count,
+                    |(h, script_path, ..)| {
+                        let spent_count = *spent_count_iter.unwrap_get_inner(h);
+                        let share = if spent_count == 0 {
+                            0.0
+                        } else {
+                            *script_path as f64 / spent_count as f64 * 100.0
+                        };
+                        (h, StoredF64::from(share))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Counts and values of the inputs of each block by spent output type
+    fn compute_spent_by_type(
+        &mut self,
+        indexer: &Indexer,
+        transactions: &transactions::Vecs,
+        starting_indexes: &Indexes,
+        exit: &Exit,
+    ) -> Result<()> {
+        let mut by_type = self.by_type.as_mut_vec().map(|vecs| {
+            (
+                vecs.indexes_to_spent_count.height.as_mut().unwrap(),
+                vecs.indexes_to_spent_value.sats.height.as_mut().unwrap(),
+                vecs.indexes_to_spent_value_share.height.as_mut().unwrap(),
+            )
+        });
+
+        let mut height_to_first_inputindex_iter = indexer.vecs.height_to_first_inputindex.iter();
+        let mut inputindex_to_outputindex_iter = indexer.vecs.inputindex_to_outputindex.iter();
+        let mut outputindex_to_outputtype_iter = indexer.vecs.outputindex_to_outputtype.iter();
+        let mut inputindex_to_value_iter = transactions.inputindex_to_value.iter();
+        let inputindex_len = indexer.vecs.inputindex_to_outputindex.len();
+
+        by_type.compute_from(
+            starting_indexes.height,
+            &indexer.vecs.height_to_first_inputindex,
+            indexer.vecs.inputindex_to_outputindex.version()
+                + indexer.vecs.outputindex_to_outputtype.version()
+                + transactions.inputindex_to_value.version(),
+            |by_type, height, first_inputindex| {
+                let first_inputindex = usize::from(first_inputindex);
+                let end_inputindex = height_to_first_inputindex_iter
+                    .get_inner(height + 1_usize)
+                    .map_or(inputindex_len, usize::from);
+
+                let mut spent = GroupedBySpendableType::<(usize, Sats)>::default();
+                let mut total_value = Sats::ZERO;
+
+                (first_inputindex..end_inputindex)
+                    .map(InputIndex::from)
+                    .for_each(|inputindex| {
+                        let outputindex =
+                            inputindex_to_outputindex_iter.unwrap_get_inner(inputindex);
+                        if outputindex.is_coinbase() {
+                            return;
+                        }
+                        let outputtype =
+                            outputindex_to_outputtype_iter.unwrap_get_inner(outputindex);
+                        let value = inputindex_to_value_iter.unwrap_get_inner(inputindex);
+                        let (count, sats) = spent.get_mut(outputtype);
+                        *count += 1;
+                        *sats += value;
+                        total_value += value;
+                    });
+
+                by_type.iter_mut().zip(spent.as_mut_vec()).try_for_each(
+                    |((count, value, share), (spent_count, spent_value))| -> Result<()> {
+                        count.forced_push_at(height, StoredUsize::from(*spent_count), exit)?;
+                        value.forced_push_at(height, *spent_value, exit)?;
+                        let spent_share = if total_value == Sats::ZERO {
+                            0.0
+                        } else {
+                            f64::from(*spent_value) / f64::from(total_value) * 100.0
+                        };
+                        share.forced_push_at(height, StoredF64::from(spent_share), exit)
+                    },
+                )
+            },
+            exit,
+        )
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.by_type
+                .as_typed_vec()
+                .into_iter()
+                .flat_map(|(_, vecs)| vecs.vecs())
+                .collect::<Vec<_>>(),
+            self.indexes_to_segwit_tx_count.vecs(),
+            self.indexes_to_segwit_tx_share.vecs(),
+            self.indexes_to_taproot_key_path_spend_count.vecs(),
+            self.indexes_to_taproot_script_path_spend_count.vecs(),
+            self.indexes_to_taproot_script_path_share.vecs(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
@@ -1,12 +1,15 @@
 use std::path::Path;
 
 use brk_core::{
-    CheckedSub, DifficultyEpoch, HalvingEpoch, Height, StoredU32, StoredU64, StoredUsize,
-    Timestamp, Version, Weight,
+    CheckedSub, DifficultyEpoch, HalvingEpoch, Height, StoredF64, StoredU32, StoredU64,
+    StoredUsize, Timestamp, Version, Weight,
 };
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, AnyIterableVec, Computation, EagerVec, Format};
+use brk_vec::{
+    AnyCollectableVec, AnyIterableVec, BoxedVecIterator, Computation, EagerVec, ImportMode,
+    VecIterator,
+};
 
 use super::{
     Indexes,
@@ -16,6 +19,11 @@ use super::{
 
 const VERSION: Version = Version::ZERO;
 
+/// Seconds between two blocks targeted by the difficulty adjustments
+const TARGET_BLOCK_INTERVAL: f64 = 600.0;
+/// A difficulty adjustment can at most divide or multiply the difficulty by 4
+const MAX_ADJUSTMENT_FACTOR: f64 = 4.0;
+
 #[derive(Clone)]
 pub struct Vecs {
     pub height_to_interval: EagerVec<Height, Timestamp>,
@@ -28,6 +36,14 @@ pub struct Vecs {
     pub indexes_to_block_size: ComputedVecsFromHeight<StoredUsize>,
     pub indexes_to_block_vbytes: ComputedVecsFromHeight<StoredU64>,
     pub indexes_to_block_weight: ComputedVecsFromHeight<Weight>,
+    pub indexes_to_blocks_before_next_difficulty_adjustment: ComputedVecsFromHeight<StoredU32>,
+    /// Percentage by which the difficulty would change if the rest of the epoch was mined at the pace of its elapsed part
+    pub indexes_to_estimated_difficulty_adjustment: ComputedVecsFromHeight<StoredF64>,
+    pub indexes_to_estimated_next_difficulty_adjustment_timestamp:
+        ComputedVecsFromHeight<Timestamp>,
+    pub indexes_to_blocks_before_next_halving: ComputedVecsFromHeight<StoredU32>,
+    /// At the target interval between blocks, the pace of the current epoch is too short lived to be projected that far
+    pub indexes_to_estimated_next_halving_timestamp: ComputedVecsFromHeight<Timestamp>,
 }
 
 impl Vecs {
@@ -35,21 +51,21 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
     ) -> color_eyre::Result<Self> {
         Ok(Self {
             height_to_interval: EagerVec::forced_import(
                 path,
                 "interval",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             timeindexes_to_timestamp: ComputedVecsFromDateIndex::forced_import(
                 path,
                 "timestamp",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_first(),
             )?,
             indexes_to_block_interval: ComputedVecsFromHeight::forced_import(
@@ -57,7 +73,7 @@ impl Vecs {
                 "block_interval",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_percentiles()
                     .add_minmax()
@@ -68,7 +84,7 @@ impl Vecs {
                 "block_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -78,7 +94,7 @@ impl Vecs {
                 "block_weight",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -88,7 +104,7 @@ impl Vecs {
                 "block_size",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -97,14 +113,14 @@ impl Vecs {
                 path,
                 "vbytes",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_block_vbytes: ComputedVecsFromHeight::forced_import(
                 path,
                 "block_vbytes",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -113,13 +129,55 @@ impl Vecs {
                 path,
                 "timestamp",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             halvingepoch_to_timestamp: EagerVec::forced_import(
                 path,
                 "timestamp",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
+            )?,
+            indexes_to_blocks_before_next_difficulty_adjustment:
+                ComputedVecsFromHeight::forced_import(
+                    path,
+                    "blocks_before_next_difficulty_adjustment",
+                    true,
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    StorableVecGeneatorOptions::default().add_last(),
+                )?,
+            indexes_to_estimated_difficulty_adjustment: ComputedVecsFromHeight::forced_import(
+                path,
+                "estimated_difficulty_adjustment",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            indexes_to_estimated_next_difficulty_adjustment_timestamp:
+                ComputedVecsFromHeight::forced_import(
+                    path,
+                    "estimated_next_difficulty_adjustment_timestamp",
+                    true,
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    StorableVecGeneatorOptions::default().add_last(),
+                )?,
+            indexes_to_blocks_before_next_halving: ComputedVecsFromHeight::forced_import(
+                path,
+                "blocks_before_next_halving",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            indexes_to_estimated_next_halving_timestamp: ComputedVecsFromHeight::forced_import(
+                path,
+                "estimated_next_halving_timestamp",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
             )?,
         })
     }
@@ -233,9 +291,157 @@ impl Vecs {
             exit,
         )?;
 
+        self.indexes_to_blocks_before_next_difficulty_adjustment
+            .compute_all(
+                indexer,
+                indexes,
+                starting_indexes,
+                exit,
+                |v, indexer, _, starting_indexes, exit| {
+                    v.compute_range(
+                        starting_indexes.height,
+                        &indexer.vecs.height_to_timestamp,
+                        |h| {
+                            let next = (DifficultyEpoch::from(h) + 1_usize).first_height();
+                            (h, StoredU32::from(u32::from(next) - u32::from(h)))
+                        },
+                        exit,
+                    )
+                },
+            )?;
+
+        self.indexes_to_blocks_before_next_halving.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |v, indexer, _, starting_indexes, exit| {
+                v.compute_range(
+                    starting_indexes.height,
+                    &indexer.vecs.height_to_timestamp,
+                    |h| {
+                        let next = (HalvingEpoch::from(h) + 1_usize).first_height();
+                        (h, StoredU32::from(u32::from(next) - u32::from(h)))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let mut height_to_timestamp_iter = indexer.vecs.height_to_timestamp.iter();
+        self.indexes_to_estimated_difficulty_adjustment
+            .compute_all(
+                indexer,
+                indexes,
+                starting_indexes,
+                exit,
+                |v, indexer, _, starting_indexes, exit| {
+                    v.compute_transform(
+                        starting_indexes.height,
+                        &indexer.vecs.height_to_timestamp,
+                        |(h, timestamp, ..)| {
+                            let (elapsed, intervals) =
+                                Self::epoch_pace(h, timestamp, &mut height_to_timestamp_iter);
+                            let adjustment = if intervals == 0 {
+                                0.0
+                            } else {
+                                let factor = (TARGET_BLOCK_INTERVAL * intervals as f64
+                                    / elapsed.max(1.0))
+                                .clamp(1.0 / MAX_ADJUSTMENT_FACTOR, MAX_ADJUSTMENT_FACTOR);
+                                (factor - 1.0) * 100.0
+                            };
+                            (h, StoredF64::from(adjustment))
+                        },
+                        exit,
+                    )
+                },
+            )?;
+
+        let mut height_to_timestamp_iter = indexer.vecs.height_to_timestamp.iter();
+        let mut blocks_before_iter = self
+            .indexes_to_blocks_before_next_difficulty_adjustment
+            .height
+            .as_ref()
+            .unwrap()
+            .into_iter();
+        self.indexes_to_estimated_next_difficulty_adjustment_timestamp
+            .compute_all(
+                indexer,
+                indexes,
+                starting_indexes,
+                exit,
+                |v, indexer, _, starting_indexes, exit| {
+                    v.compute_transform(
+                        starting_indexes.height,
+                        &indexer.vecs.height_to_timestamp,
+                        |(h, timestamp, ..)| {
+                            let (elapsed, intervals) =
+                                Self::epoch_pace(h, timestamp, &mut height_to_timestamp_iter);
+                            let interval = if intervals == 0 {
+                                TARGET_BLOCK_INTERVAL
+                            } else {
+                                elapsed / intervals as f64
+                            };
+                            let remaining = f64::from(blocks_before_iter.unwrap_get_inner(h));
+                            (
+                                h,
+                                Timestamp::from(f64::from(timestamp) + remaining * interval),
+                            )
+                        },
+                        exit,
+                    )
+                },
+            )?;
+
+        let mut blocks_before_iter = self
+            .indexes_to_blocks_before_next_halving
+            .height
+            .as_ref()
+            .unwrap()
+            .into_iter();
+        self.indexes_to_estimated_next_halving_timestamp
+            .compute_all(
+                indexer,
+                indexes,
+                starting_indexes,
+                exit,
+                |v, indexer, _, starting_indexes, exit| {
+                    v.compute_transform(
+                        starting_indexes.height,
+                        &indexer.vecs.height_to_timestamp,
+                        |(h, timestamp, ..)| {
+                            let remaining = f64::from(blocks_before_iter.unwrap_get_inner(h));
+                            (
+                                h,
+                                Timestamp::from(
+                                    f64::from(timestamp) + remaining * TARGET_BLOCK_INTERVAL,
+                                ),
+                            )
+                        },
+                        exit,
+                    )
+                },
+            )?;
+
         Ok(())
     }
 
+    /// Seconds elapsed since the first block of the difficulty epoch and number of blocks mined since
+    fn epoch_pace(
+        height: Height,
+        timestamp: Timestamp,
+        height_to_timestamp_iter: &mut BoxedVecIterator<'_, Height, Timestamp>,
+    ) -> (f64, u32) {
+        let first_height = DifficultyEpoch::from(height).first_height();
+        let elapsed = timestamp
+            .checked_sub(height_to_timestamp_iter.unwrap_get_inner(first_height))
+            .unwrap_or(Timestamp::ZERO);
+        (
+            f64::from(elapsed),
+            u32::from(height) - u32::from(first_height),
+        )
+    }
+
     pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
         [
             vec![
@@ -250,6 +456,13 @@ impl Vecs {
             self.indexes_to_block_size.vecs(),
             self.indexes_to_block_vbytes.vecs(),
             self.indexes_to_block_weight.vecs(),
+            self.indexes_to_blocks_before_next_difficulty_adjustment
+                .vecs(),
+            self.indexes_to_estimated_difficulty_adjustment.vecs(),
+            self.indexes_to_estimated_next_difficulty_adjustment_timestamp
+                .vecs(),
+            self.indexes_to_blocks_before_next_halving.vecs(),
+            self.indexes_to_estimated_next_halving_timestamp.vecs(),
         ]
         .into_iter()
         .flatten()
@@ -0,0 +1,300 @@
+use std::path::Path;
+
+use brk_core::{Feerate, Result, Sats, StoredF64, StoredUsize, Version};
+use brk_exit::Exit;
+use brk_indexer::Indexer;
+use brk_vec::{
+    AnyCollectableVec, AnyIterableVec, AnyVec, Computation, EagerVec, EagerVecGroup, ImportMode,
+};
+
+use super::{
+    Indexes, fetched,
+    grouped::{ComputedValueVecsFromHeight, ComputedVecsFromHeight, StorableVecGeneatorOptions},
+    indexes, transactions,
+};
+
+const VERSION: Version = Version::ZERO;
+
+const MAX_BLOCK_WEIGHT: f64 = 4_000_000.0;
+
+/// Lower bound in sat/vB and name of each feerate band, the last one being open ended
+const FEERATE_BANDS: [(f64, &str); 11] = [
+    (0.0, "0_1"),
+    (1.0, "1_2"),
+    (2.0, "2_5"),
+    (5.0, "5_10"),
+    (10.0, "10_20"),
+    (20.0, "20_50"),
+    (50.0, "50_100"),
+    (100.0, "100_200"),
+    (200.0, "200_500"),
+    (500.0, "500_1000"),
+    (1000.0, "1000_plus"),
+];
+
+/// How full blocks are and what their space went for, the coinbase being excluded from everything fee related
+#[derive(Clone)]
+pub struct Vecs {
+    /// Percentage of the maximum block weight used
+    pub indexes_to_block_fullness: ComputedVecsFromHeight<StoredF64>,
+    /// Feerate paid by the median vbyte of the block
+    pub indexes_to_median_feerate: ComputedVecsFromHeight<Feerate>,
+    pub indexes_to_min_included_feerate: ComputedVecsFromHeight<Feerate>,
+    /// Fees paid by the 1% of the transactions paying the most, rounded up to at least one transaction
+    pub indexes_to_top_1_percent_fees: ComputedValueVecsFromHeight,
+    /// Percentage of the vsize of the non-coinbase transactions of the block paying a feerate within each of the
+    /// `FEERATE_BANDS`, so the shares of a block add up to 100
+    pub indexes_to_vsize_share_by_feerate_band: Vec<ComputedVecsFromHeight<StoredF64>>,
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        _computation: Computation,
+        mode: ImportMode,
+        fetched: Option<&fetched::Vecs>,
+    ) -> color_eyre::Result<Self> {
+        let compute_dollars = fetched.is_some();
+
+        Ok(Self {
+            indexes_to_block_fullness: ComputedVecsFromHeight::forced_import(
+                path,
+                "block_fullness",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_average()
+                    .add_minmax(),
+            )?,
+            indexes_to_median_feerate: ComputedVecsFromHeight::forced_import(
+                path,
+                "median_feerate",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_average()
+                    .add_minmax(),
+            )?,
+            indexes_to_min_included_feerate: ComputedVecsFromHeight::forced_import(
+                path,
+                "min_included_feerate",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_average()
+                    .add_minmax(),
+            )?,
+            indexes_to_top_1_percent_fees: ComputedValueVecsFromHeight::forced_import(
+                path,
+                "top_1_percent_fees",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+                compute_dollars,
+            )?,
+            indexes_to_vsize_share_by_feerate_band: FEERATE_BANDS
+                .iter()
+                .map(|(_, band)| {
+                    ComputedVecsFromHeight::forced_import(
+                        path,
+                        &format!("vsize_share_{band}_sat_vb"),
+                        true,
+                        version + VERSION + Version::ONE,
+                        mode,
+                        StorableVecGeneatorOptions::default().add_average(),
+                    )
+                })
+                .collect::<color_eyre::Result<Vec<_>>>()?,
+        })
+    }
+
+    pub fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        transactions: &transactions::Vecs,
+        starting_indexes: &Indexes,
+        fetched: Option<&fetched::Vecs>,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        self.indexes_to_block_fullness.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |v, indexer, _, starting_indexes, exit| {
+                v.compute_transform(
+                    starting_indexes.height,
+                    &indexer.vecs.height_to_weight,
+                    |(h, weight, ..)| {
+                        (
+                            h,
+                            StoredF64::from(f64::from(weight) / MAX_BLOCK_WEIGHT * 100.0),
+                        )
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        self.compute_fee_market(indexer, transactions, starting_indexes, exit)?;
+
+        self.indexes_to_median_feerate.compute_rest(
+            indexes,
+            starting_indexes,
+            exit,
+            None as Option<&EagerVec<_, _>>,
+        )?;
+        self.indexes_to_min_included_feerate.compute_rest(
+            indexes,
+            starting_indexes,
+            exit,
+            None as Option<&EagerVec<_, _>>,
+        )?;
+        self.indexes_to_top_1_percent_fees.compute_rest(
+            indexer,
+            indexes,
+            fetched,
+            starting_indexes,
+            exit,
+            None as Option<&EagerVec<_, _>>,
+        )?;
+        self.indexes_to_vsize_share_by_feerate_band
+            .iter_mut()
+            .try_for_each(|v| {
+                v.compute_rest(
+                    indexes,
+                    starting_indexes,
+                    exit,
+                    None as Option<&EagerVec<_, _>>,
+                )
+            })?;
+
+        Ok(())
+    }
+
+    /// Everything derived from the feerates of the non-coinbase transactions of a block
+    fn compute_fee_market(
+        &mut self,
+        indexer: &Indexer,
+        transactions: &transactions::Vecs,
+        starting_indexes: &Indexes,
+        exit: &Exit,
+    ) -> Result<()> {
+        let mut fee_market = (
+            self.indexes_to_median_feerate.height.as_mut().unwrap(),
+            self.indexes_to_min_included_feerate
+                .height
+                .as_mut()
+                .unwrap(),
+            self.indexes_to_top_1_percent_fees
+                .sats
+                .height
+                .as_mut()
+                .unwrap(),
+            self.indexes_to_vsize_share_by_feerate_band
+                .iter_mut()
+                .map(|v| v.height.as_mut().unwrap())
+                .collect::<Vec<_>>(),
+        );
+
+        let mut height_to_tx_count_iter = transactions
+            .indexes_to_tx_count
+            .height
+            .as_ref()
+            .unwrap()
+            .iter();
+        let mut txindex_to_feerate_iter = transactions.txindex_to_feerate.iter();
+        let mut txindex_to_vsize_iter = transactions.txindex_to_vsize.iter();
+        let mut txindex_to_fee_iter = transactions.txindex_to_fee.iter();
+
+        let zero = Feerate::from(0.0);
+        let mut txs: Vec<(Feerate, StoredUsize, Sats)> = vec![];
+
+        fee_market.compute_from(
+            starting_indexes.height,
+            &indexer.vecs.height_to_first_txindex,
+            transactions.txindex_to_feerate.version(),
+            |(median_feerate, min_included_feerate, top_1_percent_fees, vsize_shares),
+             height,
+             first_txindex| {
+                let tx_count = *height_to_tx_count_iter.unwrap_get_inner(height);
+
+                txs.clear();
+                txs.extend((1..tx_count).map(|i| {
+                    let txindex = first_txindex + i;
+                    (
+                        txindex_to_feerate_iter.unwrap_get_inner(txindex),
+                        txindex_to_vsize_iter.unwrap_get_inner(txindex),
+                        txindex_to_fee_iter.unwrap_get_inner(txindex),
+                    )
+                }));
+                txs.sort_unstable_by_key(|(feerate, ..)| *feerate);
+
+                let total_vsize = txs.iter().map(|(_, vsize, _)| **vsize).sum::<usize>();
+                let mut cumulated_vsize = 0;
+                let median = txs
+                    .iter()
+                    .find(|(_, vsize, _)| {
+                        cumulated_vsize += **vsize;
+                        cumulated_vsize * 2 >= total_vsize
+                    })
+                    .map_or(zero, |(feerate, ..)| *feerate);
+                median_feerate.forced_push_at(height, median, exit)?;
+
+                let min = txs.first().map_or(zero, |(feerate, ..)| *feerate);
+                min_included_feerate.forced_push_at(height, min, exit)?;
+
+                let mut fees = txs.iter().map(|(.., fee)| *fee).collect::<Vec<_>>();
+                fees.sort_unstable_by(|a, b| b.cmp(a));
+                let top = fees.into_iter().take(txs.len().div_ceil(100)).sum::<Sats>();
+                top_1_percent_fees.forced_push_at(height, top, exit)?;
+
+                let mut band_vsizes = [0_usize; FEERATE_BANDS.len()];
+                txs.iter().for_each(|(feerate, vsize, _)| {
+                    let feerate = f64::from(*feerate);
+                    let band = FEERATE_BANDS
+                        .iter()
+                        .rposition(|(lower_bound, _)| feerate >= *lower_bound)
+                        .unwrap_or_default();
+                    band_vsizes[band] += **vsize;
+                });
+                vsize_shares
+                    .iter_mut()
+                    .zip(band_vsizes)
+                    .try_for_each(|(v, band_vsize)| {
+                        let share = if total_vsize == 0 {
+                            0.0
+                        } else {
+                            band_vsize as f64 / total_vsize as f64 * 100.0
+                        };
+                        v.forced_push_at(height, StoredF64::from(share), exit)
+                    })
+            },
+            exit,
+        )
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.indexes_to_block_fullness.vecs(),
+            self.indexes_to_median_feerate.vecs(),
+            self.indexes_to_min_included_feerate.vecs(),
+            self.indexes_to_top_1_percent_fees.vecs(),
+            self.indexes_to_vsize_share_by_feerate_band
+                .iter()
+                .flat_map(|v| v.vecs())
+                .collect::<Vec<_>>(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
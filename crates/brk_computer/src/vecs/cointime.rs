@@ -3,7 +3,7 @@ use std::path::Path;
 use brk_core::{Bitcoin, CheckedSub, Dollars, StoredF64, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, Computation, Format, VecIterator};
+use brk_vec::{AnyCollectableVec, Computation, ImportMode, VecIterator};
 
 use crate::vecs::{
     fetched,
@@ -52,7 +52,7 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
         fetched: Option<&fetched::Vecs>,
     ) -> color_eyre::Result<Self> {
         let compute_dollars = fetched.is_some();
@@ -63,7 +63,7 @@ impl Vecs {
                 "coinblocks_created",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -73,7 +73,7 @@ impl Vecs {
                 "coinblocks_stored",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -83,7 +83,7 @@ impl Vecs {
                 "liveliness",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_vaultedness: ComputedVecsFromHeight::forced_import(
@@ -91,7 +91,7 @@ impl Vecs {
                 "vaultedness",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_activity_to_vaultedness_ratio: ComputedVecsFromHeight::forced_import(
@@ -99,7 +99,7 @@ impl Vecs {
                 "activity_to_vaultedness_ratio",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_vaulted_supply: ComputedValueVecsFromHeight::forced_import(
@@ -107,7 +107,7 @@ impl Vecs {
                 "vaulted_supply",
                 true,
                 version + VERSION + Version::ONE,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
                 compute_dollars,
             )?,
@@ -116,7 +116,7 @@ impl Vecs {
                 "active_supply",
                 true,
                 version + VERSION + Version::ONE,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
                 compute_dollars,
             )?,
@@ -125,7 +125,7 @@ impl Vecs {
                 "thermo_cap",
                 true,
                 version + VERSION + Version::ONE,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_investor_cap: ComputedVecsFromHeight::forced_import(
@@ -133,7 +133,7 @@ impl Vecs {
                 "investor_cap",
                 true,
                 version + VERSION + Version::ONE,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_vaulted_cap: ComputedVecsFromHeight::forced_import(
@@ -141,7 +141,7 @@ impl Vecs {
                 "vaulted_cap",
                 true,
                 version + VERSION + Version::ONE,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_active_cap: ComputedVecsFromHeight::forced_import(
@@ -149,7 +149,7 @@ impl Vecs {
                 "active_cap",
                 true,
                 version + VERSION + Version::ONE,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_vaulted_price: ComputedVecsFromHeight::forced_import(
@@ -157,7 +157,7 @@ impl Vecs {
                 "vaulted_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_vaulted_price_ratio: ComputedRatioVecsFromDateIndex::forced_import(
@@ -165,14 +165,14 @@ impl Vecs {
                 "vaulted_price",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_active_price: ComputedVecsFromHeight::forced_import(
                 path,
                 "active_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_active_price_ratio: ComputedRatioVecsFromDateIndex::forced_import(
@@ -180,14 +180,14 @@ impl Vecs {
                 "active_price",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_true_market_mean: ComputedVecsFromHeight::forced_import(
                 path,
                 "true_market_mean",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_true_market_mean_ratio: ComputedRatioVecsFromDateIndex::forced_import(
@@ -195,14 +195,14 @@ impl Vecs {
                 "true_market_mean",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_cointime_value_destroyed: ComputedVecsFromHeight::forced_import(
                 path,
                 "cointime_value_destroyed",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -212,7 +212,7 @@ impl Vecs {
                 "cointime_value_created",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -222,7 +222,7 @@ impl Vecs {
                 "cointime_value_stored",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -232,7 +232,7 @@ impl Vecs {
                 "cointime_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_cointime_cap: ComputedVecsFromHeight::forced_import(
@@ -240,7 +240,7 @@ impl Vecs {
                 "cointime_cap",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_cointime_price_ratio: ComputedRatioVecsFromDateIndex::forced_import(
@@ -248,7 +248,7 @@ impl Vecs {
                 "cointime_price",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
         })
     }
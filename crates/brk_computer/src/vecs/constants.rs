@@ -3,7 +3,7 @@ use std::path::Path;
 use brk_core::{StoredU8, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, AnyVec, Computation, Format};
+use brk_vec::{AnyCollectableVec, AnyVec, Computation, ImportMode};
 
 use super::{
     Indexes,
@@ -26,7 +26,7 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
     ) -> color_eyre::Result<Self> {
         Ok(Self {
             constant_0: ComputedVecsFromHeight::forced_import(
@@ -34,7 +34,7 @@ impl Vecs {
                 "constant_0",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             constant_1: ComputedVecsFromHeight::forced_import(
@@ -42,7 +42,7 @@ impl Vecs {
                 "constant_1",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             constant_50: ComputedVecsFromHeight::forced_import(
@@ -50,7 +50,7 @@ impl Vecs {
                 "constant_50",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             constant_100: ComputedVecsFromHeight::forced_import(
@@ -58,7 +58,7 @@ impl Vecs {
                 "constant_100",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
         })
@@ -0,0 +1,482 @@
+use std::path::Path;
+
+use brk_core::{Bitcoin, DateIndex, Dollars, GroupedByTerm, StoredF64, Version};
+use brk_exit::Exit;
+use brk_indexer::Indexer;
+use brk_vec::{AnyCollectableVec, Computation, EagerVec, ImportMode, StoredIndex, VecIterator};
+
+use super::{
+    Indexes, fetched,
+    grouped::{ComputedVecsFromDateIndex, StorableVecGeneatorOptions},
+    indexes, stateful, transactions,
+};
+
+const VERSION: Version = Version::ZERO;
+
+/// Indicators derived from the coindays destroyed of the whole supply and of the short and long term holders
+#[derive(Clone)]
+pub struct Vecs {
+    pub all: CohortVecs,
+    pub by_term: GroupedByTerm<CohortVecs>,
+}
+
+/// Supply adjusted values use the circulating supply instead of the one of the cohort, so that they add up
+#[derive(Clone)]
+pub struct CohortVecs {
+    /// Coindays destroyed per coin sent, in days
+    pub indexes_to_average_dormancy: ComputedVecsFromDateIndex<StoredF64>,
+    /// Coindays destroyed divided by the circulating supply
+    pub indexes_to_supply_adjusted_cdd: ComputedVecsFromDateIndex<StoredF64>,
+    pub dateindex_to_supply_adjusted_cdd_365d_sma: EagerVec<DateIndex, StoredF64>,
+    /// 1 when the supply adjusted coindays destroyed are above their yearly moving average, 0 otherwise
+    pub indexes_to_binary_cdd: ComputedVecsFromDateIndex<StoredF64>,
+    /// Coindays destroyed times the price
+    pub indexes_to_value_days_destroyed: Option<ComputedVecsFromDateIndex<StoredF64>>,
+    pub dateindex_to_value_days_destroyed_30d_sma: Option<EagerVec<DateIndex, StoredF64>>,
+    pub dateindex_to_value_days_destroyed_365d_sma: Option<EagerVec<DateIndex, StoredF64>>,
+    /// Monthly moving average of the value days destroyed divided by the yearly one
+    pub indexes_to_vdd_multiple: Option<ComputedVecsFromDateIndex<StoredF64>>,
+    /// Price minus the monthly moving average of the value days destroyed per coin, summed since the genesis
+    pub indexes_to_hodl_bank: Option<ComputedVecsFromDateIndex<Dollars>>,
+    /// Price divided by the HODL bank
+    pub indexes_to_reserve_risk: Option<ComputedVecsFromDateIndex<StoredF64>>,
+}
+
+impl CohortVecs {
+    fn forced_import(
+        path: &Path,
+        cohort_name: Option<&str>,
+        version: Version,
+        mode: ImportMode,
+        compute_dollars: bool,
+    ) -> color_eyre::Result<Self> {
+        let suffix = |s: &str| cohort_name.map_or(s.to_string(), |name| format!("{name}_{s}"));
+
+        let import_ratio = |name: &str, options: StorableVecGeneatorOptions| {
+            ComputedVecsFromDateIndex::forced_import(
+                path,
+                &suffix(name),
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                options,
+            )
+        };
+        let import_sma = |name: &str| {
+            EagerVec::forced_import(path, &suffix(name), version + VERSION + Version::ZERO, mode)
+        };
+
+        Ok(Self {
+            indexes_to_average_dormancy: import_ratio(
+                "average_dormancy",
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            indexes_to_supply_adjusted_cdd: import_ratio(
+                "supply_adjusted_cdd",
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            dateindex_to_supply_adjusted_cdd_365d_sma: import_sma("supply_adjusted_cdd_365d_sma")?,
+            indexes_to_binary_cdd: import_ratio(
+                "binary_cdd",
+                StorableVecGeneatorOptions::default().add_average(),
+            )?,
+            indexes_to_value_days_destroyed: compute_dollars.then(|| {
+                import_ratio(
+                    "value_days_destroyed",
+                    StorableVecGeneatorOptions::default()
+                        .add_sum()
+                        .add_cumulative(),
+                )
+                .unwrap()
+            }),
+            dateindex_to_value_days_destroyed_30d_sma: compute_dollars
+                .then(|| import_sma("value_days_destroyed_30d_sma").unwrap()),
+            dateindex_to_value_days_destroyed_365d_sma: compute_dollars
+                .then(|| import_sma("value_days_destroyed_365d_sma").unwrap()),
+            indexes_to_vdd_multiple: compute_dollars.then(|| {
+                import_ratio(
+                    "vdd_multiple",
+                    StorableVecGeneatorOptions::default().add_last(),
+                )
+                .unwrap()
+            }),
+            indexes_to_hodl_bank: compute_dollars.then(|| {
+                ComputedVecsFromDateIndex::forced_import(
+                    path,
+                    &suffix("hodl_bank"),
+                    true,
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    StorableVecGeneatorOptions::default().add_last(),
+                )
+                .unwrap()
+            }),
+            indexes_to_reserve_risk: compute_dollars.then(|| {
+                import_ratio(
+                    "reserve_risk",
+                    StorableVecGeneatorOptions::default().add_last(),
+                )
+                .unwrap()
+            }),
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        starting_indexes: &Indexes,
+        fetched: Option<&fetched::Vecs>,
+        coindays_destroyed: &EagerVec<DateIndex, StoredF64>,
+        sent: &EagerVec<DateIndex, Bitcoin>,
+        supply: &EagerVec<DateIndex, Bitcoin>,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        let mut sent_iter = sent.into_iter();
+        self.indexes_to_average_dormancy.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    coindays_destroyed,
+                    |(di, cdd, ..)| {
+                        (
+                            di,
+                            ratio(f64::from(cdd), f64::from(sent_iter.unwrap_get_inner(di))),
+                        )
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let mut supply_iter = supply.into_iter();
+        self.indexes_to_supply_adjusted_cdd.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    coindays_destroyed,
+                    |(di, cdd, ..)| {
+                        (
+                            di,
+                            ratio(f64::from(cdd), f64::from(supply_iter.unwrap_get_inner(di))),
+                        )
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let supply_adjusted_cdd = self
+            .indexes_to_supply_adjusted_cdd
+            .dateindex
+            .as_ref()
+            .unwrap();
+
+        self.dateindex_to_supply_adjusted_cdd_365d_sma.compute_sma(
+            starting_indexes.dateindex,
+            supply_adjusted_cdd,
+            365,
+            exit,
+        )?;
+
+        let mut sma_iter = self.dateindex_to_supply_adjusted_cdd_365d_sma.into_iter();
+        self.indexes_to_binary_cdd.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    supply_adjusted_cdd,
+                    |(di, cdd, ..)| {
+                        let is_above = cdd > sma_iter.unwrap_get_inner(di);
+                        (di, StoredF64::from(if is_above { 1.0 } else { 0.0 }))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let Some(fetched) = fetched else {
+            return Ok(());
+        };
+
+        let close = fetched.timeindexes_to_close.dateindex.as_ref().unwrap();
+
+        let mut close_iter = close.into_iter();
+        self.indexes_to_value_days_destroyed
+            .as_mut()
+            .unwrap()
+            .compute_all(
+                indexer,
+                indexes,
+                starting_indexes,
+                exit,
+                |vec, _, _, starting_indexes, exit| {
+                    vec.compute_transform(
+                        starting_indexes.dateindex,
+                        coindays_destroyed,
+                        |(di, cdd, ..)| {
+                            let price = f64::from(Dollars::from(close_iter.unwrap_get_inner(di)));
+                            (di, StoredF64::from(f64::from(cdd) * price))
+                        },
+                        exit,
+                    )
+                },
+            )?;
+
+        let value_days_destroyed = self
+            .indexes_to_value_days_destroyed
+            .as_ref()
+            .unwrap()
+            .dateindex
+            .as_ref()
+            .unwrap();
+
+        self.dateindex_to_value_days_destroyed_30d_sma
+            .as_mut()
+            .unwrap()
+            .compute_sma(starting_indexes.dateindex, value_days_destroyed, 30, exit)?;
+        self.dateindex_to_value_days_destroyed_365d_sma
+            .as_mut()
+            .unwrap()
+            .compute_sma(starting_indexes.dateindex, value_days_destroyed, 365, exit)?;
+
+        let vdd_30d_sma = self
+            .dateindex_to_value_days_destroyed_30d_sma
+            .as_ref()
+            .unwrap();
+
+        let mut vdd_365d_sma_iter = self
+            .dateindex_to_value_days_destroyed_365d_sma
+            .as_ref()
+            .unwrap()
+            .into_iter();
+        self.indexes_to_vdd_multiple.as_mut().unwrap().compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    vdd_30d_sma,
+                    |(di, vdd_30d_sma, ..)| {
+                        (
+                            di,
+                            ratio(
+                                f64::from(vdd_30d_sma),
+                                f64::from(vdd_365d_sma_iter.unwrap_get_inner(di)),
+                            ),
+                        )
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let mut vdd_30d_sma_iter = vdd_30d_sma.into_iter();
+        let mut supply_iter = supply.into_iter();
+        let mut hodl_bank = None;
+        self.indexes_to_hodl_bank.as_mut().unwrap().compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    close,
+                    |(di, close, this)| {
+                        let prev = *hodl_bank.get_or_insert_with(|| {
+                            di.decremented().map_or(0.0, |prev| {
+                                f64::from(this.into_iter().unwrap_get_inner(prev))
+                            })
+                        });
+
+                        let supply = f64::from(supply_iter.unwrap_get_inner(di));
+                        let vdd_per_coin = if supply == 0.0 {
+                            0.0
+                        } else {
+                            f64::from(vdd_30d_sma_iter.unwrap_get_inner(di)) / supply
+                        };
+
+                        let next = prev + f64::from(Dollars::from(close)) - vdd_per_coin;
+                        hodl_bank.replace(next);
+                        (di, Dollars::from(next))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let mut hodl_bank_iter = self
+            .indexes_to_hodl_bank
+            .as_ref()
+            .unwrap()
+            .dateindex
+            .as_ref()
+            .unwrap()
+            .into_iter();
+        self.indexes_to_reserve_risk.as_mut().unwrap().compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    close,
+                    |(di, close, ..)| {
+                        (
+                            di,
+                            ratio(
+                                f64::from(Dollars::from(close)),
+                                f64::from(hodl_bank_iter.unwrap_get_inner(di)),
+                            ),
+                        )
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        Ok(())
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.indexes_to_average_dormancy.vecs(),
+            self.indexes_to_supply_adjusted_cdd.vecs(),
+            vec![&self.dateindex_to_supply_adjusted_cdd_365d_sma as &dyn AnyCollectableVec],
+            self.indexes_to_binary_cdd.vecs(),
+            self.indexes_to_value_days_destroyed
+                .as_ref()
+                .map_or(vec![], |v| v.vecs()),
+            self.dateindex_to_value_days_destroyed_30d_sma
+                .as_ref()
+                .map_or(vec![], |v| vec![v as &dyn AnyCollectableVec]),
+            self.dateindex_to_value_days_destroyed_365d_sma
+                .as_ref()
+                .map_or(vec![], |v| vec![v as &dyn AnyCollectableVec]),
+            self.indexes_to_vdd_multiple
+                .as_ref()
+                .map_or(vec![], |v| v.vecs()),
+            self.indexes_to_hodl_bank
+                .as_ref()
+                .map_or(vec![], |v| v.vecs()),
+            self.indexes_to_reserve_risk
+                .as_ref()
+                .map_or(vec![], |v| v.vecs()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        _computation: Computation,
+        mode: ImportMode,
+        fetched: Option<&fetched::Vecs>,
+    ) -> color_eyre::Result<Self> {
+        let compute_dollars = fetched.is_some();
+
+        Ok(Self {
+            all: CohortVecs::forced_import(
+                path,
+                None,
+                version + VERSION + Version::ZERO,
+                mode,
+                compute_dollars,
+            )?,
+            by_term: GroupedByTerm {
+                short: CohortVecs::forced_import(
+                    path,
+                    Some("sth"),
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    compute_dollars,
+                )?,
+                long: CohortVecs::forced_import(
+                    path,
+                    Some("lth"),
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    compute_dollars,
+                )?,
+            },
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        starting_indexes: &Indexes,
+        fetched: Option<&fetched::Vecs>,
+        transactions: &transactions::Vecs,
+        stateful: &stateful::Vecs,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        let supply = transactions
+            .indexes_to_subsidy
+            .bitcoin
+            .dateindex
+            .unwrap_cumulative();
+
+        let utxos_vecs = &stateful.utxos_vecs;
+
+        [
+            (&mut self.all, &utxos_vecs.all.1),
+            (&mut self.by_term.short, &utxos_vecs.by_term.short.1),
+            (&mut self.by_term.long, &utxos_vecs.by_term.long.1),
+        ]
+        .into_iter()
+        .try_for_each(|(vecs, cohort)| {
+            vecs.compute(
+                indexer,
+                indexes,
+                starting_indexes,
+                fetched,
+                cohort.indexes_to_coindays_destroyed.dateindex.unwrap_sum(),
+                cohort.indexes_to_sent.bitcoin.dateindex.unwrap_sum(),
+                supply,
+                exit,
+            )
+        })
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.all.vecs(),
+            self.by_term.short.vecs(),
+            self.by_term.long.vecs(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> StoredF64 {
+    StoredF64::from(if denominator == 0.0 {
+        f64::NAN
+    } else {
+        numerator / denominator
+    })
+}
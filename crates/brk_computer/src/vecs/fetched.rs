@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{fs, io, path::Path};
 
 use brk_core::{
     Cents, Close, DateIndex, DecadeIndex, DifficultyEpoch, Dollars, Height, High, Low, MonthIndex,
@@ -7,7 +7,11 @@ use brk_core::{
 use brk_exit::Exit;
 use brk_fetcher::Fetcher;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, AnyIterableVec, Computation, EagerVec, Format, StoredIndex};
+use brk_vec::{
+    AnyCollectableVec, AnyIterableVec, AnyVec, ColumnarVec, Computation, EagerVec, ImportMode,
+    StoredIndex,
+};
+use log::info;
 
 use super::{
     Indexes,
@@ -17,22 +21,18 @@ use super::{
     indexes,
 };
 
+/// Fetched prices, each of open, high, low and close is its own vec
+pub type OHLCCentsVec<I> =
+    ColumnarVec<I, OHLCCents, (Open<Cents>, High<Cents>, Low<Cents>, Close<Cents>)>;
+
 #[derive(Clone)]
 pub struct Vecs {
-    pub dateindex_to_close_in_cents: EagerVec<DateIndex, Close<Cents>>,
-    pub dateindex_to_high_in_cents: EagerVec<DateIndex, High<Cents>>,
-    pub dateindex_to_low_in_cents: EagerVec<DateIndex, Low<Cents>>,
     pub dateindex_to_ohlc: EagerVec<DateIndex, OHLCDollars>,
     pub dateindex_to_ohlc_in_sats: EagerVec<DateIndex, OHLCSats>,
-    pub dateindex_to_ohlc_in_cents: EagerVec<DateIndex, OHLCCents>,
-    pub dateindex_to_open_in_cents: EagerVec<DateIndex, Open<Cents>>,
-    pub height_to_close_in_cents: EagerVec<Height, Close<Cents>>,
-    pub height_to_high_in_cents: EagerVec<Height, High<Cents>>,
-    pub height_to_low_in_cents: EagerVec<Height, Low<Cents>>,
+    pub dateindex_to_ohlc_in_cents: OHLCCentsVec<DateIndex>,
     pub height_to_ohlc: EagerVec<Height, OHLCDollars>,
     pub height_to_ohlc_in_sats: EagerVec<Height, OHLCSats>,
-    pub height_to_ohlc_in_cents: EagerVec<Height, OHLCCents>,
-    pub height_to_open_in_cents: EagerVec<Height, Open<Cents>>,
+    pub height_to_ohlc_in_cents: OHLCCentsVec<Height>,
     pub timeindexes_to_close: ComputedVecsFromDateIndex<Close<Dollars>>,
     pub timeindexes_to_high: ComputedVecsFromDateIndex<High<Dollars>>,
     pub timeindexes_to_low: ComputedVecsFromDateIndex<Low<Dollars>>,
@@ -63,6 +63,9 @@ pub struct Vecs {
     // pub halvingepoch_to_ohlc_in_sats: StorableVec<Halvingepoch, OHLCSats>,
     pub decadeindex_to_ohlc: EagerVec<DecadeIndex, OHLCDollars>,
     pub decadeindex_to_ohlc_in_sats: EagerVec<DecadeIndex, OHLCSats>,
+    /// Fetched OHLCs stored whole by previous versions, moved to the columns by the next compute
+    height_to_ohlc_rows_in_cents: Option<EagerVec<Height, OHLCCents>>,
+    dateindex_to_ohlc_rows_in_cents: Option<EagerVec<DateIndex, OHLCCents>>,
 }
 
 const VERSION: Version = Version::ZERO;
@@ -73,103 +76,90 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
     ) -> color_eyre::Result<Self> {
         let mut fetched_path = path.to_owned();
         fetched_path.pop();
         fetched_path = fetched_path.join("fetched");
 
+        if !mode.read_only {
+            // Computed from the fetched OHLCs by previous versions, they're the columns of the fetched ones now
+            for name in [
+                "open_in_cents",
+                "high_in_cents",
+                "low_in_cents",
+                "close_in_cents",
+            ] {
+                remove_vec::<Height>(&path.join(name))?;
+                remove_vec::<DateIndex>(&path.join(name))?;
+            }
+        }
+
         Ok(Self {
-            dateindex_to_ohlc_in_cents: EagerVec::forced_import(
+            height_to_ohlc_rows_in_cents: import_ohlc_rows_in_cents(
+                &fetched_path,
+                version + VERSION + Version::ZERO,
+                mode,
+            )?,
+            dateindex_to_ohlc_rows_in_cents: import_ohlc_rows_in_cents(
+                &fetched_path,
+                version + VERSION + Version::ZERO,
+                mode,
+            )?,
+            dateindex_to_ohlc_in_cents: OHLCCentsVec::forced_import(
                 &fetched_path,
                 "ohlc_in_cents",
+                &[
+                    "open_in_cents",
+                    "high_in_cents",
+                    "low_in_cents",
+                    "close_in_cents",
+                ],
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             dateindex_to_ohlc: EagerVec::forced_import(
                 path,
                 "ohlc",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             dateindex_to_ohlc_in_sats: EagerVec::forced_import(
                 path,
                 "ohlc_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
-            )?,
-            dateindex_to_close_in_cents: EagerVec::forced_import(
-                path,
-                "close_in_cents",
-                version + VERSION + Version::ZERO,
-                format,
-            )?,
-            dateindex_to_high_in_cents: EagerVec::forced_import(
-                path,
-                "high_in_cents",
-                version + VERSION + Version::ZERO,
-                format,
-            )?,
-            dateindex_to_low_in_cents: EagerVec::forced_import(
-                path,
-                "low_in_cents",
-                version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
-            dateindex_to_open_in_cents: EagerVec::forced_import(
-                path,
-                "open_in_cents",
-                version + VERSION + Version::ZERO,
-                format,
-            )?,
-            height_to_ohlc_in_cents: EagerVec::forced_import(
+            height_to_ohlc_in_cents: OHLCCentsVec::forced_import(
                 &fetched_path,
                 "ohlc_in_cents",
+                &[
+                    "open_in_cents",
+                    "high_in_cents",
+                    "low_in_cents",
+                    "close_in_cents",
+                ],
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_ohlc: EagerVec::forced_import(
                 path,
                 "ohlc",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_ohlc_in_sats: EagerVec::forced_import(
                 path,
                 "ohlc_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
-            )?,
-            height_to_close_in_cents: EagerVec::forced_import(
-                path,
-                "close_in_cents",
-                version + VERSION + Version::ZERO,
-                format,
-            )?,
-            height_to_high_in_cents: EagerVec::forced_import(
-                path,
-                "high_in_cents",
-                version + VERSION + Version::ZERO,
-                format,
-            )?,
-            height_to_low_in_cents: EagerVec::forced_import(
-                path,
-                "low_in_cents",
-                version + VERSION + Version::ZERO,
-                format,
-            )?,
-            height_to_open_in_cents: EagerVec::forced_import(
-                path,
-                "open_in_cents",
-                version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             timeindexes_to_open: ComputedVecsFromDateIndex::forced_import(
                 path,
                 "open",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_first(),
             )?,
             timeindexes_to_high: ComputedVecsFromDateIndex::forced_import(
@@ -177,7 +167,7 @@ impl Vecs {
                 "high",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_max(),
             )?,
             timeindexes_to_low: ComputedVecsFromDateIndex::forced_import(
@@ -185,7 +175,7 @@ impl Vecs {
                 "low",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_min(),
             )?,
             timeindexes_to_close: ComputedVecsFromDateIndex::forced_import(
@@ -193,7 +183,7 @@ impl Vecs {
                 "close",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             timeindexes_to_open_in_sats: ComputedVecsFromDateIndex::forced_import(
@@ -201,7 +191,7 @@ impl Vecs {
                 "open_in_sats",
                 true,
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_first(),
             )?,
             timeindexes_to_high_in_sats: ComputedVecsFromDateIndex::forced_import(
@@ -209,7 +199,7 @@ impl Vecs {
                 "high_in_sats",
                 true,
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_max(),
             )?,
             timeindexes_to_low_in_sats: ComputedVecsFromDateIndex::forced_import(
@@ -217,7 +207,7 @@ impl Vecs {
                 "low_in_sats",
                 true,
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_min(),
             )?,
             timeindexes_to_close_in_sats: ComputedVecsFromDateIndex::forced_import(
@@ -225,138 +215,138 @@ impl Vecs {
                 "close_in_sats",
                 true,
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             chainindexes_to_open: ComputedVecsFromHeightStrict::forced_import(
                 path,
                 "open",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_first(),
             )?,
             chainindexes_to_high: ComputedVecsFromHeightStrict::forced_import(
                 path,
                 "high",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_max(),
             )?,
             chainindexes_to_low: ComputedVecsFromHeightStrict::forced_import(
                 path,
                 "low",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_min(),
             )?,
             chainindexes_to_close: ComputedVecsFromHeightStrict::forced_import(
                 path,
                 "close",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             chainindexes_to_open_in_sats: ComputedVecsFromHeightStrict::forced_import(
                 path,
                 "open_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_first(),
             )?,
             chainindexes_to_high_in_sats: ComputedVecsFromHeightStrict::forced_import(
                 path,
                 "high_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_max(),
             )?,
             chainindexes_to_low_in_sats: ComputedVecsFromHeightStrict::forced_import(
                 path,
                 "low_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_min(),
             )?,
             chainindexes_to_close_in_sats: ComputedVecsFromHeightStrict::forced_import(
                 path,
                 "close_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             weekindex_to_ohlc: EagerVec::forced_import(
                 path,
                 "ohlc",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             weekindex_to_ohlc_in_sats: EagerVec::forced_import(
                 path,
                 "ohlc_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
             )?,
             difficultyepoch_to_ohlc: EagerVec::forced_import(
                 path,
                 "ohlc",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             difficultyepoch_to_ohlc_in_sats: EagerVec::forced_import(
                 path,
                 "ohlc_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
             )?,
             monthindex_to_ohlc: EagerVec::forced_import(
                 path,
                 "ohlc",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             monthindex_to_ohlc_in_sats: EagerVec::forced_import(
                 path,
                 "ohlc_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
             )?,
             quarterindex_to_ohlc: EagerVec::forced_import(
                 path,
                 "ohlc",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             quarterindex_to_ohlc_in_sats: EagerVec::forced_import(
                 path,
                 "ohlc_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
             )?,
             yearindex_to_ohlc: EagerVec::forced_import(
                 path,
                 "ohlc",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             yearindex_to_ohlc_in_sats: EagerVec::forced_import(
                 path,
                 "ohlc_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
             )?,
             // halvingepoch_to_ohlc: StorableVec::forced_import(path,
-            // "halvingepoch_to_ohlc"), version + VERSION + Version::ZERO, format)?,
+            // "halvingepoch_to_ohlc"), version + VERSION + Version::ZERO, mode)?,
             decadeindex_to_ohlc: EagerVec::forced_import(
                 path,
                 "ohlc",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             decadeindex_to_ohlc_in_sats: EagerVec::forced_import(
                 path,
                 "ohlc_in_sats",
                 version + VERSION + VERSION_IN_SATS + Version::ZERO,
-                format,
+                mode,
             )?,
         })
     }
@@ -369,6 +359,17 @@ impl Vecs {
         fetcher: &mut Fetcher,
         exit: &Exit,
     ) -> color_eyre::Result<()> {
+        if let Some(rows) = self.height_to_ohlc_rows_in_cents.take() {
+            self.height_to_ohlc_in_cents.compute_from_rows(
+                &rows,
+                &indexer.vecs.height_to_timestamp,
+                exit,
+            )?;
+            if self.height_to_ohlc_in_cents.len() >= rows.len() {
+                remove_vec::<Height>(rows.path().parent().unwrap())?;
+            }
+        }
+
         let mut height_to_timestamp_iter = indexer.vecs.height_to_timestamp.iter();
         self.height_to_ohlc_in_cents.compute_transform(
             starting_indexes.height,
@@ -387,34 +388,6 @@ impl Vecs {
             exit,
         )?;
 
-        self.height_to_open_in_cents.compute_transform(
-            starting_indexes.height,
-            &self.height_to_ohlc_in_cents,
-            |(di, ohlc, ..)| (di, ohlc.open),
-            exit,
-        )?;
-
-        self.height_to_high_in_cents.compute_transform(
-            starting_indexes.height,
-            &self.height_to_ohlc_in_cents,
-            |(di, ohlc, ..)| (di, ohlc.high),
-            exit,
-        )?;
-
-        self.height_to_low_in_cents.compute_transform(
-            starting_indexes.height,
-            &self.height_to_ohlc_in_cents,
-            |(di, ohlc, ..)| (di, ohlc.low),
-            exit,
-        )?;
-
-        self.height_to_close_in_cents.compute_transform(
-            starting_indexes.height,
-            &self.height_to_ohlc_in_cents,
-            |(di, ohlc, ..)| (di, ohlc.close),
-            exit,
-        )?;
-
         self.height_to_ohlc.compute_transform(
             starting_indexes.height,
             &self.height_to_ohlc_in_cents,
@@ -422,17 +395,28 @@ impl Vecs {
             exit,
         )?;
 
+        if let Some(rows) = self.dateindex_to_ohlc_rows_in_cents.take() {
+            self.dateindex_to_ohlc_in_cents.compute_from_rows(
+                &rows,
+                &indexes.dateindex_to_date,
+                exit,
+            )?;
+            if self.dateindex_to_ohlc_in_cents.len() >= rows.len() {
+                remove_vec::<DateIndex>(rows.path().parent().unwrap())?;
+            }
+        }
+
         self.dateindex_to_ohlc_in_cents.compute_transform(
             starting_indexes.dateindex,
             &indexes.dateindex_to_date,
             |(di, d, this)| {
                 let mut ohlc = fetcher.get_date(d).unwrap();
                 if let Some(prev) = di.decremented() {
-                    let prev_open = *this
-                        .get_or_read(prev, &this.mmap().load())
-                        .unwrap()
+                    let close = &this.columns().3;
+                    let prev_open = **close
+                        .get_or_read(prev, &close.mmap().load())
                         .unwrap()
-                        .close;
+                        .unwrap();
                     *ohlc.open = prev_open;
                     *ohlc.high = (*ohlc.high).max(prev_open);
                     *ohlc.low = (*ohlc.low).min(prev_open);
@@ -442,34 +426,6 @@ impl Vecs {
             exit,
         )?;
 
-        self.dateindex_to_open_in_cents.compute_transform(
-            starting_indexes.dateindex,
-            &self.dateindex_to_ohlc_in_cents,
-            |(di, ohlc, ..)| (di, ohlc.open),
-            exit,
-        )?;
-
-        self.dateindex_to_high_in_cents.compute_transform(
-            starting_indexes.dateindex,
-            &self.dateindex_to_ohlc_in_cents,
-            |(di, ohlc, ..)| (di, ohlc.high),
-            exit,
-        )?;
-
-        self.dateindex_to_low_in_cents.compute_transform(
-            starting_indexes.dateindex,
-            &self.dateindex_to_ohlc_in_cents,
-            |(di, ohlc, ..)| (di, ohlc.low),
-            exit,
-        )?;
-
-        self.dateindex_to_close_in_cents.compute_transform(
-            starting_indexes.dateindex,
-            &self.dateindex_to_ohlc_in_cents,
-            |(di, ohlc, ..)| (di, ohlc.close),
-            exit,
-        )?;
-
         self.dateindex_to_ohlc.compute_transform(
             starting_indexes.dateindex,
             &self.dateindex_to_ohlc_in_cents,
@@ -1111,18 +1067,8 @@ impl Vecs {
     pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
         vec![
             vec![
-                &self.dateindex_to_close_in_cents as &dyn AnyCollectableVec,
-                &self.dateindex_to_high_in_cents,
-                &self.dateindex_to_low_in_cents,
-                &self.dateindex_to_ohlc,
-                &self.dateindex_to_ohlc_in_cents,
-                &self.dateindex_to_open_in_cents,
-                &self.height_to_close_in_cents,
-                &self.height_to_high_in_cents,
-                &self.height_to_low_in_cents,
+                &self.dateindex_to_ohlc as &dyn AnyCollectableVec,
                 &self.height_to_ohlc,
-                &self.height_to_ohlc_in_cents,
-                &self.height_to_open_in_cents,
                 &self.weekindex_to_ohlc,
                 &self.difficultyepoch_to_ohlc,
                 &self.monthindex_to_ohlc,
@@ -1140,6 +1086,8 @@ impl Vecs {
                 // &self.halvingepoch_to_ohlc_in_sats,
                 &self.decadeindex_to_ohlc_in_sats,
             ],
+            self.dateindex_to_ohlc_in_cents.vecs(),
+            self.height_to_ohlc_in_cents.vecs(),
             self.timeindexes_to_close.vecs(),
             self.timeindexes_to_high.vecs(),
             self.timeindexes_to_low.vecs(),
@@ -1162,3 +1110,59 @@ impl Vecs {
         .collect::<Vec<_>>()
     }
 }
+
+/// Opens the fetched OHLCs that previous versions stored whole under `ohlc_in_cents`, if any, an old vec which can't
+/// be read anymore is reset and then removed without anything to move
+fn import_ohlc_rows_in_cents<I>(
+    fetched_path: &Path,
+    version: Version,
+    mode: ImportMode,
+) -> color_eyre::Result<Option<EagerVec<I, OHLCCents>>>
+where
+    I: StoredIndex,
+{
+    if mode.read_only
+        || !fetched_path
+            .join("ohlc_in_cents")
+            .join(I::to_string())
+            .exists()
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(EagerVec::forced_import(
+        fetched_path,
+        "ohlc_in_cents",
+        version,
+        mode,
+    )?))
+}
+
+/// Removes the files of the vec of `folder` indexed by `I` and the folder once empty
+pub fn remove_vec<I>(folder: &Path) -> io::Result<()>
+where
+    I: StoredIndex,
+{
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Ok(());
+    };
+
+    let index = I::to_string();
+    let prefix = format!("{index}-");
+
+    for entry in entries {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        if file_name == index || file_name.starts_with(&prefix) {
+            info!("Removing {}...", entry.path().display());
+            fs::remove_file(entry.path())?;
+        }
+    }
+
+    if fs::read_dir(folder)?.next().is_none() {
+        fs::remove_dir(folder)?;
+    }
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use brk_core::{CheckedSub, Result, StoredUsize, Version};
 use brk_exit::Exit;
-use brk_vec::{AnyCollectableVec, AnyIterableVec, EagerVec, Format, StoredIndex, StoredType};
+use brk_vec::{AnyCollectableVec, AnyIterableVec, EagerVec, ImportMode, StoredIndex, StoredType};
 use color_eyre::eyre::ContextCompat;
 
 use crate::utils::get_percentile;
@@ -40,7 +40,7 @@ where
         path: &Path,
         name: &str,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         options: StorableVecGeneatorOptions,
     ) -> color_eyre::Result<Self> {
         let only_one_active = options.is_only_one_active();
@@ -72,14 +72,14 @@ where
                         path,
                         &maybe_prefix("first"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
             }),
             last: options.last.then(|| {
                 Box::new(
-                    EagerVec::forced_import(path, name, version + Version::ZERO, format).unwrap(),
+                    EagerVec::forced_import(path, name, version + Version::ZERO, mode).unwrap(),
                 )
             }),
             min: options.min.then(|| {
@@ -88,7 +88,7 @@ where
                         path,
                         &maybe_suffix("min"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -99,7 +99,7 @@ where
                         path,
                         &maybe_suffix("max"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -110,7 +110,7 @@ where
                         path,
                         &maybe_suffix("median"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -121,7 +121,7 @@ where
                         path,
                         &maybe_suffix("average"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -136,7 +136,7 @@ where
                             maybe_suffix("sum")
                         }),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -147,7 +147,7 @@ where
                         path,
                         &prefix("cumulative"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -158,7 +158,7 @@ where
                         path,
                         &maybe_suffix("90p"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -169,7 +169,7 @@ where
                         path,
                         &maybe_suffix("75p"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -180,7 +180,7 @@ where
                         path,
                         &maybe_suffix("25p"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
@@ -191,7 +191,7 @@ where
                         path,
                         &maybe_suffix("10p"),
                         version + VERSION + Version::ZERO,
-                        format,
+                        mode,
                     )
                     .unwrap(),
                 )
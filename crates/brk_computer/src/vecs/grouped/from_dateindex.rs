@@ -5,7 +5,7 @@ use brk_core::{
 };
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, AnyIterableVec, EagerVec, Format};
+use brk_vec::{AnyCollectableVec, AnyIterableVec, EagerVec, ImportMode};
 
 use crate::vecs::{Indexes, indexes};
 
@@ -36,18 +36,18 @@ where
         name: &str,
         compute_source: bool,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         options: StorableVecGeneatorOptions,
     ) -> color_eyre::Result<Self> {
         let dateindex = compute_source.then(|| {
-            EagerVec::forced_import(path, name, version + VERSION + Version::ZERO, format).unwrap()
+            EagerVec::forced_import(path, name, version + VERSION + Version::ZERO, mode).unwrap()
         });
 
         let dateindex_extra = ComputedVecBuilder::forced_import(
             path,
             name,
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             options.copy_self_extra(),
         )?;
 
@@ -60,35 +60,35 @@ where
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             monthindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             quarterindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             yearindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             decadeindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
         })
@@ -3,7 +3,7 @@ use std::path::Path;
 use brk_core::{DifficultyEpoch, Height, Result, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, EagerVec, Format};
+use brk_vec::{AnyCollectableVec, EagerVec, ImportMode};
 
 use crate::vecs::{Indexes, indexes};
 
@@ -31,17 +31,16 @@ where
         path: &Path,
         name: &str,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         options: StorableVecGeneatorOptions,
     ) -> color_eyre::Result<Self> {
-        let height =
-            EagerVec::forced_import(path, name, version + VERSION + Version::ZERO, format)?;
+        let height = EagerVec::forced_import(path, name, version + VERSION + Version::ZERO, mode)?;
 
         let height_extra = ComputedVecBuilder::forced_import(
             path,
             name,
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             options.copy_self_extra(),
         )?;
 
@@ -54,10 +53,10 @@ where
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
-            // halvingepoch: StorableVecGeneator::forced_import(path, name, version + VERSION + Version::ZERO, format, options)?,
+            // halvingepoch: StorableVecGeneator::forced_import(path, name, version + VERSION + Version::ZERO, mode, options)?,
         })
     }
 
@@ -7,7 +7,7 @@ use brk_core::{
 use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_vec::{
-    AnyCollectableVec, AnyVec, CollectableVec, EagerVec, Format, StoredIndex, VecIterator,
+    AnyCollectableVec, AnyVec, CollectableVec, EagerVec, ImportMode, StoredIndex, VecIterator,
 };
 
 use crate::vecs::{Indexes, fetched, indexes};
@@ -43,12 +43,12 @@ where
         name: &str,
         compute_source: bool,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         options: StorableVecGeneatorOptions,
     ) -> color_eyre::Result<Self> {
         let txindex = compute_source.then(|| {
             Box::new(
-                EagerVec::forced_import(path, name, version + VERSION + Version::ZERO, format)
+                EagerVec::forced_import(path, name, version + VERSION + Version::ZERO, mode)
                     .unwrap(),
             )
         });
@@ -57,7 +57,7 @@ where
             path,
             name,
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             options,
         )?;
 
@@ -70,50 +70,50 @@ where
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             weekindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             difficultyepoch: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             monthindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             quarterindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             yearindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
-            // halvingepoch: StorableVecGeneator::forced_import(path, name, version + VERSION + Version::ZERO, format, options)?,
+            // halvingepoch: StorableVecGeneator::forced_import(path, name, version + VERSION + Version::ZERO, mode, options)?,
             decadeindex: ComputedVecBuilder::forced_import(
                 path,
                 name,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
         })
@@ -4,7 +4,7 @@ use brk_core::{Date, DateIndex, Dollars, Result, StoredF32, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_vec::{
-    AnyCollectableVec, AnyIterableVec, AnyVec, CollectableVec, EagerVec, Format, StoredIndex,
+    AnyCollectableVec, AnyIterableVec, AnyVec, CollectableVec, EagerVec, ImportMode, StoredIndex,
     VecIterator,
 };
 
@@ -66,7 +66,7 @@ impl ComputedRatioVecsFromDateIndex {
         name: &str,
         compute_source: bool,
         version: Version,
-        format: Format,
+        mode: ImportMode,
     ) -> color_eyre::Result<Self> {
         let options = StorableVecGeneatorOptions::default().add_last();
 
@@ -77,7 +77,7 @@ impl ComputedRatioVecsFromDateIndex {
                     name,
                     true,
                     version + VERSION,
-                    format,
+                    mode,
                     options,
                 )
                 .unwrap()
@@ -87,7 +87,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_sma: ComputedVecsFromDateIndex::forced_import(
@@ -95,7 +95,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_sma"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_1w_sma: ComputedVecsFromDateIndex::forced_import(
@@ -103,7 +103,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_1w_sma"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_1m_sma: ComputedVecsFromDateIndex::forced_import(
@@ -111,7 +111,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_1m_sma"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_1y_sma: ComputedVecsFromDateIndex::forced_import(
@@ -119,7 +119,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_1y_sma"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_4y_sma: ComputedVecsFromDateIndex::forced_import(
@@ -127,7 +127,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_4y_sma"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_1y_sma_momentum_oscillator: ComputedVecsFromDateIndex::forced_import(
@@ -135,7 +135,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_1y_sma_momentum_oscillator"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_sd: ComputedVecsFromDateIndex::forced_import(
@@ -143,7 +143,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_4y_sd: ComputedVecsFromDateIndex::forced_import(
@@ -151,7 +151,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_4y_sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_1y_sd: ComputedVecsFromDateIndex::forced_import(
@@ -159,7 +159,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_1y_sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p99_9: ComputedVecsFromDateIndex::forced_import(
@@ -167,7 +167,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p99_9"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p99_5: ComputedVecsFromDateIndex::forced_import(
@@ -175,7 +175,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p99_5"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p99: ComputedVecsFromDateIndex::forced_import(
@@ -183,7 +183,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p99"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p1: ComputedVecsFromDateIndex::forced_import(
@@ -191,7 +191,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p1"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p0_5: ComputedVecsFromDateIndex::forced_import(
@@ -199,7 +199,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p0_5"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p0_1: ComputedVecsFromDateIndex::forced_import(
@@ -207,7 +207,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p0_1"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p1sd: ComputedVecsFromDateIndex::forced_import(
@@ -215,7 +215,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p1sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p2sd: ComputedVecsFromDateIndex::forced_import(
@@ -223,7 +223,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p2sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p3sd: ComputedVecsFromDateIndex::forced_import(
@@ -231,7 +231,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p3sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_m1sd: ComputedVecsFromDateIndex::forced_import(
@@ -239,7 +239,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_m1sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_m2sd: ComputedVecsFromDateIndex::forced_import(
@@ -247,7 +247,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_m2sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_m3sd: ComputedVecsFromDateIndex::forced_import(
@@ -255,7 +255,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_m3sd"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p99_9_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -263,7 +263,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p99_9_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p99_5_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -271,7 +271,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p99_5_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p99_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -279,7 +279,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p99_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p1_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -287,7 +287,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p1_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p0_5_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -295,7 +295,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p0_5_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p0_1_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -303,7 +303,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p0_1_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p1sd_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -311,7 +311,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p1sd_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p2sd_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -319,7 +319,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p2sd_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_p3sd_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -327,7 +327,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_p3sd_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_m1sd_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -335,7 +335,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_m1sd_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_m2sd_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -343,7 +343,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_m2sd_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_m3sd_as_price: ComputedVecsFromDateIndex::forced_import(
@@ -351,7 +351,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_m3sd_as_price"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_zscore: ComputedVecsFromDateIndex::forced_import(
@@ -359,7 +359,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_zscore"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_4y_zscore: ComputedVecsFromDateIndex::forced_import(
@@ -367,7 +367,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_4y_zscore"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
             ratio_1y_zscore: ComputedVecsFromDateIndex::forced_import(
@@ -375,7 +375,7 @@ impl ComputedRatioVecsFromDateIndex {
                 &format!("{name}_ratio_1y_zscore"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 options,
             )?,
         })
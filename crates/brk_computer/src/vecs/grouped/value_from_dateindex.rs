@@ -3,7 +3,7 @@ use std::path::Path;
 use brk_core::{Bitcoin, DateIndex, Dollars, Result, Sats, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, CollectableVec, EagerVec, Format, StoredVec};
+use brk_vec::{AnyCollectableVec, CollectableVec, EagerVec, ImportMode, StoredVec};
 
 use crate::vecs::{Indexes, fetched, grouped::ComputedVecsFromDateIndex, indexes};
 
@@ -24,7 +24,7 @@ impl ComputedValueVecsFromDateIndex {
         name: &str,
         compute_source: bool,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         options: StorableVecGeneatorOptions,
         compute_dollars: bool,
     ) -> color_eyre::Result<Self> {
@@ -34,7 +34,7 @@ impl ComputedValueVecsFromDateIndex {
                 name,
                 compute_source,
                 version + VERSION,
-                format,
+                mode,
                 options,
             )?,
             bitcoin: ComputedVecsFromDateIndex::forced_import(
@@ -42,7 +42,7 @@ impl ComputedValueVecsFromDateIndex {
                 &format!("{name}_in_btc"),
                 true,
                 version + VERSION,
-                format,
+                mode,
                 options,
             )?,
             dollars: compute_dollars.then(|| {
@@ -51,7 +51,7 @@ impl ComputedValueVecsFromDateIndex {
                     &format!("{name}_in_usd"),
                     true,
                     version + VERSION,
-                    format,
+                    mode,
                     options,
                 )
                 .unwrap()
@@ -134,16 +134,16 @@ impl ComputedValueVecsFromDateIndex {
             )?;
         }
 
-        let dateindex_to_bitcoin = self.bitcoin.dateindex.as_ref().unwrap();
-        let dateindex_to_close = fetched
-            .as_ref()
-            .unwrap()
-            .timeindexes_to_close
-            .dateindex
-            .as_ref()
-            .unwrap();
-
         if let Some(dollars) = self.dollars.as_mut() {
+            let dateindex_to_bitcoin = self.bitcoin.dateindex.as_ref().unwrap();
+            let dateindex_to_close = fetched
+                .as_ref()
+                .unwrap()
+                .timeindexes_to_close
+                .dateindex
+                .as_ref()
+                .unwrap();
+
             dollars.compute_all(
                 indexer,
                 indexes,
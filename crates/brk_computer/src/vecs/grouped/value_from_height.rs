@@ -3,7 +3,7 @@ use std::path::Path;
 use brk_core::{Bitcoin, Dollars, Height, Result, Sats, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, CollectableVec, EagerVec, Format, StoredVec};
+use brk_vec::{AnyCollectableVec, CollectableVec, EagerVec, ImportMode, StoredVec};
 
 use crate::vecs::{Indexes, fetched, indexes};
 
@@ -24,7 +24,7 @@ impl ComputedValueVecsFromHeight {
         name: &str,
         compute_source: bool,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         options: StorableVecGeneatorOptions,
         compute_dollars: bool,
     ) -> color_eyre::Result<Self> {
@@ -34,7 +34,7 @@ impl ComputedValueVecsFromHeight {
                 name,
                 compute_source,
                 version + VERSION,
-                format,
+                mode,
                 options,
             )?,
             bitcoin: ComputedVecsFromHeight::forced_import(
@@ -42,7 +42,7 @@ impl ComputedValueVecsFromHeight {
                 &format!("{name}_in_btc"),
                 true,
                 version + VERSION,
-                format,
+                mode,
                 options,
             )?,
             dollars: compute_dollars.then(|| {
@@ -51,7 +51,7 @@ impl ComputedValueVecsFromHeight {
                     &format!("{name}_in_usd"),
                     true,
                     version + VERSION,
-                    format,
+                    mode,
                     options,
                 )
                 .unwrap()
@@ -3,7 +3,7 @@ use std::path::Path;
 use brk_core::{Bitcoin, Dollars, Height, Result, Sats, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, CollectableVec, EagerVec, Format, StoredVec};
+use brk_vec::{AnyCollectableVec, CollectableVec, EagerVec, ImportMode, StoredVec};
 
 use crate::vecs::{Indexes, fetched, indexes};
 
@@ -22,26 +22,26 @@ impl ComputedHeightValueVecs {
         name: &str,
         compute_source: bool,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         compute_dollars: bool,
     ) -> color_eyre::Result<Self> {
         Ok(Self {
             sats: compute_source.then(|| {
-                EagerVec::forced_import(path, name, version + VERSION + Version::ZERO, format)
+                EagerVec::forced_import(path, name, version + VERSION + Version::ZERO, mode)
                     .unwrap()
             }),
             bitcoin: EagerVec::forced_import(
                 path,
                 &format!("{name}_in_btc"),
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             dollars: compute_dollars.then(|| {
                 EagerVec::forced_import(
                     path,
                     &format!("{name}_in_usd"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -1,18 +1,18 @@
 use std::{ops::Deref, path::Path};
 
 use brk_core::{
-    Date, DateIndex, DecadeIndex, DifficultyEpoch, EmptyOutputIndex, HalvingEpoch, Height,
-    InputIndex, MonthIndex, OpReturnIndex, OutputIndex, P2AAddressIndex, P2ABytes, P2MSOutputIndex,
-    P2PK33AddressIndex, P2PK33Bytes, P2PK65AddressIndex, P2PK65Bytes, P2PKHAddressIndex,
-    P2PKHBytes, P2SHAddressIndex, P2SHBytes, P2TRAddressIndex, P2TRBytes, P2WPKHAddressIndex,
-    P2WPKHBytes, P2WSHAddressIndex, P2WSHBytes, QuarterIndex, Sats, StoredUsize, Timestamp,
-    TxIndex, Txid, UnknownOutputIndex, Version, WeekIndex, YearIndex,
+    ChannelCloseIndex, Date, DateIndex, DecadeIndex, DifficultyEpoch, EmptyOutputIndex,
+    HalvingEpoch, Height, InputIndex, MonthIndex, OpReturnIndex, OutputIndex, P2AAddressIndex,
+    P2ABytes, P2MSOutputIndex, P2PK33AddressIndex, P2PK33Bytes, P2PK65AddressIndex, P2PK65Bytes,
+    P2PKHAddressIndex, P2PKHBytes, P2SHAddressIndex, P2SHBytes, P2TRAddressIndex, P2TRBytes,
+    P2WPKHAddressIndex, P2WPKHBytes, P2WSHAddressIndex, P2WSHBytes, QuarterIndex, Sats,
+    StoredUsize, Timestamp, TxIndex, Txid, UnknownOutputIndex, Version, WeekIndex, YearIndex,
 };
 use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_vec::{
     AnyCollectableVec, CloneableAnyIterableVec, Computation, ComputedVec, ComputedVecFrom1,
-    ComputedVecFrom2, EagerVec, Format, StoredIndex, VecIterator,
+    ComputedVecFrom2, EagerVec, ImportMode, StoredIndex, VecIterator,
 };
 
 use crate::vecs::indexes;
@@ -21,6 +21,8 @@ const VERSION: Version = Version::ZERO;
 
 #[derive(Clone)]
 pub struct Vecs {
+    pub channelcloseindex_to_channelcloseindex:
+        ComputedVecFrom1<ChannelCloseIndex, ChannelCloseIndex, ChannelCloseIndex, InputIndex>,
     pub dateindex_to_date: EagerVec<DateIndex, Date>,
     pub dateindex_to_dateindex: EagerVec<DateIndex, DateIndex>,
     pub dateindex_to_first_height: EagerVec<DateIndex, Height>,
@@ -99,14 +101,14 @@ impl Vecs {
         version: Version,
         indexer: &Indexer,
         computation: Computation,
-        format: Format,
+        mode: ImportMode,
     ) -> color_eyre::Result<Self> {
         let outputindex_to_outputindex = ComputedVec::forced_import_or_init_from_1(
             computation,
             path,
             "outputindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.outputindex_to_value.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -116,7 +118,7 @@ impl Vecs {
             path,
             "inputindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.inputindex_to_outputindex.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -126,7 +128,7 @@ impl Vecs {
             path,
             "txindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.txindex_to_txid.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -136,7 +138,7 @@ impl Vecs {
             path,
             "input_count",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.txindex_to_first_inputindex.boxed_clone(),
             indexer.vecs.inputindex_to_outputindex.boxed_clone(),
             |index: TxIndex, txindex_to_first_inputindex_iter, inputindex_to_outputindex_iter| {
@@ -159,7 +161,7 @@ impl Vecs {
             path,
             "output_count",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.txindex_to_first_outputindex.boxed_clone(),
             indexer.vecs.outputindex_to_value.boxed_clone(),
             |index: TxIndex, txindex_to_first_outputindex_iter, outputindex_to_value_iter| {
@@ -182,7 +184,7 @@ impl Vecs {
             path,
             "p2pk33addressindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2pk33addressindex_to_p2pk33bytes.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -191,7 +193,7 @@ impl Vecs {
             path,
             "p2pk65addressindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2pk65addressindex_to_p2pk65bytes.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -200,7 +202,7 @@ impl Vecs {
             path,
             "p2pkhaddressindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2pkhaddressindex_to_p2pkhbytes.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -209,7 +211,7 @@ impl Vecs {
             path,
             "p2shaddressindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2shaddressindex_to_p2shbytes.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -218,7 +220,7 @@ impl Vecs {
             path,
             "p2traddressindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2traddressindex_to_p2trbytes.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -227,7 +229,7 @@ impl Vecs {
             path,
             "p2wpkhaddressindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2wpkhaddressindex_to_p2wpkhbytes.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -236,7 +238,7 @@ impl Vecs {
             path,
             "p2wshaddressindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2wshaddressindex_to_p2wshbytes.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -245,7 +247,7 @@ impl Vecs {
             path,
             "p2aaddressindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2aaddressindex_to_p2abytes.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -254,16 +256,25 @@ impl Vecs {
             path,
             "p2msoutputindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.p2msoutputindex_to_txindex.boxed_clone(),
             |index, _| Some(index),
         )?;
+        let channelcloseindex_to_channelcloseindex = ComputedVec::forced_import_or_init_from_1(
+            computation,
+            path,
+            "channelcloseindex",
+            version + VERSION + Version::ZERO,
+            mode,
+            indexer.vecs.channelcloseindex_to_inputindex.boxed_clone(),
+            |index, _| Some(index),
+        )?;
         let emptyoutputindex_to_emptyoutputindex = ComputedVec::forced_import_or_init_from_1(
             computation,
             path,
             "emptyoutputindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.emptyoutputindex_to_txindex.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -272,7 +283,7 @@ impl Vecs {
             path,
             "unknownoutputindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.unknownoutputindex_to_txindex.boxed_clone(),
             |index, _| Some(index),
         )?;
@@ -281,12 +292,13 @@ impl Vecs {
             path,
             "opreturnindex",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.opreturnindex_to_txindex.boxed_clone(),
             |index, _| Some(index),
         )?;
 
         Ok(Self {
+            channelcloseindex_to_channelcloseindex,
             emptyoutputindex_to_emptyoutputindex,
             inputindex_to_inputindex,
             opreturnindex_to_opreturnindex,
@@ -309,235 +321,235 @@ impl Vecs {
                 path,
                 "date",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             dateindex_to_dateindex: EagerVec::forced_import(
                 path,
                 "dateindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             dateindex_to_first_height: EagerVec::forced_import(
                 path,
                 "first_height",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             dateindex_to_monthindex: EagerVec::forced_import(
                 path,
                 "monthindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             dateindex_to_weekindex: EagerVec::forced_import(
                 path,
                 "weekindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             decadeindex_to_decadeindex: EagerVec::forced_import(
                 path,
                 "decadeindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             decadeindex_to_first_yearindex: EagerVec::forced_import(
                 path,
                 "first_yearindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             difficultyepoch_to_difficultyepoch: EagerVec::forced_import(
                 path,
                 "difficultyepoch",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             difficultyepoch_to_first_height: EagerVec::forced_import(
                 path,
                 "first_height",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             halvingepoch_to_first_height: EagerVec::forced_import(
                 path,
                 "first_height",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             halvingepoch_to_halvingepoch: EagerVec::forced_import(
                 path,
                 "halvingepoch",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_date: EagerVec::forced_import(
                 path,
                 "date",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_difficultyepoch: EagerVec::forced_import(
                 path,
                 "difficultyepoch",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_halvingepoch: EagerVec::forced_import(
                 path,
                 "halvingepoch",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_height: EagerVec::forced_import(
                 path,
                 "height",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             monthindex_to_first_dateindex: EagerVec::forced_import(
                 path,
                 "first_dateindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             monthindex_to_monthindex: EagerVec::forced_import(
                 path,
                 "monthindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             monthindex_to_quarterindex: EagerVec::forced_import(
                 path,
                 "quarterindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             monthindex_to_yearindex: EagerVec::forced_import(
                 path,
                 "yearindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             quarterindex_to_first_monthindex: EagerVec::forced_import(
                 path,
                 "first_monthindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             weekindex_to_first_dateindex: EagerVec::forced_import(
                 path,
                 "first_dateindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             yearindex_to_first_monthindex: EagerVec::forced_import(
                 path,
                 "first_monthindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             quarterindex_to_quarterindex: EagerVec::forced_import(
                 path,
                 "quarterindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             weekindex_to_weekindex: EagerVec::forced_import(
                 path,
                 "weekindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             yearindex_to_decadeindex: EagerVec::forced_import(
                 path,
                 "decadeindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             yearindex_to_yearindex: EagerVec::forced_import(
                 path,
                 "yearindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_date_fixed: EagerVec::forced_import(
                 path,
                 "date_fixed",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_dateindex: EagerVec::forced_import(
                 path,
                 "dateindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             txindex_to_height: EagerVec::forced_import(
                 path,
                 "height",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_timestamp_fixed: EagerVec::forced_import(
                 path,
                 "timestamp_fixed",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_txindex_count: EagerVec::forced_import(
                 path,
                 "txindex_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             dateindex_to_height_count: EagerVec::forced_import(
                 path,
                 "height_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             weekindex_to_dateindex_count: EagerVec::forced_import(
                 path,
                 "dateindex_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             difficultyepoch_to_height_count: EagerVec::forced_import(
                 path,
                 "height_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             monthindex_to_dateindex_count: EagerVec::forced_import(
                 path,
                 "dateindex_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             quarterindex_to_monthindex_count: EagerVec::forced_import(
                 path,
                 "monthindex_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             yearindex_to_monthindex_count: EagerVec::forced_import(
                 path,
                 "monthindex_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             decadeindex_to_yearindex_count: EagerVec::forced_import(
                 path,
                 "yearindex_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             outputindex_to_txindex: EagerVec::forced_import(
                 path,
                 "txindex",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
         })
     }
@@ -640,6 +652,13 @@ impl Vecs {
                 exit,
             )?;
 
+        self.channelcloseindex_to_channelcloseindex
+            .compute_if_necessary(
+                starting_indexes.channelcloseindex,
+                &indexer.vecs.channelcloseindex_to_inputindex,
+                exit,
+            )?;
+
         self.opreturnindex_to_opreturnindex.compute_if_necessary(
             starting_indexes.opreturnindex,
             &indexer.vecs.opreturnindex_to_txindex,
@@ -1060,6 +1079,7 @@ impl Vecs {
 
     pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
         vec![
+            &self.channelcloseindex_to_channelcloseindex,
             &self.dateindex_to_date,
             &self.dateindex_to_dateindex,
             &self.dateindex_to_first_height,
@@ -1120,6 +1140,7 @@ impl Vecs {
     }
 }
 
+#[derive(Clone)]
 pub struct Indexes {
     indexes: brk_indexer::Indexes,
     pub dateindex: DateIndex,
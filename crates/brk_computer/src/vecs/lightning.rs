@@ -0,0 +1,344 @@
+use std::{fs, path::Path};
+
+use brk_core::{
+    ChannelCloseIndex, DateIndex, Height, OutputIndex, OutputType, Result, Sats, StoredUsize,
+    Version,
+};
+use brk_exit::Exit;
+use brk_indexer::Indexer;
+use brk_vec::{
+    AnyCollectableVec, AnyIterableVec, AnyVec, Computation, ImportMode, SparseVec, StoredIndex,
+};
+use log::info;
+
+use super::{
+    Indexes, fetched,
+    grouped::{ComputedValueVecsFromHeight, ComputedVecsFromHeight, StorableVecGeneatorOptions},
+    indexes,
+};
+
+const VERSION: Version = Version::ONE;
+
+/// First block of December 2017, when the first implementations went on mainnet
+const FIRST_CHANNEL_HEIGHT: Height = Height::new(500_000);
+/// Default minimum channel size of LND, smaller P2WSH outputs are mostly anchors and HTLCs
+const MIN_CHANNEL_CAPACITY: Sats = Sats::new(20_000);
+/// Largest channel without `option_support_large_channel`, larger P2WSH outputs are mostly multisig wallets
+const MAX_CHANNEL_CAPACITY: Sats = Sats::new(16_777_215);
+
+/// Lightning channel activity estimated from chain data
+///
+/// Closes are the spends of 2-of-2 multisig P2WSH outputs and the commitment transactions of Taproot channels, see
+/// the indexer. A funding output only shows what it is once spent, so opens and capacity are estimated from the P2WSH
+/// outputs which could be one: created since the first channels, within the channel size limits and not by a
+/// commitment transaction, which counts other multisigs of that size too. Taproot funding outputs look like any other
+/// key path output and are left out.
+#[derive(Clone)]
+pub struct Vecs {
+    pub indexes_to_channel_close_count: ComputedVecsFromHeight<StoredUsize>,
+    pub indexes_to_cooperative_channel_close_count: ComputedVecsFromHeight<StoredUsize>,
+    pub indexes_to_force_channel_close_count: ComputedVecsFromHeight<StoredUsize>,
+    /// Zero before the first channels, hence sparse
+    pub height_to_channel_open_count: SparseVec<Height, StoredUsize>,
+    pub indexes_to_channel_open_count: ComputedVecsFromHeight<StoredUsize>,
+    /// Capacity locked: value of the possible funding outputs not yet spent, zero before the first channels too
+    pub height_to_channel_capacity: SparseVec<Height, Sats>,
+    pub indexes_to_channel_capacity: ComputedValueVecsFromHeight,
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        _computation: Computation,
+        mode: ImportMode,
+        fetched: Option<&fetched::Vecs>,
+    ) -> color_eyre::Result<Self> {
+        let compute_dollars = fetched.is_some();
+
+        if !mode.read_only {
+            // Vecs of the previous version, which only knew the channels once closed
+            fetched::remove_vec::<ChannelCloseIndex>(&path.join("open_dateindex"))?;
+            fetched::remove_vec::<DateIndex>(&path.join("channel_open_count"))?;
+            for name in [
+                "closed_channel_capacity",
+                "closed_channel_capacity_in_btc",
+                "closed_channel_capacity_in_usd",
+            ] {
+                let folder = path.join(name);
+                if folder.exists() {
+                    info!("Removing {}...", folder.display());
+                    fs::remove_dir_all(folder)?;
+                }
+            }
+        }
+
+        Ok(Self {
+            indexes_to_channel_close_count: ComputedVecsFromHeight::forced_import(
+                path,
+                "channel_close_count",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            indexes_to_cooperative_channel_close_count: ComputedVecsFromHeight::forced_import(
+                path,
+                "cooperative_channel_close_count",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            indexes_to_force_channel_close_count: ComputedVecsFromHeight::forced_import(
+                path,
+                "force_channel_close_count",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            height_to_channel_open_count: SparseVec::forced_import(
+                path,
+                "channel_open_count",
+                version + VERSION + Version::ZERO,
+                StoredUsize::default(),
+                mode,
+            )?,
+            indexes_to_channel_open_count: ComputedVecsFromHeight::forced_import(
+                path,
+                "channel_open_count",
+                false,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            height_to_channel_capacity: SparseVec::forced_import(
+                path,
+                "channel_capacity",
+                version + VERSION + Version::ZERO,
+                Sats::ZERO,
+                mode,
+            )?,
+            indexes_to_channel_capacity: ComputedValueVecsFromHeight::forced_import(
+                path,
+                "channel_capacity",
+                false,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+                compute_dollars,
+            )?,
+        })
+    }
+
+    pub fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        starting_indexes: &Indexes,
+        fetched: Option<&fetched::Vecs>,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        self.indexes_to_channel_close_count.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |v, indexer, _, starting_indexes, exit| {
+                v.compute_count_from_indexes(
+                    starting_indexes.height,
+                    &indexer.vecs.height_to_first_channelcloseindex,
+                    &indexer.vecs.channelcloseindex_to_inputindex,
+                    exit,
+                )
+            },
+        )?;
+
+        let compute_indexes_to_close_count =
+            |indexes_to_close_count: &mut ComputedVecsFromHeight<StoredUsize>, force: bool| {
+                let mut channelcloseindex_to_channelclosetype_iter =
+                    indexer.vecs.channelcloseindex_to_channelclosetype.iter();
+                indexes_to_close_count.compute_all(
+                    indexer,
+                    indexes,
+                    starting_indexes,
+                    exit,
+                    |v, indexer, _, starting_indexes, exit| {
+                        v.compute_filtered_count_from_indexes(
+                            starting_indexes.height,
+                            &indexer.vecs.height_to_first_channelcloseindex,
+                            &indexer.vecs.channelcloseindex_to_channelclosetype,
+                            |channelcloseindex| {
+                                channelcloseindex_to_channelclosetype_iter
+                                    .unwrap_get_inner(channelcloseindex)
+                                    .is_force()
+                                    == force
+                            },
+                            exit,
+                        )
+                    },
+                )
+            };
+        compute_indexes_to_close_count(
+            &mut self.indexes_to_cooperative_channel_close_count,
+            false,
+        )?;
+        compute_indexes_to_close_count(&mut self.indexes_to_force_channel_close_count, true)?;
+
+        self.compute_opens_and_capacity(indexer, indexes, starting_indexes, exit)?;
+
+        self.indexes_to_channel_open_count.compute_rest(
+            indexes,
+            starting_indexes,
+            exit,
+            Some(&self.height_to_channel_open_count),
+        )?;
+        self.indexes_to_channel_capacity.compute_rest(
+            indexer,
+            indexes,
+            fetched,
+            starting_indexes,
+            exit,
+            Some(&self.height_to_channel_capacity),
+        )?;
+
+        Ok(())
+    }
+
+    /// Counts the possible funding outputs created at each height, their value being locked until they're spent,
+    /// whether by a channel close or not
+    fn compute_opens_and_capacity(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        starting_indexes: &Indexes,
+        exit: &Exit,
+    ) -> Result<()> {
+        let source_version = indexer.vecs.height_to_first_outputindex.version()
+            + indexer.vecs.height_to_first_inputindex.version()
+            + indexer.vecs.inputindex_to_outputindex.version()
+            + indexer.vecs.outputindex_to_outputtype.version()
+            + indexer.vecs.outputindex_to_value.version()
+            + indexer.vecs.txindex_to_rawlocktime.version()
+            + indexes.outputindex_to_txindex.version();
+
+        let opens = &mut self.height_to_channel_open_count;
+        let capacity = &mut self.height_to_channel_capacity;
+
+        opens.validate_computed_version_or_reset_file(
+            Version::ZERO + opens.inner_version() + source_version,
+        )?;
+        capacity.validate_computed_version_or_reset_file(
+            Version::ZERO + capacity.inner_version() + source_version,
+        )?;
+
+        let starting_height = starting_indexes
+            .height
+            .min(Height::from(opens.len()))
+            .min(Height::from(capacity.len()));
+
+        let mut locked = match starting_height.decremented() {
+            Some(prev_height) => capacity.get(prev_height)?.unwrap(),
+            None => Sats::ZERO,
+        };
+
+        let mut height_to_first_outputindex_iter = indexer.vecs.height_to_first_outputindex.iter();
+        let mut height_to_first_inputindex_iter = indexer.vecs.height_to_first_inputindex.iter();
+        let mut inputindex_to_outputindex_iter = indexer.vecs.inputindex_to_outputindex.iter();
+        let mut outputindex_to_outputtype_iter = indexer.vecs.outputindex_to_outputtype.iter();
+        let mut outputindex_to_value_iter = indexer.vecs.outputindex_to_value.iter();
+        let mut outputindex_to_txindex_iter = indexes.outputindex_to_txindex.iter();
+        let mut txindex_to_rawlocktime_iter = indexer.vecs.txindex_to_rawlocktime.iter();
+
+        let output_count = indexer.vecs.outputindex_to_value.len();
+        let input_count = indexer.vecs.inputindex_to_outputindex.len();
+
+        let first_channel_outputindex = height_to_first_outputindex_iter
+            .get_inner(FIRST_CHANNEL_HEIGHT)
+            .unwrap_or(OutputIndex::from(output_count));
+
+        let mut funding_value = |outputindex: OutputIndex| {
+            if outputindex < first_channel_outputindex
+                || outputindex_to_outputtype_iter.unwrap_get_inner(outputindex) != OutputType::P2WSH
+            {
+                return None;
+            }
+
+            let value = outputindex_to_value_iter.unwrap_get_inner(outputindex);
+            if value < MIN_CHANNEL_CAPACITY || value > MAX_CHANNEL_CAPACITY {
+                return None;
+            }
+
+            // The delayed output paying back the closer of a force close is a P2WSH output too
+            let locktime = u32::from(
+                txindex_to_rawlocktime_iter
+                    .unwrap_get_inner(outputindex_to_txindex_iter.unwrap_get_inner(outputindex)),
+            );
+            (locktime >> 24 != 0x20).then_some(value)
+        };
+
+        indexer
+            .vecs
+            .height_to_first_outputindex
+            .iter_at(starting_height)
+            .try_for_each(|(height, first_outputindex)| -> Result<()> {
+                let mut open_count = 0;
+
+                if height >= FIRST_CHANNEL_HEIGHT {
+                    let outputindex_end = height_to_first_outputindex_iter
+                        .get_inner(height.incremented())
+                        .map_or(output_count, |outputindex| outputindex.unwrap_to_usize());
+                    (first_outputindex.into_inner().unwrap_to_usize()..outputindex_end)
+                        .filter_map(|outputindex| funding_value(OutputIndex::from(outputindex)))
+                        .for_each(|value| {
+                            open_count += 1;
+                            locked += value;
+                        });
+
+                    let first_inputindex = height_to_first_inputindex_iter.unwrap_get_inner(height);
+                    let inputindex_end = height_to_first_inputindex_iter
+                        .get_inner(height.incremented())
+                        .map_or(input_count, |inputindex| inputindex.unwrap_to_usize());
+                    (first_inputindex.unwrap_to_usize()..inputindex_end)
+                        .map(|inputindex| {
+                            inputindex_to_outputindex_iter.unwrap_get_inner_(inputindex)
+                        })
+                        .filter(|outputindex| *outputindex != OutputIndex::COINBASE)
+                        .filter_map(&mut funding_value)
+                        .for_each(|value| locked -= value);
+                }
+
+                opens.forced_push_at(height, StoredUsize::from(open_count), exit)?;
+                capacity.forced_push_at(height, locked, exit)
+            })?;
+
+        opens.safe_flush(exit)?;
+        capacity.safe_flush(exit)
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.indexes_to_channel_close_count.vecs(),
+            self.indexes_to_cooperative_channel_close_count.vecs(),
+            self.indexes_to_force_channel_close_count.vecs(),
+            vec![
+                &self.height_to_channel_open_count as &dyn AnyCollectableVec,
+                &self.height_to_channel_capacity,
+            ],
+            self.indexes_to_channel_open_count.vecs(),
+            self.indexes_to_channel_capacity.vecs(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
@@ -3,7 +3,7 @@ use std::{path::Path, thread};
 use brk_core::{Date, DateIndex, Dollars, Height, Sats, StoredF32, StoredUsize, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, Computation, EagerVec, Format, StoredIndex, VecIterator};
+use brk_vec::{AnyCollectableVec, Computation, EagerVec, ImportMode, StoredIndex, VecIterator};
 
 use super::{
     Indexes, fetched,
@@ -166,33 +166,33 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
     ) -> color_eyre::Result<Self> {
-        Ok(Self {
+        let mut this = Self {
             height_to_marketcap: EagerVec::forced_import(
                 path,
                 "marketcap",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_ath: EagerVec::forced_import(
                 path,
                 "ath",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_drawdown: EagerVec::forced_import(
                 path,
                 "drawdown",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_marketcap: ComputedVecsFromDateIndex::forced_import(
                 path,
                 "marketcap",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_ath: ComputedVecsFromDateIndex::forced_import(
@@ -200,7 +200,7 @@ impl Vecs {
                 "ath",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_drawdown: ComputedVecsFromDateIndex::forced_import(
@@ -208,7 +208,7 @@ impl Vecs {
                 "drawdown",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_days_since_ath: ComputedVecsFromDateIndex::forced_import(
@@ -216,7 +216,7 @@ impl Vecs {
                 "days_since_ath",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_max_days_between_aths: ComputedVecsFromDateIndex::forced_import(
@@ -224,7 +224,7 @@ impl Vecs {
                 "max_days_between_aths",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_max_years_between_aths: ComputedVecsFromDateIndex::forced_import(
@@ -232,7 +232,7 @@ impl Vecs {
                 "max_years_between_aths",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
 
@@ -241,98 +241,98 @@ impl Vecs {
                 "1w_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_8d_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "8d_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_13d_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "13d_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_21d_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "21d_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_1m_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "1m_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_34d_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "34d_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_55d_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "55d_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_89d_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "89d_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_144d_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "144d_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_200d_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "200d_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_1y_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "1y_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_2y_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "2y_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_200w_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "200w_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_4y_sma: ComputedRatioVecsFromDateIndex::forced_import(
                 path,
                 "4y_sma",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
 
             _1d_returns: ComputedVecsFromDateIndex::forced_import(
@@ -340,7 +340,7 @@ impl Vecs {
                 "1d_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1w_returns: ComputedVecsFromDateIndex::forced_import(
@@ -348,7 +348,7 @@ impl Vecs {
                 "1w_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1m_returns: ComputedVecsFromDateIndex::forced_import(
@@ -356,7 +356,7 @@ impl Vecs {
                 "1m_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3m_returns: ComputedVecsFromDateIndex::forced_import(
@@ -364,7 +364,7 @@ impl Vecs {
                 "3m_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6m_returns: ComputedVecsFromDateIndex::forced_import(
@@ -372,7 +372,7 @@ impl Vecs {
                 "6m_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1y_returns: ComputedVecsFromDateIndex::forced_import(
@@ -380,7 +380,7 @@ impl Vecs {
                 "1y_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _2y_returns: ComputedVecsFromDateIndex::forced_import(
@@ -388,7 +388,7 @@ impl Vecs {
                 "2y_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3y_returns: ComputedVecsFromDateIndex::forced_import(
@@ -396,7 +396,7 @@ impl Vecs {
                 "3y_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _4y_returns: ComputedVecsFromDateIndex::forced_import(
@@ -404,7 +404,7 @@ impl Vecs {
                 "4y_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _5y_returns: ComputedVecsFromDateIndex::forced_import(
@@ -412,7 +412,7 @@ impl Vecs {
                 "5y_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6y_returns: ComputedVecsFromDateIndex::forced_import(
@@ -420,7 +420,7 @@ impl Vecs {
                 "6y_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _8y_returns: ComputedVecsFromDateIndex::forced_import(
@@ -428,7 +428,7 @@ impl Vecs {
                 "8y_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _10y_returns: ComputedVecsFromDateIndex::forced_import(
@@ -436,7 +436,7 @@ impl Vecs {
                 "10y_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _2y_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -444,7 +444,7 @@ impl Vecs {
                 "2y_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3y_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -452,7 +452,7 @@ impl Vecs {
                 "3y_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _4y_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -460,7 +460,7 @@ impl Vecs {
                 "4y_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _5y_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -468,7 +468,7 @@ impl Vecs {
                 "5y_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6y_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -476,7 +476,7 @@ impl Vecs {
                 "6y_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _8y_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -484,7 +484,7 @@ impl Vecs {
                 "8y_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _10y_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -492,7 +492,7 @@ impl Vecs {
                 "10y_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
 
@@ -501,7 +501,7 @@ impl Vecs {
                 "1w_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1m_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -509,7 +509,7 @@ impl Vecs {
                 "1m_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3m_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -517,7 +517,7 @@ impl Vecs {
                 "3m_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6m_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -525,7 +525,7 @@ impl Vecs {
                 "6m_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1y_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -533,7 +533,7 @@ impl Vecs {
                 "1y_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _2y_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -541,7 +541,7 @@ impl Vecs {
                 "2y_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3y_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -549,7 +549,7 @@ impl Vecs {
                 "3y_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _4y_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -557,7 +557,7 @@ impl Vecs {
                 "4y_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _5y_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -565,7 +565,7 @@ impl Vecs {
                 "5y_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6y_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -573,7 +573,7 @@ impl Vecs {
                 "6y_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _8y_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -581,7 +581,7 @@ impl Vecs {
                 "8y_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _10y_dca_returns: ComputedVecsFromDateIndex::forced_import(
@@ -589,7 +589,7 @@ impl Vecs {
                 "10y_dca_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _2y_dca_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -597,7 +597,7 @@ impl Vecs {
                 "2y_dca_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3y_dca_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -605,7 +605,7 @@ impl Vecs {
                 "3y_dca_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _4y_dca_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -613,7 +613,7 @@ impl Vecs {
                 "4y_dca_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _5y_dca_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -621,7 +621,7 @@ impl Vecs {
                 "5y_dca_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6y_dca_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -629,7 +629,7 @@ impl Vecs {
                 "6y_dca_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _8y_dca_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -637,7 +637,7 @@ impl Vecs {
                 "8y_dca_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _10y_dca_cagr: ComputedVecsFromDateIndex::forced_import(
@@ -645,7 +645,7 @@ impl Vecs {
                 "10y_dca_cagr",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1w_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -653,7 +653,7 @@ impl Vecs {
                 "1w_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1m_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -661,7 +661,7 @@ impl Vecs {
                 "1m_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3m_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -669,7 +669,7 @@ impl Vecs {
                 "3m_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6m_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -677,7 +677,7 @@ impl Vecs {
                 "6m_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1y_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -685,7 +685,7 @@ impl Vecs {
                 "1y_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _2y_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -693,7 +693,7 @@ impl Vecs {
                 "2y_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3y_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -701,7 +701,7 @@ impl Vecs {
                 "3y_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _4y_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -709,7 +709,7 @@ impl Vecs {
                 "4y_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _5y_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -717,7 +717,7 @@ impl Vecs {
                 "5y_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6y_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -725,7 +725,7 @@ impl Vecs {
                 "6y_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _8y_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -733,7 +733,7 @@ impl Vecs {
                 "8y_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _10y_dca_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -741,7 +741,7 @@ impl Vecs {
                 "10y_dca_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_1d_ago: ComputedVecsFromDateIndex::forced_import(
@@ -749,7 +749,7 @@ impl Vecs {
                 "price_1d_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_1w_ago: ComputedVecsFromDateIndex::forced_import(
@@ -757,7 +757,7 @@ impl Vecs {
                 "price_1w_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_1m_ago: ComputedVecsFromDateIndex::forced_import(
@@ -765,7 +765,7 @@ impl Vecs {
                 "price_1m_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_3m_ago: ComputedVecsFromDateIndex::forced_import(
@@ -773,7 +773,7 @@ impl Vecs {
                 "price_3m_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_6m_ago: ComputedVecsFromDateIndex::forced_import(
@@ -781,7 +781,7 @@ impl Vecs {
                 "price_6m_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_1y_ago: ComputedVecsFromDateIndex::forced_import(
@@ -789,7 +789,7 @@ impl Vecs {
                 "price_1y_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_2y_ago: ComputedVecsFromDateIndex::forced_import(
@@ -797,7 +797,7 @@ impl Vecs {
                 "price_2y_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_3y_ago: ComputedVecsFromDateIndex::forced_import(
@@ -805,7 +805,7 @@ impl Vecs {
                 "price_3y_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_4y_ago: ComputedVecsFromDateIndex::forced_import(
@@ -813,7 +813,7 @@ impl Vecs {
                 "price_4y_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_5y_ago: ComputedVecsFromDateIndex::forced_import(
@@ -821,7 +821,7 @@ impl Vecs {
                 "price_5y_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_6y_ago: ComputedVecsFromDateIndex::forced_import(
@@ -829,7 +829,7 @@ impl Vecs {
                 "price_6y_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_8y_ago: ComputedVecsFromDateIndex::forced_import(
@@ -837,7 +837,7 @@ impl Vecs {
                 "price_8y_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             price_10y_ago: ComputedVecsFromDateIndex::forced_import(
@@ -845,7 +845,7 @@ impl Vecs {
                 "price_10y_ago",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1w_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -853,7 +853,7 @@ impl Vecs {
                 "1w_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1m_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -861,7 +861,7 @@ impl Vecs {
                 "1m_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3m_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -869,7 +869,7 @@ impl Vecs {
                 "3m_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6m_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -877,7 +877,7 @@ impl Vecs {
                 "6m_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _1y_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -885,7 +885,7 @@ impl Vecs {
                 "1y_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _2y_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -893,7 +893,7 @@ impl Vecs {
                 "2y_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _3y_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -901,7 +901,7 @@ impl Vecs {
                 "3y_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _4y_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -909,7 +909,7 @@ impl Vecs {
                 "4y_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _5y_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -917,7 +917,7 @@ impl Vecs {
                 "5y_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _6y_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -925,7 +925,7 @@ impl Vecs {
                 "6y_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _8y_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -933,7 +933,7 @@ impl Vecs {
                 "8y_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             _10y_dca_stack: ComputedVecsFromDateIndex::forced_import(
@@ -941,7 +941,7 @@ impl Vecs {
                 "10y_dca_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
 
@@ -950,7 +950,7 @@ impl Vecs {
                 "dca_class_2025_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2024_stack: ComputedVecsFromDateIndex::forced_import(
@@ -958,7 +958,7 @@ impl Vecs {
                 "dca_class_2024_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2023_stack: ComputedVecsFromDateIndex::forced_import(
@@ -966,7 +966,7 @@ impl Vecs {
                 "dca_class_2023_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2022_stack: ComputedVecsFromDateIndex::forced_import(
@@ -974,7 +974,7 @@ impl Vecs {
                 "dca_class_2022_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2021_stack: ComputedVecsFromDateIndex::forced_import(
@@ -982,7 +982,7 @@ impl Vecs {
                 "dca_class_2021_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2020_stack: ComputedVecsFromDateIndex::forced_import(
@@ -990,7 +990,7 @@ impl Vecs {
                 "dca_class_2020_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2019_stack: ComputedVecsFromDateIndex::forced_import(
@@ -998,7 +998,7 @@ impl Vecs {
                 "dca_class_2019_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2018_stack: ComputedVecsFromDateIndex::forced_import(
@@ -1006,7 +1006,7 @@ impl Vecs {
                 "dca_class_2018_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2017_stack: ComputedVecsFromDateIndex::forced_import(
@@ -1014,7 +1014,7 @@ impl Vecs {
                 "dca_class_2017_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2016_stack: ComputedVecsFromDateIndex::forced_import(
@@ -1022,7 +1022,7 @@ impl Vecs {
                 "dca_class_2016_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2015_stack: ComputedVecsFromDateIndex::forced_import(
@@ -1030,7 +1030,7 @@ impl Vecs {
                 "dca_class_2015_stack",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
 
@@ -1039,7 +1039,7 @@ impl Vecs {
                 "dca_class_2025_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2024_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1047,7 +1047,7 @@ impl Vecs {
                 "dca_class_2024_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2023_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1055,7 +1055,7 @@ impl Vecs {
                 "dca_class_2023_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2022_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1063,7 +1063,7 @@ impl Vecs {
                 "dca_class_2022_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2021_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1071,7 +1071,7 @@ impl Vecs {
                 "dca_class_2021_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2020_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1079,7 +1079,7 @@ impl Vecs {
                 "dca_class_2020_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2019_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1087,7 +1087,7 @@ impl Vecs {
                 "dca_class_2019_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2018_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1095,7 +1095,7 @@ impl Vecs {
                 "dca_class_2018_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2017_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1103,7 +1103,7 @@ impl Vecs {
                 "dca_class_2017_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2016_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1111,7 +1111,7 @@ impl Vecs {
                 "dca_class_2016_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2015_avg_price: ComputedVecsFromDateIndex::forced_import(
@@ -1119,7 +1119,7 @@ impl Vecs {
                 "dca_class_2015_avg_price",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
 
@@ -1128,7 +1128,7 @@ impl Vecs {
                 "dca_class_2025_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2024_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1136,7 +1136,7 @@ impl Vecs {
                 "dca_class_2024_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2023_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1144,7 +1144,7 @@ impl Vecs {
                 "dca_class_2023_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2022_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1152,7 +1152,7 @@ impl Vecs {
                 "dca_class_2022_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2021_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1160,7 +1160,7 @@ impl Vecs {
                 "dca_class_2021_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2020_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1168,7 +1168,7 @@ impl Vecs {
                 "dca_class_2020_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2019_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1176,7 +1176,7 @@ impl Vecs {
                 "dca_class_2019_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2018_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1184,7 +1184,7 @@ impl Vecs {
                 "dca_class_2018_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2017_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1192,7 +1192,7 @@ impl Vecs {
                 "dca_class_2017_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2016_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1200,7 +1200,7 @@ impl Vecs {
                 "dca_class_2016_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             dca_class_2015_returns: ComputedVecsFromDateIndex::forced_import(
@@ -1208,7 +1208,7 @@ impl Vecs {
                 "dca_class_2015_returns",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
 
@@ -1217,7 +1217,7 @@ impl Vecs {
                 "200d_sma_x2_4",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_200d_sma_x0_8: ComputedVecsFromDateIndex::forced_import(
@@ -1225,10 +1225,20 @@ impl Vecs {
                 "200d_sma_x0_8",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
-        })
+        };
+
+        // Queried with predicates, "days where drawdown < -50%"
+        this.height_to_drawdown.enable_page_stats()?;
+        this.indexes_to_drawdown
+            .dateindex
+            .as_mut()
+            .unwrap()
+            .enable_page_stats()?;
+
+        Ok(this)
     }
 
     pub fn compute(
@@ -1,23 +1,41 @@
 use std::path::Path;
 
-use brk_core::{DifficultyEpoch, HalvingEpoch, StoredF64, Version};
+use brk_core::{DateIndex, DifficultyEpoch, Dollars, HalvingEpoch, StoredF64, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, Computation, Format, VecIterator};
+use brk_vec::{AnyCollectableVec, Computation, EagerVec, ImportMode, VecIterator};
 
 use super::{
-    Indexes,
+    Indexes, blocks, fetched,
     grouped::{ComputedVecsFromDateIndex, ComputedVecsFromHeight, StorableVecGeneatorOptions},
-    indexes,
+    indexes, transactions,
 };
 
 const VERSION: Version = Version::ZERO;
 
+/// Hashes needed on average to find a block at a difficulty of 1
+const HASHES_PER_DIFFICULTY: f64 = 4_294_967_296.0;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const HASHES_PER_TERAHASH: f64 = 1_000_000_000_000.0;
+
 #[derive(Clone)]
 pub struct Vecs {
     pub indexes_to_difficulty: ComputedVecsFromHeight<StoredF64>,
     pub indexes_to_difficultyepoch: ComputedVecsFromDateIndex<DifficultyEpoch>,
     pub indexes_to_halvingepoch: ComputedVecsFromDateIndex<HalvingEpoch>,
+    /// Estimated in hashes per second from the blocks mined each day and their difficulty
+    pub indexes_to_hash_rate: ComputedVecsFromDateIndex<StoredF64>,
+    pub indexes_to_hash_rate_1w_sma: ComputedVecsFromDateIndex<StoredF64>,
+    pub indexes_to_hash_rate_1m_sma: ComputedVecsFromDateIndex<StoredF64>,
+    /// Miner revenue in dollars per terahash per second per day
+    pub indexes_to_hash_price: Option<ComputedVecsFromDateIndex<Dollars>>,
+    /// Miner revenue in sats per terahash per second per day
+    pub indexes_to_hash_value: ComputedVecsFromDateIndex<StoredF64>,
+    pub dateindex_to_coinbase_in_usd_365d_sma: Option<EagerVec<DateIndex, Dollars>>,
+    /// Daily miner revenue in dollars divided by its yearly moving average
+    pub indexes_to_puell_multiple: Option<ComputedVecsFromDateIndex<StoredF64>>,
+    /// Percentage of the miner revenue which came from fees
+    pub indexes_to_fee_share: ComputedVecsFromDateIndex<StoredF64>,
 }
 
 impl Vecs {
@@ -25,15 +43,18 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
+        fetched: Option<&fetched::Vecs>,
     ) -> color_eyre::Result<Self> {
+        let compute_dollars = fetched.is_some();
+
         Ok(Self {
             indexes_to_difficulty: ComputedVecsFromHeight::forced_import(
                 path,
                 "difficulty",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_difficultyepoch: ComputedVecsFromDateIndex::forced_import(
@@ -41,7 +62,7 @@ impl Vecs {
                 "difficultyepoch",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_halvingepoch: ComputedVecsFromDateIndex::forced_import(
@@ -49,7 +70,78 @@ impl Vecs {
                 "halvingepoch",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            indexes_to_hash_rate: ComputedVecsFromDateIndex::forced_import(
+                path,
+                "hash_rate",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            indexes_to_hash_rate_1w_sma: ComputedVecsFromDateIndex::forced_import(
+                path,
+                "hash_rate_1w_sma",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            indexes_to_hash_rate_1m_sma: ComputedVecsFromDateIndex::forced_import(
+                path,
+                "hash_rate_1m_sma",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            indexes_to_hash_price: compute_dollars.then(|| {
+                ComputedVecsFromDateIndex::forced_import(
+                    path,
+                    "hash_price",
+                    true,
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    StorableVecGeneatorOptions::default().add_last(),
+                )
+                .unwrap()
+            }),
+            indexes_to_hash_value: ComputedVecsFromDateIndex::forced_import(
+                path,
+                "hash_value",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+            dateindex_to_coinbase_in_usd_365d_sma: compute_dollars.then(|| {
+                EagerVec::forced_import(
+                    path,
+                    "coinbase_in_usd_365d_sma",
+                    version + VERSION + Version::ZERO,
+                    mode,
+                )
+                .unwrap()
+            }),
+            indexes_to_puell_multiple: compute_dollars.then(|| {
+                ComputedVecsFromDateIndex::forced_import(
+                    path,
+                    "puell_multiple",
+                    true,
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    StorableVecGeneatorOptions::default().add_last(),
+                )
+                .unwrap()
+            }),
+            indexes_to_fee_share: ComputedVecsFromDateIndex::forced_import(
+                path,
+                "fee_share",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
         })
@@ -59,6 +151,8 @@ impl Vecs {
         &mut self,
         indexer: &Indexer,
         indexes: &indexes::Vecs,
+        blocks: &blocks::Vecs,
+        transactions: &transactions::Vecs,
         starting_indexes: &Indexes,
         exit: &Exit,
     ) -> color_eyre::Result<()> {
@@ -117,6 +211,172 @@ impl Vecs {
             Some(&indexer.vecs.height_to_difficulty),
         )?;
 
+        let mut difficulty_iter = self
+            .indexes_to_difficulty
+            .dateindex
+            .unwrap_last()
+            .into_iter();
+        self.indexes_to_hash_rate.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    blocks.indexes_to_block_count.dateindex.unwrap_sum(),
+                    |(di, block_count, ..)| {
+                        let difficulty = f64::from(difficulty_iter.unwrap_get_inner(di));
+                        let hash_rate = f64::from(block_count) * difficulty * HASHES_PER_DIFFICULTY
+                            / SECONDS_PER_DAY;
+                        (di, StoredF64::from(hash_rate))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let hash_rate = self.indexes_to_hash_rate.dateindex.as_ref().unwrap();
+
+        [
+            (&mut self.indexes_to_hash_rate_1w_sma, 7),
+            (&mut self.indexes_to_hash_rate_1m_sma, 30),
+        ]
+        .into_iter()
+        .try_for_each(|(vecs, sma)| {
+            vecs.compute_all(
+                indexer,
+                indexes,
+                starting_indexes,
+                exit,
+                |v, _, _, starting_indexes, exit| {
+                    v.compute_sma(starting_indexes.dateindex, hash_rate, sma, exit)
+                },
+            )
+        })?;
+
+        let coinbase = &transactions.indexes_to_coinbase;
+
+        let mut hash_rate_iter = hash_rate.into_iter();
+        self.indexes_to_hash_value.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    coinbase.sats.dateindex.unwrap_sum(),
+                    |(di, sats, ..)| {
+                        let terahashes =
+                            f64::from(hash_rate_iter.unwrap_get_inner(di)) / HASHES_PER_TERAHASH;
+                        let hash_value = if terahashes == 0.0 {
+                            f64::NAN
+                        } else {
+                            f64::from(sats) / terahashes
+                        };
+                        (di, StoredF64::from(hash_value))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let mut fee_iter = transactions
+            .indexes_to_fee
+            .sats
+            .dateindex
+            .unwrap_sum()
+            .into_iter();
+        self.indexes_to_fee_share.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    coinbase.sats.dateindex.unwrap_sum(),
+                    |(di, sats, ..)| {
+                        let sats = f64::from(sats);
+                        let fee_share = if sats == 0.0 {
+                            0.0
+                        } else {
+                            f64::from(fee_iter.unwrap_get_inner(di)) / sats * 100.0
+                        };
+                        (di, StoredF64::from(fee_share))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let Some(coinbase_in_usd) = coinbase.dollars.as_ref() else {
+            return Ok(());
+        };
+        let coinbase_in_usd = coinbase_in_usd.dateindex.unwrap_sum();
+
+        let mut hash_rate_iter = hash_rate.into_iter();
+        self.indexes_to_hash_price.as_mut().unwrap().compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    coinbase_in_usd,
+                    |(di, dollars, ..)| {
+                        let terahashes =
+                            f64::from(hash_rate_iter.unwrap_get_inner(di)) / HASHES_PER_TERAHASH;
+                        let hash_price = if terahashes == 0.0 {
+                            f64::NAN
+                        } else {
+                            f64::from(dollars) / terahashes
+                        };
+                        (di, Dollars::from(hash_price))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        self.dateindex_to_coinbase_in_usd_365d_sma
+            .as_mut()
+            .unwrap()
+            .compute_sma(starting_indexes.dateindex, coinbase_in_usd, 365, exit)?;
+
+        let mut sma_iter = self
+            .dateindex_to_coinbase_in_usd_365d_sma
+            .as_ref()
+            .unwrap()
+            .into_iter();
+        self.indexes_to_puell_multiple
+            .as_mut()
+            .unwrap()
+            .compute_all(
+                indexer,
+                indexes,
+                starting_indexes,
+                exit,
+                |vec, _, _, starting_indexes, exit| {
+                    vec.compute_transform(
+                        starting_indexes.dateindex,
+                        coinbase_in_usd,
+                        |(di, dollars, ..)| {
+                            let sma = f64::from(sma_iter.unwrap_get_inner(di));
+                            let puell_multiple = if sma == 0.0 {
+                                f64::NAN
+                            } else {
+                                f64::from(dollars) / sma
+                            };
+                            (di, StoredF64::from(puell_multiple))
+                        },
+                        exit,
+                    )
+                },
+            )?;
+
         Ok(())
     }
 
@@ -125,6 +385,20 @@ impl Vecs {
             self.indexes_to_difficulty.vecs(),
             self.indexes_to_difficultyepoch.vecs(),
             self.indexes_to_halvingepoch.vecs(),
+            self.indexes_to_hash_rate.vecs(),
+            self.indexes_to_hash_rate_1w_sma.vecs(),
+            self.indexes_to_hash_rate_1m_sma.vecs(),
+            self.indexes_to_hash_price
+                .as_ref()
+                .map_or(vec![], |v| v.vecs()),
+            self.indexes_to_hash_value.vecs(),
+            self.dateindex_to_coinbase_in_usd_365d_sma
+                .as_ref()
+                .map_or(vec![], |v| vec![v as &dyn AnyCollectableVec]),
+            self.indexes_to_puell_multiple
+                .as_ref()
+                .map_or(vec![], |v| v.vecs()),
+            self.indexes_to_fee_share.vecs(),
         ]
         .into_iter()
         .flatten()
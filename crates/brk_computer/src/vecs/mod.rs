@@ -1,21 +1,29 @@
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
-use brk_core::Version;
+use brk_core::{Height, Version};
 use brk_exit::Exit;
 use brk_fetcher::Fetcher;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, Computation, Format};
+use brk_vec::{AnyCollectableVec, AnyVec, Commit, CommitLog, Computation, ImportMode};
+use rayon::prelude::*;
 
+pub mod adoption;
 pub mod blocks;
+pub mod blockspace;
 pub mod cointime;
 pub mod constants;
+pub mod dormancy;
 pub mod fetched;
 pub mod grouped;
 pub mod indexes;
+pub mod lightning;
 pub mod market;
 pub mod mining;
+pub mod patterns;
+pub mod pools;
 pub mod stateful;
 pub mod transactions;
+pub mod volume;
 
 pub use indexes::Indexes;
 use log::info;
@@ -27,72 +35,110 @@ pub struct Vecs {
     pub indexes: indexes::Vecs,
     pub constants: constants::Vecs,
     pub blocks: blocks::Vecs,
+    pub adoption: adoption::Vecs,
+    pub blockspace: blockspace::Vecs,
+    pub lightning: lightning::Vecs,
     pub mining: mining::Vecs,
+    pub pools: pools::Vecs,
     pub market: market::Vecs,
     pub transactions: transactions::Vecs,
+    pub patterns: patterns::Vecs,
+    pub volume: volume::Vecs,
     pub stateful: stateful::Vecs,
     pub fetched: Option<fetched::Vecs>,
     pub cointime: cointime::Vecs,
+    pub dormancy: dormancy::Vecs,
+    commit_log: CommitLog,
+    /// Generations of the vecs when they were last synced, only the ones which changed since need to be synced again
+    synced_generations: BTreeMap<String, u64>,
 }
 
 impl Vecs {
+    #[allow(clippy::too_many_arguments)]
     pub fn import(
         path: &Path,
         version: Version,
         indexer: &Indexer,
         fetch: bool,
+        pools: pools::Pools,
+        urpd_buckets: stateful::urpd::UrpdBuckets,
         computation: Computation,
-        format: Format,
+        mode: ImportMode,
     ) -> color_eyre::Result<Self> {
         let indexes = indexes::Vecs::forced_import(
             path,
             version + VERSION + Version::ZERO,
             indexer,
             computation,
-            format,
+            mode,
         )?;
 
         let fetched = fetch.then(|| {
-            fetched::Vecs::forced_import(
-                path,
-                version + VERSION + Version::ZERO,
-                computation,
-                format,
-            )
-            .unwrap()
+            fetched::Vecs::forced_import(path, version + VERSION + Version::ZERO, computation, mode)
+                .unwrap()
         });
 
-        Ok(Self {
+        let mut this = Self {
             blocks: blocks::Vecs::forced_import(
                 path,
                 version + VERSION + Version::ZERO,
                 computation,
-                format,
+                mode,
+            )?,
+            adoption: adoption::Vecs::forced_import(
+                path,
+                version + VERSION + Version::ZERO,
+                computation,
+                mode,
+                fetched.as_ref(),
+            )?,
+            blockspace: blockspace::Vecs::forced_import(
+                path,
+                version + VERSION + Version::ZERO,
+                computation,
+                mode,
+                fetched.as_ref(),
+            )?,
+            lightning: lightning::Vecs::forced_import(
+                path,
+                version + VERSION + Version::ZERO,
+                computation,
+                mode,
+                fetched.as_ref(),
             )?,
             mining: mining::Vecs::forced_import(
                 path,
                 version + VERSION + Version::ZERO,
                 computation,
-                format,
+                mode,
+                fetched.as_ref(),
+            )?,
+            pools: pools::Vecs::forced_import(
+                path,
+                version + VERSION + Version::ZERO,
+                pools,
+                computation,
+                mode,
             )?,
             constants: constants::Vecs::forced_import(
                 path,
                 version + VERSION + Version::ZERO,
                 computation,
-                format,
+                mode,
             )?,
             market: market::Vecs::forced_import(
                 path,
                 version + VERSION + Version::ZERO,
                 computation,
-                format,
+                mode,
             )?,
             stateful: stateful::Vecs::forced_import(
                 path,
                 version + VERSION + Version::ZERO,
                 computation,
-                format,
+                mode,
                 fetched.as_ref(),
+                urpd_buckets,
             )?,
             transactions: transactions::Vecs::forced_import(
                 path,
@@ -100,19 +146,46 @@ impl Vecs {
                 indexer,
                 &indexes,
                 computation,
-                format,
+                mode,
+                fetched.as_ref(),
+            )?,
+            patterns: patterns::Vecs::forced_import(
+                path,
+                version + VERSION + Version::ZERO,
+                computation,
+                mode,
+                fetched.as_ref(),
+            )?,
+            volume: volume::Vecs::forced_import(
+                path,
+                version + VERSION + Version::ZERO,
+                computation,
+                mode,
                 fetched.as_ref(),
             )?,
             cointime: cointime::Vecs::forced_import(
                 path,
                 version + VERSION + Version::ZERO,
                 computation,
-                format,
+                mode,
+                fetched.as_ref(),
+            )?,
+            dormancy: dormancy::Vecs::forced_import(
+                path,
+                version + VERSION + Version::ZERO,
+                computation,
+                mode,
                 fetched.as_ref(),
             )?,
             indexes,
             fetched,
-        })
+            commit_log: CommitLog::import(&path.join("commit"))?,
+            synced_generations: BTreeMap::new(),
+        };
+
+        this.synced_generations = this.generations();
+
+        Ok(this)
     }
 
     pub fn compute(
@@ -122,6 +195,14 @@ impl Vecs {
         fetcher: Option<&mut Fetcher>,
         exit: &Exit,
     ) -> color_eyre::Result<()> {
+        // Vecs flushed after the last commit could have been torn by a crash, so everything after it is computed again
+        let starting_indexes = match self.commit_log.last() {
+            Some(commit) if commit.height.incremented() < starting_indexes.height => {
+                brk_indexer::Indexes::at_height(&indexer.vecs, commit.height.incremented())?
+            }
+            _ => starting_indexes,
+        };
+
         info!("Computing indexes...");
         let mut starting_indexes = self.indexes.compute(indexer, starting_indexes, exit)?;
 
@@ -133,9 +214,14 @@ impl Vecs {
         self.blocks
             .compute(indexer, &self.indexes, &starting_indexes, exit)?;
 
-        info!("Computing mining...");
-        self.mining
-            .compute(indexer, &self.indexes, &starting_indexes, exit)?;
+        info!("Computing pools...");
+        self.pools.compute(
+            indexer,
+            &self.indexes,
+            &self.blocks,
+            &starting_indexes,
+            exit,
+        )?;
 
         if let Some(fetched) = self.fetched.as_mut() {
             info!("Computing fetched...");
@@ -157,6 +243,55 @@ impl Vecs {
             exit,
         )?;
 
+        info!("Computing blockspace...");
+        self.blockspace.compute(
+            indexer,
+            &self.indexes,
+            &self.transactions,
+            &starting_indexes,
+            self.fetched.as_ref(),
+            exit,
+        )?;
+
+        info!("Computing patterns...");
+        self.patterns.compute(
+            indexer,
+            &self.indexes,
+            &self.transactions,
+            &starting_indexes,
+            self.fetched.as_ref(),
+            exit,
+        )?;
+
+        info!("Computing adoption...");
+        self.adoption.compute(
+            indexer,
+            &self.indexes,
+            &self.transactions,
+            &starting_indexes,
+            self.fetched.as_ref(),
+            exit,
+        )?;
+
+        info!("Computing lightning...");
+        self.lightning.compute(
+            indexer,
+            &self.indexes,
+            &starting_indexes,
+            self.fetched.as_ref(),
+            exit,
+        )?;
+
+        info!("Computing mining...");
+        self.mining.compute(
+            indexer,
+            &self.indexes,
+            &self.blocks,
+            &self.transactions,
+            &starting_indexes,
+            exit,
+        )?;
+
         if let Some(fetched) = self.fetched.as_ref() {
             info!("Computing market...");
             self.market.compute(
@@ -169,6 +304,17 @@ impl Vecs {
             )?;
         }
 
+        info!("Computing volume...");
+        self.volume.compute(
+            indexer,
+            &self.indexes,
+            &self.transactions,
+            &self.market,
+            &starting_indexes,
+            self.fetched.as_ref(),
+            exit,
+        )?;
+
         info!("Computing stateful...");
         self.stateful.compute(
             indexer,
@@ -190,7 +336,64 @@ impl Vecs {
             exit,
         )?;
 
-        Ok(())
+        info!("Computing dormancy...");
+        self.dormancy.compute(
+            indexer,
+            &self.indexes,
+            &starting_indexes,
+            self.fetched.as_ref(),
+            &self.transactions,
+            &self.stateful,
+            exit,
+        )?;
+
+        self.commit(indexer, exit)
+    }
+
+    /// Syncs all vecs and records them as a new generation, unless the computation was interrupted
+    fn commit(&mut self, indexer: &Indexer, exit: &Exit) -> color_eyre::Result<()> {
+        let Some(height) = indexer
+            .vecs
+            .height_to_blockhash
+            .len()
+            .checked_sub(1)
+            .map(Height::from)
+        else {
+            return Ok(());
+        };
+
+        if exit.triggered() {
+            return Ok(());
+        }
+
+        exit.block();
+
+        let generations = self.generations();
+        let vecs = self.vecs();
+
+        let mut commit_log = self.commit_log.clone();
+        let res = vecs
+            .par_iter()
+            .filter(|vec| {
+                self.synced_generations.get(&Commit::key(**vec)) != Some(&vec.generation())
+            })
+            .try_for_each(|vec| vec.sync_to_disk())
+            .and_then(|_| commit_log.commit(height, vecs));
+        if res.is_ok() {
+            self.commit_log = commit_log;
+            self.synced_generations = generations;
+        }
+
+        exit.release();
+
+        Ok(res?)
+    }
+
+    fn generations(&self) -> BTreeMap<String, u64> {
+        self.vecs()
+            .into_iter()
+            .map(|vec| (Commit::key(vec), vec.generation()))
+            .collect()
     }
 
     pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
@@ -198,11 +401,18 @@ impl Vecs {
             self.constants.vecs(),
             self.indexes.vecs(),
             self.blocks.vecs(),
+            self.blockspace.vecs(),
+            self.adoption.vecs(),
+            self.lightning.vecs(),
             self.mining.vecs(),
+            self.pools.vecs(),
             self.market.vecs(),
             self.transactions.vecs(),
+            self.patterns.vecs(),
+            self.volume.vecs(),
             self.stateful.vecs(),
             self.cointime.vecs(),
+            self.dormancy.vecs(),
             self.fetched.as_ref().map_or(vec![], |v| v.vecs()),
         ]
         .into_iter()
@@ -0,0 +1,277 @@
+use std::path::Path;
+
+use brk_core::{Result, Sats, StoredUsize, TxIndex, TxPattern, Version};
+use brk_exit::Exit;
+use brk_indexer::Indexer;
+use brk_vec::{
+    AnyCollectableVec, AnyIterableVec, AnyVec, Computation, EagerVec, EagerVecGroup, ImportMode,
+};
+
+use super::{
+    Indexes, fetched,
+    grouped::{ComputedValueVecsFromHeight, ComputedVecsFromHeight, StorableVecGeneatorOptions},
+    indexes, transactions,
+};
+
+const VERSION: Version = Version::ZERO;
+
+/// Transactions classified by the shape of their inputs and outputs, see `TxPattern`
+#[derive(Clone)]
+pub struct Vecs {
+    pub txindex_to_txpattern: EagerVec<TxIndex, TxPattern>,
+    pub by_pattern: Vec<PatternVecs>,
+}
+
+/// Transactions of a given pattern
+#[derive(Clone)]
+pub struct PatternVecs {
+    pub pattern: TxPattern,
+    pub indexes_to_tx_count: ComputedVecsFromHeight<StoredUsize>,
+    /// Sum of the values of the outputs
+    pub indexes_to_tx_volume: ComputedValueVecsFromHeight,
+    pub indexes_to_tx_vsize: ComputedVecsFromHeight<StoredUsize>,
+}
+
+impl PatternVecs {
+    fn forced_import(
+        path: &Path,
+        pattern: TxPattern,
+        version: Version,
+        mode: ImportMode,
+        compute_dollars: bool,
+    ) -> color_eyre::Result<Self> {
+        let slug = pattern.slug();
+
+        Ok(Self {
+            pattern,
+            indexes_to_tx_count: ComputedVecsFromHeight::forced_import(
+                path,
+                &format!("{slug}_tx_count"),
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            indexes_to_tx_volume: ComputedValueVecsFromHeight::forced_import(
+                path,
+                &format!("{slug}_tx_volume"),
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+                compute_dollars,
+            )?,
+            indexes_to_tx_vsize: ComputedVecsFromHeight::forced_import(
+                path,
+                &format!("{slug}_tx_vsize"),
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+        })
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.indexes_to_tx_count.vecs(),
+            self.indexes_to_tx_volume.vecs(),
+            self.indexes_to_tx_vsize.vecs(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        _computation: Computation,
+        mode: ImportMode,
+        fetched: Option<&fetched::Vecs>,
+    ) -> color_eyre::Result<Self> {
+        let compute_dollars = fetched.is_some();
+
+        Ok(Self {
+            txindex_to_txpattern: EagerVec::forced_import(
+                path,
+                "txpattern",
+                version + VERSION + Version::ZERO,
+                mode,
+            )?,
+            by_pattern: TxPattern::as_vec()
+                .into_iter()
+                .map(|pattern| {
+                    PatternVecs::forced_import(
+                        path,
+                        pattern,
+                        version + VERSION + Version::ZERO,
+                        mode,
+                        compute_dollars,
+                    )
+                })
+                .collect::<color_eyre::Result<Vec<_>>>()?,
+        })
+    }
+
+    pub fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        transactions: &transactions::Vecs,
+        starting_indexes: &Indexes,
+        fetched: Option<&fetched::Vecs>,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        let mut txindex_to_is_coinbase_iter = transactions.txindex_to_is_coinbase.iter();
+        let mut txindex_to_input_count_iter = indexes.txindex_to_input_count.iter();
+        let mut txindex_to_output_count_iter = indexes.txindex_to_output_count.iter();
+        let mut outputindex_to_value_iter = indexer.vecs.outputindex_to_value.iter();
+        let mut values = vec![];
+
+        self.txindex_to_txpattern.compute_transform(
+            starting_indexes.txindex,
+            &indexer.vecs.txindex_to_first_outputindex,
+            |(txindex, first_outputindex, ..)| {
+                let output_count = *txindex_to_output_count_iter.unwrap_get_inner(txindex);
+
+                values.clear();
+                values.extend(
+                    (0..output_count)
+                        .map(|vout| {
+                            outputindex_to_value_iter.unwrap_get_inner(first_outputindex + vout)
+                        })
+                        .filter(|value| *value != Sats::ZERO),
+                );
+                values.sort_unstable();
+                let equal_outputs = values
+                    .chunk_by(|a, b| a == b)
+                    .map(|chunk| chunk.len())
+                    .max()
+                    .unwrap_or_default();
+
+                (
+                    txindex,
+                    TxPattern::classify(
+                        txindex_to_is_coinbase_iter.unwrap_get_inner(txindex),
+                        *txindex_to_input_count_iter.unwrap_get_inner(txindex),
+                        output_count,
+                        equal_outputs,
+                    ),
+                )
+            },
+            exit,
+        )?;
+
+        self.compute_by_pattern(indexer, transactions, starting_indexes, exit)?;
+
+        self.by_pattern
+            .iter_mut()
+            .try_for_each(|vecs| -> color_eyre::Result<()> {
+                vecs.indexes_to_tx_count.compute_rest(
+                    indexes,
+                    starting_indexes,
+                    exit,
+                    None as Option<&EagerVec<_, _>>,
+                )?;
+                vecs.indexes_to_tx_volume.compute_rest(
+                    indexer,
+                    indexes,
+                    fetched,
+                    starting_indexes,
+                    exit,
+                    None as Option<&EagerVec<_, _>>,
+                )?;
+                vecs.indexes_to_tx_vsize.compute_rest(
+                    indexes,
+                    starting_indexes,
+                    exit,
+                    None as Option<&EagerVec<_, _>>,
+                )
+            })?;
+
+        Ok(())
+    }
+
+    /// Counts, volumes and vsizes of the transactions of each block by pattern
+    fn compute_by_pattern(
+        &mut self,
+        indexer: &Indexer,
+        transactions: &transactions::Vecs,
+        starting_indexes: &Indexes,
+        exit: &Exit,
+    ) -> Result<()> {
+        let mut by_pattern = self
+            .by_pattern
+            .iter_mut()
+            .map(|vecs| {
+                (
+                    vecs.indexes_to_tx_count.height.as_mut().unwrap(),
+                    vecs.indexes_to_tx_volume.sats.height.as_mut().unwrap(),
+                    vecs.indexes_to_tx_vsize.height.as_mut().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut height_to_tx_count_iter = transactions
+            .indexes_to_tx_count
+            .height
+            .as_ref()
+            .unwrap()
+            .iter();
+        let mut txindex_to_txpattern_iter = self.txindex_to_txpattern.iter();
+        let mut txindex_to_output_value_iter = transactions.txindex_to_output_value.iter();
+        let mut txindex_to_vsize_iter = transactions.txindex_to_vsize.iter();
+
+        by_pattern.compute_from(
+            starting_indexes.height,
+            &indexer.vecs.height_to_first_txindex,
+            self.txindex_to_txpattern.version()
+                + transactions.txindex_to_output_value.version()
+                + transactions.txindex_to_vsize.version(),
+            |by_pattern, height, first_txindex| {
+                let tx_count = *height_to_tx_count_iter.unwrap_get_inner(height);
+
+                let mut totals = vec![(0_usize, Sats::ZERO, 0_usize); by_pattern.len()];
+                (0..tx_count).for_each(|i| {
+                    let txindex = first_txindex + i;
+                    let pattern = txindex_to_txpattern_iter.unwrap_get_inner(txindex);
+                    let (count, volume, vsize) = &mut totals[pattern as usize];
+                    *count += 1;
+                    *volume += txindex_to_output_value_iter.unwrap_get_inner(txindex);
+                    *vsize += *txindex_to_vsize_iter.unwrap_get_inner(txindex);
+                });
+
+                by_pattern.iter_mut().zip(totals).try_for_each(
+                    |((count, volume, vsize), (total_count, total_volume, total_vsize))| -> Result<()> {
+                        count.forced_push_at(height, StoredUsize::from(total_count), exit)?;
+                        volume.forced_push_at(height, total_volume, exit)?;
+                        vsize.forced_push_at(height, StoredUsize::from(total_vsize), exit)
+                    },
+                )
+            },
+            exit,
+        )
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            vec![&self.txindex_to_txpattern as &dyn AnyCollectableVec],
+            self.by_pattern
+                .iter()
+                .flat_map(|vecs| vecs.vecs())
+                .collect::<Vec<_>>(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
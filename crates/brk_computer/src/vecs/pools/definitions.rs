@@ -0,0 +1,130 @@
+use std::{collections::BTreeMap, fs, path::Path, str::FromStr};
+
+use bitcoin::Address;
+use brk_core::{AddressBytes, AddressBytesHash, OutputType, PoolId, Version};
+use color_eyre::eyre::eyre;
+use log::info;
+use serde::Deserialize;
+
+const BUNDLED: &str = include_str!("pools.json");
+const OVERRIDE_FILE_NAME: &str = "pools.json";
+
+/// Mining pool definitions, the bundled ones unless overridden by a `pools.json` in the outputs directory
+///
+/// Ids are what's stored, so they should never be changed nor reused, and the version should be bumped on every edit
+/// as it's part of the version of every pool vec.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pools {
+    version: u64,
+    pools: Vec<Pool>,
+    #[serde(skip)]
+    addresses: BTreeMap<AddressBytesHash, PoolId>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pool {
+    pub id: PoolId,
+    /// Prefix of the names of the pool's vecs
+    pub slug: String,
+    pub name: String,
+    /// Searched for in the scriptSig of the coinbase
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Matched against the outputs of the coinbase, before the tags
+    #[serde(default)]
+    pub addresses: Vec<String>,
+}
+
+impl Pools {
+    pub fn import(outputs_dir: &Path) -> color_eyre::Result<Self> {
+        let path = outputs_dir.join(OVERRIDE_FILE_NAME);
+
+        let this = if path.is_file() {
+            info!("Using pool definitions from {}", path.display());
+            Self::parse(&fs::read_to_string(path)?)?
+        } else {
+            Self::parse(BUNDLED)?
+        };
+
+        Ok(this)
+    }
+
+    fn parse(json: &str) -> color_eyre::Result<Self> {
+        let mut this: Self = serde_json::from_str(json)?;
+
+        let mut slugs = vec![];
+        this.pools
+            .iter()
+            .try_for_each(|pool| -> color_eyre::Result<()> {
+                if pool.id.is_unknown() {
+                    return Err(eyre!("Pool id 0 is reserved for unknown pools"));
+                }
+                if pool.slug.is_empty()
+                    || pool.slug.contains("_to_")
+                    || !pool
+                        .slug
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                {
+                    return Err(eyre!("Invalid pool slug: {}", pool.slug));
+                }
+                if slugs.contains(&&pool.slug) {
+                    return Err(eyre!("Duplicate pool slug: {}", pool.slug));
+                }
+                slugs.push(&pool.slug);
+                Ok(())
+            })?;
+
+        let mut ids = this.pools.iter().map(|pool| pool.id).collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.len() != this.pools.len() {
+            return Err(eyre!("Duplicate pool ids"));
+        }
+
+        this.addresses = this
+            .pools
+            .iter()
+            .flat_map(|pool| pool.addresses.iter().map(move |address| (pool.id, address)))
+            .map(|(id, address)| -> color_eyre::Result<_> {
+                let script = Address::from_str(address)
+                    .map_err(|_| eyre!("Invalid pool address: {address}"))?
+                    .assume_checked()
+                    .script_pubkey();
+                let outputtype = OutputType::from(&script);
+                let bytes = AddressBytes::try_from((&script, outputtype))?;
+                Ok((AddressBytesHash::from((&bytes, outputtype)), id))
+            })
+            .collect::<color_eyre::Result<_>>()?;
+
+        Ok(this)
+    }
+
+    pub fn version(&self) -> Version {
+        Version::new(self.version)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Pool> {
+        self.pools.iter()
+    }
+
+    /// Payout addresses first as tags can be copied, then tags in the order of the definitions
+    pub fn find(&self, scriptsig: &[u8], payout_addresses: &[AddressBytesHash]) -> PoolId {
+        if let Some(id) = payout_addresses
+            .iter()
+            .find_map(|hash| self.addresses.get(hash))
+        {
+            return *id;
+        }
+
+        self.pools
+            .iter()
+            .find(|pool| {
+                pool.tags.iter().any(|tag| {
+                    let tag = tag.as_bytes();
+                    !tag.is_empty() && scriptsig.windows(tag.len()).any(|window| window == tag)
+                })
+            })
+            .map_or(PoolId::UNKNOWN, |pool| pool.id)
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use brk_core::{AddressBytes, AddressBytesHash, Height, OutputIndex, OutputType, PoolId, Version};
+use brk_exit::Exit;
+use brk_indexer::Indexer;
+use brk_vec::{AnyCollectableVec, AnyVec, Computation, EagerVec, ImportMode, VecIterator};
+
+use super::{Indexes, blocks, indexes};
+
+mod definitions;
+mod pool;
+
+pub use definitions::*;
+
+const VERSION: Version = Version::ZERO;
+
+/// Attribution of every block to a mining pool, from the tags and payout addresses of its coinbase
+#[derive(Clone)]
+pub struct Vecs {
+    pub pools: Pools,
+    /// `PoolId::UNKNOWN` when no definition matched
+    pub height_to_pool: EagerVec<Height, PoolId>,
+    pub pool_vecs: Vec<pool::Vecs>,
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        pools: Pools,
+        _computation: Computation,
+        mode: ImportMode,
+    ) -> color_eyre::Result<Self> {
+        let version = version + VERSION + pools.version();
+
+        Ok(Self {
+            height_to_pool: EagerVec::forced_import(path, "pool", version + Version::ZERO, mode)?,
+            pool_vecs: pools
+                .iter()
+                .map(|pool| pool::Vecs::forced_import(path, pool, version + Version::ZERO, mode))
+                .collect::<color_eyre::Result<Vec<_>>>()?,
+            pools,
+        })
+    }
+
+    pub fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        blocks: &blocks::Vecs,
+        starting_indexes: &Indexes,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        let pools = &self.pools;
+        let vecs = &indexer.vecs;
+
+        let mut txindex_to_first_outputindex_iter = vecs.txindex_to_first_outputindex.into_iter();
+        let mut outputindex_to_outputtype_iter = vecs.outputindex_to_outputtype.into_iter();
+        let mut outputindex_to_typeindex_iter = vecs.outputindex_to_typeindex.into_iter();
+        let mut p2pk65_iter = vecs.p2pk65addressindex_to_p2pk65bytes.into_iter();
+        let mut p2pk33_iter = vecs.p2pk33addressindex_to_p2pk33bytes.into_iter();
+        let mut p2pkh_iter = vecs.p2pkhaddressindex_to_p2pkhbytes.into_iter();
+        let mut p2sh_iter = vecs.p2shaddressindex_to_p2shbytes.into_iter();
+        let mut p2wpkh_iter = vecs.p2wpkhaddressindex_to_p2wpkhbytes.into_iter();
+        let mut p2wsh_iter = vecs.p2wshaddressindex_to_p2wshbytes.into_iter();
+        let mut p2tr_iter = vecs.p2traddressindex_to_p2trbytes.into_iter();
+        let mut p2a_iter = vecs.p2aaddressindex_to_p2abytes.into_iter();
+        let outputindex_len = vecs.outputindex_to_outputtype.len();
+
+        self.height_to_pool.compute_transform(
+            starting_indexes.height,
+            &vecs.height_to_first_txindex,
+            |(height, txindex, ..)| {
+                let first_outputindex = txindex_to_first_outputindex_iter.unwrap_get_inner(txindex);
+                let end_outputindex = txindex_to_first_outputindex_iter
+                    .get_inner(txindex + 1_usize)
+                    .map_or(outputindex_len, usize::from);
+
+                let payout_addresses = (usize::from(first_outputindex)..end_outputindex)
+                    .map(OutputIndex::from)
+                    .filter_map(|outputindex| {
+                        let outputtype =
+                            outputindex_to_outputtype_iter.unwrap_get_inner(outputindex);
+                        let typeindex = outputindex_to_typeindex_iter.unwrap_get_inner(outputindex);
+                        let bytes = match outputtype {
+                            OutputType::P2PK65 => {
+                                AddressBytes::from(p2pk65_iter.unwrap_get_inner(typeindex.into()))
+                            }
+                            OutputType::P2PK33 => {
+                                AddressBytes::from(p2pk33_iter.unwrap_get_inner(typeindex.into()))
+                            }
+                            OutputType::P2PKH => {
+                                AddressBytes::from(p2pkh_iter.unwrap_get_inner(typeindex.into()))
+                            }
+                            OutputType::P2SH => {
+                                AddressBytes::from(p2sh_iter.unwrap_get_inner(typeindex.into()))
+                            }
+                            OutputType::P2WPKH => {
+                                AddressBytes::from(p2wpkh_iter.unwrap_get_inner(typeindex.into()))
+                            }
+                            OutputType::P2WSH => {
+                                AddressBytes::from(p2wsh_iter.unwrap_get_inner(typeindex.into()))
+                            }
+                            OutputType::P2TR => {
+                                AddressBytes::from(p2tr_iter.unwrap_get_inner(typeindex.into()))
+                            }
+                            OutputType::P2A => {
+                                AddressBytes::from(p2a_iter.unwrap_get_inner(typeindex.into()))
+                            }
+                            OutputType::Empty
+                            | OutputType::OpReturn
+                            | OutputType::P2MS
+                            | OutputType::Unknown => return None,
+                        };
+                        Some(AddressBytesHash::from((&bytes, outputtype)))
+                    })
+                    .collect::<Vec<_>>();
+
+                let scriptsig = vecs
+                    .height_to_coinbase_scriptsig
+                    .get(height)
+                    .unwrap()
+                    .unwrap_or_default();
+
+                (height, pools.find(&scriptsig, &payout_addresses))
+            },
+            exit,
+        )?;
+
+        let height_to_pool = &self.height_to_pool;
+        self.pool_vecs.iter_mut().try_for_each(|vecs| {
+            vecs.compute(
+                indexer,
+                indexes,
+                blocks,
+                height_to_pool,
+                starting_indexes,
+                exit,
+            )
+        })?;
+
+        Ok(())
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            vec![&self.height_to_pool as &dyn AnyCollectableVec],
+            self.pool_vecs
+                .iter()
+                .flat_map(|vecs| vecs.vecs())
+                .collect::<Vec<_>>(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
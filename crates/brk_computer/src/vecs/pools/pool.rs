@@ -0,0 +1,166 @@
+use std::path::Path;
+
+use brk_core::{
+    DateIndex, DifficultyEpoch, Height, PoolId, StoredF64, StoredU32, Version, WeekIndex,
+};
+use brk_exit::Exit;
+use brk_indexer::Indexer;
+use brk_vec::{AnyCollectableVec, EagerVec, ImportMode, StoredIndex, VecIterator};
+
+use crate::vecs::{
+    Indexes, blocks,
+    grouped::{ComputedVecBuilder, ComputedVecsFromHeight, StorableVecGeneatorOptions},
+    indexes,
+};
+
+use super::Pool;
+
+const VERSION: Version = Version::ZERO;
+
+/// Blocks mined by a pool and its share of all the blocks mined
+#[derive(Clone)]
+pub struct Vecs {
+    pub id: PoolId,
+    pub indexes_to_blocks_mined: ComputedVecsFromHeight<StoredU32>,
+    pub dateindex_to_dominance: EagerVec<DateIndex, StoredF64>,
+    pub weekindex_to_dominance: EagerVec<WeekIndex, StoredF64>,
+    pub difficultyepoch_to_dominance: EagerVec<DifficultyEpoch, StoredF64>,
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        pool: &Pool,
+        version: Version,
+        mode: ImportMode,
+    ) -> color_eyre::Result<Self> {
+        let dominance = format!("{}_dominance", pool.slug);
+
+        Ok(Self {
+            id: pool.id,
+            indexes_to_blocks_mined: ComputedVecsFromHeight::forced_import(
+                path,
+                &format!("{}_blocks_mined", pool.slug),
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+            )?,
+            dateindex_to_dominance: EagerVec::forced_import(
+                path,
+                &dominance,
+                version + VERSION + Version::ZERO,
+                mode,
+            )?,
+            weekindex_to_dominance: EagerVec::forced_import(
+                path,
+                &dominance,
+                version + VERSION + Version::ZERO,
+                mode,
+            )?,
+            difficultyepoch_to_dominance: EagerVec::forced_import(
+                path,
+                &dominance,
+                version + VERSION + Version::ZERO,
+                mode,
+            )?,
+        })
+    }
+
+    pub fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        blocks: &blocks::Vecs,
+        height_to_pool: &EagerVec<Height, PoolId>,
+        starting_indexes: &Indexes,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        let id = self.id;
+        self.indexes_to_blocks_mined.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |v, _, _, starting_indexes, exit| {
+                v.compute_transform(
+                    starting_indexes.height,
+                    height_to_pool,
+                    |(h, pool, ..)| (h, StoredU32::from(u32::from(pool == id))),
+                    exit,
+                )
+            },
+        )?;
+
+        let block_count = &blocks.indexes_to_block_count;
+
+        Self::compute_dominance(
+            &mut self.dateindex_to_dominance,
+            starting_indexes.dateindex,
+            &self.indexes_to_blocks_mined.dateindex,
+            &block_count.dateindex,
+            exit,
+        )?;
+        Self::compute_dominance(
+            &mut self.weekindex_to_dominance,
+            starting_indexes.weekindex,
+            &self.indexes_to_blocks_mined.weekindex,
+            &block_count.weekindex,
+            exit,
+        )?;
+        Self::compute_dominance(
+            &mut self.difficultyepoch_to_dominance,
+            starting_indexes.difficultyepoch,
+            &self.indexes_to_blocks_mined.difficultyepoch,
+            &block_count.difficultyepoch,
+            exit,
+        )?;
+
+        Ok(())
+    }
+
+    /// Percentage of the blocks of each period mined by the pool
+    fn compute_dominance<I>(
+        dominance: &mut EagerVec<I, StoredF64>,
+        starting_index: I,
+        blocks_mined: &ComputedVecBuilder<I, StoredU32>,
+        block_count: &ComputedVecBuilder<I, StoredU32>,
+        exit: &Exit,
+    ) -> color_eyre::Result<()>
+    where
+        I: StoredIndex,
+    {
+        let mut block_count_iter = block_count.unwrap_sum().into_iter();
+        dominance.compute_transform(
+            starting_index,
+            blocks_mined.unwrap_sum(),
+            |(i, blocks_mined, ..)| {
+                let block_count = f64::from(block_count_iter.unwrap_get_inner(i));
+                let dominance = if block_count == 0.0 {
+                    0.0
+                } else {
+                    f64::from(blocks_mined) / block_count * 100.0
+                };
+                (i, StoredF64::from(dominance))
+            },
+            exit,
+        )?;
+        Ok(())
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.indexes_to_blocks_mined.vecs(),
+            vec![
+                &self.dateindex_to_dominance as &dyn AnyCollectableVec,
+                &self.weekindex_to_dominance,
+                &self.difficultyepoch_to_dominance,
+            ],
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
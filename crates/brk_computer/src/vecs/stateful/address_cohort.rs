@@ -4,7 +4,7 @@ use brk_core::{Bitcoin, DateIndex, Dollars, Height, Result, StoredUsize, Version
 use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_state::{AddressCohortState, CohortStateTrait};
-use brk_vec::{AnyCollectableVec, AnyIterableVec, AnyVec, Computation, EagerVec, Format};
+use brk_vec::{AnyCollectableVec, AnyIterableVec, AnyVec, Computation, EagerVec, ImportMode};
 
 use crate::vecs::{
     Indexes, fetched, indexes, market,
@@ -30,7 +30,7 @@ impl CohortVecs for Vecs {
         path: &Path,
         cohort_name: Option<&str>,
         computation: Computation,
-        format: Format,
+        mode: ImportMode,
         version: Version,
         fetched: Option<&fetched::Vecs>,
         states_path: &Path,
@@ -51,13 +51,13 @@ impl CohortVecs for Vecs {
                 path,
                 &suffix("address_count"),
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             inner: common::Vecs::forced_import(
                 path,
                 cohort_name,
                 computation,
-                format,
+                mode,
                 version,
                 fetched,
                 compute_relative_to_all,
@@ -3,7 +3,7 @@ use std::path::Path;
 use brk_core::{
     AddressGroups, GroupFilter, GroupedByFromSize, GroupedBySizeRange, GroupedByUpToSize, Version,
 };
-use brk_vec::{Computation, Format};
+use brk_vec::{Computation, ImportMode};
 
 use crate::vecs::{
     fetched,
@@ -20,7 +20,7 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
         fetched: Option<&fetched::Vecs>,
         states_path: &Path,
     ) -> color_eyre::Result<Self> {
@@ -31,7 +31,7 @@ impl Vecs {
                         path,
                         Some("0sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -41,7 +41,7 @@ impl Vecs {
                         path,
                         Some("from_1sat_to_10sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -51,7 +51,7 @@ impl Vecs {
                         path,
                         Some("from_10sats_to_100sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -61,7 +61,7 @@ impl Vecs {
                         path,
                         Some("from_100sats_to_1_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -71,7 +71,7 @@ impl Vecs {
                         path,
                         Some("from_1_000sats_to_10_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -81,7 +81,7 @@ impl Vecs {
                         path,
                         Some("from_10_000sats_to_100_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -91,7 +91,7 @@ impl Vecs {
                         path,
                         Some("from_100_000sats_to_1_000_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -101,7 +101,7 @@ impl Vecs {
                         path,
                         Some("from_1_000_000sats_to_10_000_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -111,7 +111,7 @@ impl Vecs {
                         path,
                         Some("from_10_000_000sats_to_1btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -121,7 +121,7 @@ impl Vecs {
                         path,
                         Some("from_1btc_to_10btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -131,7 +131,7 @@ impl Vecs {
                         path,
                         Some("from_10btc_to_100btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -141,7 +141,7 @@ impl Vecs {
                         path,
                         Some("from_100btc_to_1_000btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -151,7 +151,7 @@ impl Vecs {
                         path,
                         Some("from_1_000btc_to_10_000btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -161,7 +161,7 @@ impl Vecs {
                         path,
                         Some("from_10_000btc_to_100_000btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -171,7 +171,7 @@ impl Vecs {
                         path,
                         Some("from_100_000btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -183,7 +183,7 @@ impl Vecs {
                         path,
                         Some("up_to_1_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -193,7 +193,7 @@ impl Vecs {
                         path,
                         Some("up_to_10_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -203,7 +203,7 @@ impl Vecs {
                         path,
                         Some("up_to_1btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -213,7 +213,7 @@ impl Vecs {
                         path,
                         Some("up_to_10btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -223,7 +223,7 @@ impl Vecs {
                         path,
                         Some("up_to_100btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -235,7 +235,7 @@ impl Vecs {
                         path,
                         Some("from_1_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -245,7 +245,7 @@ impl Vecs {
                         path,
                         Some("from_1btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -255,7 +255,7 @@ impl Vecs {
                         path,
                         Some("from_10btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -265,7 +265,7 @@ impl Vecs {
                         path,
                         Some("from_100btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
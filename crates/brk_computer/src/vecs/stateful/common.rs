@@ -7,14 +7,15 @@ use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_state::{CohortState, CohortStateTrait};
 use brk_vec::{
-    AnyCollectableVec, AnyIterableVec, AnyVec, Computation, EagerVec, Format, VecIterator,
+    AnyCollectableVec, AnyIterableVec, AnyVec, Computation, EagerVec, ImportMode, VecIterator,
 };
 
 use crate::vecs::{
     Indexes, fetched,
     grouped::{
         ComputedHeightValueVecs, ComputedRatioVecsFromDateIndex, ComputedValueVecsFromDateIndex,
-        ComputedVecsFromDateIndex, ComputedVecsFromHeight, StorableVecGeneatorOptions,
+        ComputedValueVecsFromHeight, ComputedVecsFromDateIndex, ComputedVecsFromHeight,
+        StorableVecGeneatorOptions,
     },
     indexes, market,
 };
@@ -48,9 +49,11 @@ pub struct Vecs {
     pub height_to_value_destroyed: Option<EagerVec<Height, Dollars>>,
     pub height_to_satblocks_destroyed: EagerVec<Height, Sats>,
     pub height_to_satdays_destroyed: EagerVec<Height, Sats>,
+    pub height_to_sent: EagerVec<Height, Sats>,
 
     pub indexes_to_coinblocks_destroyed: ComputedVecsFromHeight<StoredF64>,
     pub indexes_to_coindays_destroyed: ComputedVecsFromHeight<StoredF64>,
+    pub indexes_to_sent: ComputedValueVecsFromHeight,
     pub dateindex_to_adjusted_spent_output_profit_ratio: Option<EagerVec<DateIndex, StoredF32>>,
     pub indexes_to_realized_cap_30d_change: Option<ComputedVecsFromDateIndex<Dollars>>,
     pub dateindex_to_sell_side_risk_ratio: Option<EagerVec<DateIndex, StoredF32>>,
@@ -130,7 +133,7 @@ impl Vecs {
         path: &Path,
         cohort_name: Option<&str>,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
         version: Version,
         fetched: Option<&fetched::Vecs>,
         compute_relative_to_all: bool,
@@ -148,7 +151,7 @@ impl Vecs {
                     path,
                     &suffix("supply_in_profit"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -157,7 +160,7 @@ impl Vecs {
                     path,
                     &suffix("supply_in_profit"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -167,7 +170,7 @@ impl Vecs {
                     &suffix("supply_in_profit"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                     compute_dollars,
                 )
@@ -178,7 +181,7 @@ impl Vecs {
                     path,
                     &suffix("supply_even"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -187,7 +190,7 @@ impl Vecs {
                     path,
                     &suffix("supply_even"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -197,7 +200,7 @@ impl Vecs {
                     &suffix("supply_even"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                     compute_dollars,
                 )
@@ -208,7 +211,7 @@ impl Vecs {
                     path,
                     &suffix("supply_in_loss"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -217,7 +220,7 @@ impl Vecs {
                     path,
                     &suffix("supply_in_loss"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -227,7 +230,7 @@ impl Vecs {
                     &suffix("supply_in_loss"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                     compute_dollars,
                 )
@@ -238,7 +241,7 @@ impl Vecs {
                     path,
                     &suffix("unrealized_profit"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -247,7 +250,7 @@ impl Vecs {
                     path,
                     &suffix("unrealized_profit"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -257,7 +260,7 @@ impl Vecs {
                     &suffix("unrealized_profit"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -267,7 +270,7 @@ impl Vecs {
                     path,
                     &suffix("unrealized_loss"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -276,7 +279,7 @@ impl Vecs {
                     path,
                     &suffix("min_price_paid"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -285,7 +288,7 @@ impl Vecs {
                     path,
                     &suffix("max_price_paid"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -294,7 +297,7 @@ impl Vecs {
                     path,
                     &suffix("unrealized_loss"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -304,7 +307,7 @@ impl Vecs {
                     &suffix("unrealized_loss"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -314,7 +317,7 @@ impl Vecs {
                     path,
                     &suffix("realized_cap"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -324,7 +327,7 @@ impl Vecs {
                     &suffix("realized_cap"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -335,7 +338,7 @@ impl Vecs {
                     &suffix("min_price_paid"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -346,7 +349,7 @@ impl Vecs {
                     &suffix("max_price_paid"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -355,14 +358,14 @@ impl Vecs {
                 path,
                 &suffix("supply"),
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_supply_value: ComputedHeightValueVecs::forced_import(
                 path,
                 &suffix("supply"),
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 compute_dollars,
             )?,
             indexes_to_supply: ComputedValueVecsFromDateIndex::forced_import(
@@ -370,7 +373,7 @@ impl Vecs {
                 &suffix("supply"),
                 true,
                 version + VERSION + Version::ONE,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
                 compute_dollars,
             )?,
@@ -378,14 +381,14 @@ impl Vecs {
                 path,
                 &suffix("utxo_count"),
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_utxo_count: ComputedVecsFromHeight::forced_import(
                 path,
                 &suffix("utxo_count"),
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             indexes_to_realized_price: compute_dollars.then(|| {
@@ -394,7 +397,7 @@ impl Vecs {
                     &suffix("realized_price"),
                     true,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -405,7 +408,7 @@ impl Vecs {
                     &suffix("realized_price"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -414,7 +417,7 @@ impl Vecs {
                     path,
                     &suffix("realized_profit"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -424,7 +427,7 @@ impl Vecs {
                     &suffix("realized_profit"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default()
                         .add_sum()
                         .add_cumulative(),
@@ -436,7 +439,7 @@ impl Vecs {
                     path,
                     &suffix("realized_loss"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -446,7 +449,7 @@ impl Vecs {
                     &suffix("realized_loss"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default()
                         .add_sum()
                         .add_cumulative(),
@@ -459,7 +462,7 @@ impl Vecs {
                     &suffix("negative_realized_loss"),
                     true,
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_sum().add_cumulative(),
                 )
                 .unwrap()
@@ -469,7 +472,7 @@ impl Vecs {
                     path,
                     &suffix("value_created"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -479,7 +482,7 @@ impl Vecs {
                     &suffix("value_created"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_sum(),
                 )
                 .unwrap()
@@ -490,7 +493,7 @@ impl Vecs {
                     &suffix("realized_value"),
                     true,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_sum(),
                 )
                 .unwrap()
@@ -500,7 +503,7 @@ impl Vecs {
                     path,
                     &suffix("adjusted_value_created"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -510,7 +513,7 @@ impl Vecs {
                     &suffix("adjusted_value_created"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_sum(),
                 )
                 .unwrap()
@@ -520,7 +523,7 @@ impl Vecs {
                     path,
                     &suffix("value_destroyed"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -530,7 +533,7 @@ impl Vecs {
                     &suffix("value_destroyed"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_sum(),
                 )
                 .unwrap()
@@ -540,7 +543,7 @@ impl Vecs {
                     path,
                     &suffix("adjusted_value_destroyed"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -550,7 +553,7 @@ impl Vecs {
                     &suffix("adjusted_value_destroyed"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_sum(),
                 )
                 .unwrap()
@@ -561,7 +564,7 @@ impl Vecs {
                     &suffix("realized_cap_30d_change"),
                     true,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -572,7 +575,7 @@ impl Vecs {
                     &suffix("net_realized_profit_and_loss"),
                     true,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default()
                         .add_sum()
                         .add_cumulative(),
@@ -584,7 +587,7 @@ impl Vecs {
                     path,
                     &suffix("sell_side_risk_ratio"),
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -593,7 +596,7 @@ impl Vecs {
                     path,
                     &suffix("spent_output_profit_ratio"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -602,7 +605,7 @@ impl Vecs {
                     path,
                     &suffix("adjusted_spent_output_profit_ratio"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -611,7 +614,7 @@ impl Vecs {
                 &suffix("halved_supply"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 compute_dollars,
             )?,
             indexes_to_halved_supply: ComputedValueVecsFromDateIndex::forced_import(
@@ -619,7 +622,7 @@ impl Vecs {
                 &suffix("halved_supply"),
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
                 compute_dollars,
             )?,
@@ -628,7 +631,7 @@ impl Vecs {
                     path,
                     &suffix("negative_unrealized_loss"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -638,7 +641,7 @@ impl Vecs {
                     &suffix("negative_unrealized_loss"),
                     true,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -648,7 +651,7 @@ impl Vecs {
                     path,
                     &suffix("net_unrealized_profit_and_loss"),
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -658,7 +661,7 @@ impl Vecs {
                     &suffix("net_unrealized_profit_and_loss"),
                     true,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -669,7 +672,7 @@ impl Vecs {
                         path,
                         &suffix("net_unrealized_profit_and_loss_relative_to_market_cap"),
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                     )
                     .unwrap()
                 },
@@ -681,7 +684,7 @@ impl Vecs {
                         &suffix("net_unrealized_profit_and_loss_relative_to_market_cap"),
                         true,
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                         StorableVecGeneatorOptions::default().add_last(),
                     )
                     .unwrap()
@@ -693,7 +696,7 @@ impl Vecs {
                     &suffix("realized_profit_relative_to_realized_cap"),
                     true,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_sum(),
                 )
                 .unwrap()
@@ -704,7 +707,7 @@ impl Vecs {
                     &suffix("realized_loss_relative_to_realized_cap"),
                     true,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_sum(),
                 )
                 .unwrap()
@@ -716,7 +719,7 @@ impl Vecs {
                         &suffix("net_realized_profit_and_loss_relative_to_realized_cap"),
                         true,
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                         StorableVecGeneatorOptions::default().add_sum(),
                     )
                     .unwrap()
@@ -728,7 +731,7 @@ impl Vecs {
                     &suffix("supply_even"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     compute_dollars,
                 )
                 .unwrap()
@@ -739,7 +742,7 @@ impl Vecs {
                     &suffix("supply_in_loss"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     compute_dollars,
                 )
                 .unwrap()
@@ -750,7 +753,7 @@ impl Vecs {
                     &suffix("supply_in_profit"),
                     false,
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                     compute_dollars,
                 )
                 .unwrap()
@@ -760,7 +763,7 @@ impl Vecs {
                     path,
                     &suffix("supply_even_relative_to_own_supply"),
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -769,7 +772,7 @@ impl Vecs {
                     path,
                     &suffix("supply_in_loss_relative_to_own_supply"),
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -778,7 +781,7 @@ impl Vecs {
                     path,
                     &suffix("supply_in_profit_relative_to_own_supply"),
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                 )
                 .unwrap()
             }),
@@ -788,7 +791,7 @@ impl Vecs {
                     &suffix("supply_even_relative_to_own_supply"),
                     true,
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -799,7 +802,7 @@ impl Vecs {
                     &suffix("supply_in_loss_relative_to_own_supply"),
                     true,
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -810,7 +813,7 @@ impl Vecs {
                     &suffix("supply_in_profit_relative_to_own_supply"),
                     true,
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -821,7 +824,7 @@ impl Vecs {
                     &suffix("supply_relative_to_circulating_supply"),
                     true,
                     version + VERSION + Version::ONE,
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last(),
                 )
                 .unwrap()
@@ -833,7 +836,7 @@ impl Vecs {
                         path,
                         &suffix("supply_even_relative_to_circulating_supply"),
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                     )
                     .unwrap()
                 }),
@@ -844,7 +847,7 @@ impl Vecs {
                         path,
                         &suffix("supply_in_loss_relative_to_circulating_supply"),
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                     )
                     .unwrap()
                 }),
@@ -855,7 +858,7 @@ impl Vecs {
                         path,
                         &suffix("supply_in_profit_relative_to_circulating_supply"),
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                     )
                     .unwrap()
                 }),
@@ -867,7 +870,7 @@ impl Vecs {
                         &suffix("supply_even_relative_to_circulating_supply"),
                         true,
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                         StorableVecGeneatorOptions::default().add_last(),
                     )
                     .unwrap()
@@ -880,7 +883,7 @@ impl Vecs {
                         &suffix("supply_in_loss_relative_to_circulating_supply"),
                         true,
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                         StorableVecGeneatorOptions::default().add_last(),
                     )
                     .unwrap()
@@ -893,7 +896,7 @@ impl Vecs {
                         &suffix("supply_in_profit_relative_to_circulating_supply"),
                         true,
                         version + VERSION + Version::ONE,
-                        format,
+                        mode,
                         StorableVecGeneatorOptions::default().add_last(),
                     )
                     .unwrap()
@@ -902,20 +905,26 @@ impl Vecs {
                 path,
                 &suffix("satblocks_destroyed"),
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             height_to_satdays_destroyed: EagerVec::forced_import(
                 path,
                 &suffix("satdays_destroyed"),
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
+            )?,
+            height_to_sent: EagerVec::forced_import(
+                path,
+                &suffix("sent"),
+                version + VERSION + Version::ZERO,
+                mode,
             )?,
             indexes_to_coinblocks_destroyed: ComputedVecsFromHeight::forced_import(
                 path,
                 &suffix("coinblocks_destroyed"),
                 true,
                 version + VERSION + Version::TWO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_sum().add_cumulative(),
             )?,
             indexes_to_coindays_destroyed: ComputedVecsFromHeight::forced_import(
@@ -923,8 +932,17 @@ impl Vecs {
                 &suffix("coindays_destroyed"),
                 true,
                 version + VERSION + Version::TWO,
-                format,
+                mode,
+                StorableVecGeneatorOptions::default().add_sum().add_cumulative(),
+            )?,
+            indexes_to_sent: ComputedValueVecsFromHeight::forced_import(
+                path,
+                &suffix("sent"),
+                false,
+                version + VERSION + Version::ZERO,
+                mode,
                 StorableVecGeneatorOptions::default().add_sum().add_cumulative(),
+                compute_dollars,
             )?,
             indexes_to_cumulative_net_realized_profit_and_loss_30d_change: compute_dollars.then(|| {
                 ComputedVecsFromDateIndex::forced_import(
@@ -932,7 +950,7 @@ impl Vecs {
                     &format!("cumulative_{}", suffix("net_realized_profit_and_loss_30d_change")),
                     true,
                     version + VERSION + Version::new(3),
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last()
                 )
                 .unwrap()
@@ -943,7 +961,7 @@ impl Vecs {
                     &format!("cumulative_{}", suffix("net_realized_profit_and_loss_30d_change_relative_to_realized_cap")),
                     true,
                     version + VERSION + Version::new(3),
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last()
                 )
                 .unwrap()
@@ -954,7 +972,7 @@ impl Vecs {
                     &format!("cumulative_{}", suffix("net_realized_profit_and_loss_30d_change_relative_to_market_cap")),
                     true,
                     version + VERSION + Version::new(3),
-                    format,
+                    mode,
                     StorableVecGeneatorOptions::default().add_last()
                 )
                 .unwrap()
@@ -1010,6 +1028,7 @@ impl Vecs {
                 .map_or(usize::MAX, |v| v.len()),
             self.height_to_satdays_destroyed.len(),
             self.height_to_satblocks_destroyed.len(),
+            self.height_to_sent.len(),
         ]
         .into_iter()
         .map(Height::from)
@@ -1057,6 +1076,11 @@ impl Vecs {
                 base_version + self.height_to_satdays_destroyed.inner_version(),
             )?;
 
+        self.height_to_sent
+            .validate_computed_version_or_reset_file(
+                base_version + self.height_to_sent.inner_version(),
+            )?;
+
         if let Some(height_to_realized_cap) = self.height_to_realized_cap.as_mut().as_mut() {
             height_to_realized_cap.validate_computed_version_or_reset_file(
                 base_version + height_to_realized_cap.inner_version(),
@@ -1286,6 +1310,9 @@ impl Vecs {
         self.height_to_satdays_destroyed
             .forced_push_at(height, state.satdays_destroyed, exit)?;
 
+        self.height_to_sent
+            .forced_push_at(height, state.sent, exit)?;
+
         if let Some(height_to_realized_cap) = self.height_to_realized_cap.as_mut() {
             let realized = state.realized.as_ref().unwrap_or_else(|| {
                 dbg!((&state.realized, &state.supply));
@@ -1420,6 +1447,7 @@ impl Vecs {
         self.height_to_utxo_count.safe_flush(exit)?;
         self.height_to_satdays_destroyed.safe_flush(exit)?;
         self.height_to_satblocks_destroyed.safe_flush(exit)?;
+        self.height_to_sent.safe_flush(exit)?;
 
         if let Some(height_to_realized_cap) = self.height_to_realized_cap.as_mut() {
             height_to_realized_cap.safe_flush(exit)?;
@@ -1544,6 +1572,15 @@ impl Vecs {
                 .as_slice(),
             exit,
         )?;
+        self.height_to_sent.compute_sum_of_others(
+            starting_indexes.height,
+            others
+                .iter()
+                .map(|v| &v.height_to_sent)
+                .collect::<Vec<_>>()
+                .as_slice(),
+            exit,
+        )?;
 
         if let Some(height_to_realized_cap) = &mut self.height_to_realized_cap {
             height_to_realized_cap.compute_sum_of_others(
@@ -1912,6 +1949,15 @@ impl Vecs {
             },
         )?;
 
+        self.indexes_to_sent.compute_rest(
+            indexer,
+            indexes,
+            fetched,
+            starting_indexes,
+            exit,
+            Some(&self.height_to_sent),
+        )?;
+
         Ok(())
     }
 
@@ -2705,6 +2751,7 @@ impl Vecs {
                 &self.height_to_utxo_count,
                 &self.height_to_satblocks_destroyed,
                 &self.height_to_satdays_destroyed,
+                &self.height_to_sent,
             ],
             self.height_to_realized_cap
                 .as_ref()
@@ -2914,6 +2961,7 @@ impl Vecs {
                 .map_or(vec![], |v| v.vecs()),
             self.indexes_to_coinblocks_destroyed.vecs(),
             self.indexes_to_coindays_destroyed.vecs(),
+            self.indexes_to_sent.vecs(),
             self.indexes_to_cumulative_net_realized_profit_and_loss_30d_change.as_ref()
             .map_or(vec![], |v| v.vecs()),
             self.indexes_to_cumulative_net_realized_profit_and_loss_30d_change_relative_to_realized_cap.as_ref()
@@ -8,7 +8,7 @@ use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_vec::{
     AnyCollectableVec, AnyVec, CollectableVec, Computation, EagerVec, Format, GenericStoredVec,
-    StoredIndex, StoredVec, UnsafeSlice, VecIterator,
+    ImportMode, StoredIndex, StoredVec, UnsafeSlice, VecIterator,
 };
 use log::info;
 use rayon::prelude::*;
@@ -27,10 +27,11 @@ mod address_cohort;
 mod address_cohorts;
 mod common;
 mod r#trait;
+pub mod urpd;
 mod utxo_cohort;
 mod utxo_cohorts;
 
-const VERSION: Version = Version::new(5);
+const VERSION: Version = Version::new(6);
 
 #[derive(Clone)]
 pub struct Vecs {
@@ -44,6 +45,7 @@ pub struct Vecs {
     pub addresstype_to_height_to_address_count: GroupedByAddressType<EagerVec<Height, StoredUsize>>,
     pub utxos_vecs: utxo_cohorts::Vecs,
     pub address_vecs: address_cohorts::Vecs,
+    pub urpd: Option<urpd::Vecs>,
 }
 
 impl Vecs {
@@ -51,8 +53,9 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
         fetched: Option<&fetched::Vecs>,
+        urpd_buckets: urpd::UrpdBuckets,
     ) -> color_eyre::Result<Self> {
         let compute_dollars = fetched.is_some();
 
@@ -66,21 +69,24 @@ impl Vecs {
                 &states_path,
                 "chain",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                ImportMode {
+                    format: Format::Raw,
+                    ..mode
+                },
             )?,
 
             height_to_unspendable_supply: EagerVec::forced_import(
                 path,
                 "unspendable_supply",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_unspendable_supply: ComputedValueVecsFromHeight::forced_import(
                 path,
                 "unspendable_supply",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
                 compute_dollars,
             )?,
@@ -88,14 +94,14 @@ impl Vecs {
                 path,
                 "opreturn_supply",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             indexes_to_opreturn_supply: ComputedValueVecsFromHeight::forced_import(
                 path,
                 "opreturn_supply",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
                 compute_dollars,
             )?,
@@ -103,63 +109,63 @@ impl Vecs {
                 path,
                 "address_count",
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
             )?,
             addresstype_to_height_to_address_count: GroupedByAddressType {
                 p2pk65: EagerVec::forced_import(
                     path,
                     "p2pk65_address_count",
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )?,
                 p2pk33: EagerVec::forced_import(
                     path,
                     "p2pk33_address_count",
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )?,
                 p2pkh: EagerVec::forced_import(
                     path,
                     "p2pkh_address_count",
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )?,
                 p2sh: EagerVec::forced_import(
                     path,
                     "p2sh_address_count",
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )?,
                 p2wpkh: EagerVec::forced_import(
                     path,
                     "p2wpkh_address_count",
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )?,
                 p2wsh: EagerVec::forced_import(
                     path,
                     "p2wsh_address_count",
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )?,
                 p2tr: EagerVec::forced_import(
                     path,
                     "p2tr_address_count",
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )?,
                 p2a: EagerVec::forced_import(
                     path,
                     "p2a_address_count",
                     version + VERSION + Version::ZERO,
-                    format,
+                    mode,
                 )?,
             },
             utxos_vecs: utxo_cohorts::Vecs::forced_import(
                 path,
                 version,
                 _computation,
-                format,
+                mode,
                 fetched,
                 &states_path,
             )?,
@@ -167,10 +173,19 @@ impl Vecs {
                 path,
                 version,
                 _computation,
-                format,
+                mode,
                 fetched,
                 &states_path,
             )?,
+            urpd: compute_dollars.then(|| {
+                urpd::Vecs::forced_import(
+                    path,
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    urpd_buckets,
+                )
+                .unwrap()
+            }),
         })
     }
 
@@ -267,15 +282,25 @@ impl Vecs {
             .validate_computed_version_or_reset_file(
                 base_version + self.height_to_opreturn_supply.inner_version(),
             )?;
+        if let Some(urpd) = self.urpd.as_mut() {
+            urpd.validate_computed_versions(base_version)?;
+        }
 
         let mut chain_state: Vec<BlockState> = vec![];
         let mut chain_state_starting_height = Height::from(self.chain_state.len());
 
+        let urpd_starting_height = self
+            .urpd
+            .as_ref()
+            .and_then(|urpd| dateindex_to_first_height_iter.get_inner(urpd.starting_dateindex()))
+            .unwrap_or(Height::MAX);
+
         let stateful_starting_height = match separate_utxo_vecs
             .par_iter_mut()
             .map(|(_, v)| v.starting_height())
             .min()
             .unwrap_or_default()
+            .min(urpd_starting_height)
             .min(chain_state_starting_height)
             .cmp(&chain_state_starting_height)
         {
@@ -381,46 +406,54 @@ impl Vecs {
 
                         let sent_handle = s.spawn(|| {
                             // Skip coinbase
-                            (first_inputindex + 1..first_inputindex + *input_count)
-                                .into_par_iter()
+                            let inputindexes = (first_inputindex + 1
+                                ..first_inputindex + *input_count)
                                 .map(InputIndex::from)
-                                .map(|inputindex| {
-                                    let outputindex = inputindex_to_outputindex
-                                        .get_or_read(inputindex, &inputindex_to_outputindex_mmap)
-                                        .unwrap()
-                                        .unwrap()
-                                        .into_inner();
-
-                                    let value = outputindex_to_value
-                                        .get_or_read(outputindex, &outputindex_to_value_mmap)
-                                        .unwrap()
-                                        .unwrap()
-                                        .into_inner();
-
-                                    let input_type = outputindex_to_outputtype
-                                        .get_or_read(outputindex, &outputindex_to_outputtype_mmap)
-                                        .unwrap()
+                                .collect::<Vec<_>>();
+
+                            // Spent outputs are scattered, gathered reads decode each page only once
+                            let outputindexes = inputindex_to_outputindex
+                                .gather(&inputindexes, &inputindex_to_outputindex_mmap)
+                                .unwrap();
+
+                            let ((values, input_types), heights) = rayon::join(
+                                || {
+                                    rayon::join(
+                                        || {
+                                            outputindex_to_value
+                                                .gather(&outputindexes, &outputindex_to_value_mmap)
+                                                .unwrap()
+                                        },
+                                        || {
+                                            outputindex_to_outputtype
+                                                .gather(
+                                                    &outputindexes,
+                                                    &outputindex_to_outputtype_mmap,
+                                                )
+                                                .unwrap()
+                                        },
+                                    )
+                                },
+                                || {
+                                    let input_txindexes = outputindex_to_txindex
+                                        .gather(&outputindexes, &outputindex_to_txindex_mmap)
+                                        .unwrap();
+
+                                    txindex_to_height
+                                        .gather(&input_txindexes, &txindex_to_height_mmap)
                                         .unwrap()
-                                        .into_inner();
-
-                                    // dbg!(input_type);
+                                },
+                            );
 
+                            heights
+                                .into_par_iter()
+                                .zip(values)
+                                .zip(input_types)
+                                .map(|((height, value), input_type)| {
                                     if input_type.is_unspendable() {
                                         unreachable!()
                                     }
 
-                                    let input_txindex = outputindex_to_txindex
-                                        .get_or_read(outputindex, &outputindex_to_txindex_mmap)
-                                        .unwrap()
-                                        .unwrap()
-                                        .into_inner();
-
-                                    let height = txindex_to_height
-                                        .get_or_read(input_txindex, &txindex_to_height_mmap)
-                                        .unwrap()
-                                        .unwrap()
-                                        .into_inner();
-
                                     (height, value, input_type)
                                 })
                                 .fold(
@@ -560,6 +593,18 @@ impl Vecs {
                         )
                     })?;
 
+                    if let Some(urpd) = self.urpd.as_mut().filter(|_| is_date_last_height) {
+                        urpd.compute_then_force_push(
+                            dateindex,
+                            self.utxos_vecs
+                                .by_date_range
+                                .as_vec()
+                                .into_iter()
+                                .map(|(filter, v)| (filter, &*v.state.price_to_amount)),
+                            exit,
+                        )?;
+                    }
+
                     if height != Height::ZERO && height.unwrap_to_usize() % 20_000 == 0 {
                         info!("Flushing...");
                         exit.block();
@@ -662,6 +707,9 @@ impl Vecs {
             .try_for_each(|(_, v)| v.safe_flush_stateful_vecs(height, exit))?;
         self.height_to_unspendable_supply.safe_flush(exit)?;
         self.height_to_opreturn_supply.safe_flush(exit)?;
+        if let Some(urpd) = self.urpd.as_mut() {
+            urpd.safe_flush(exit)?;
+        }
 
         self.chain_state.truncate_if_needed(Height::ZERO)?;
         chain_state.iter().for_each(|block_state| {
@@ -681,6 +729,7 @@ impl Vecs {
                 .collect::<Vec<_>>(),
             self.indexes_to_unspendable_supply.vecs(),
             self.indexes_to_opreturn_supply.vecs(),
+            self.urpd.as_ref().map_or(vec![], |v| v.vecs()),
             vec![
                 &self.height_to_unspendable_supply,
                 &self.height_to_opreturn_supply,
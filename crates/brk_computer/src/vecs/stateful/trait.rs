@@ -3,7 +3,7 @@ use std::path::Path;
 use brk_core::{Bitcoin, DateIndex, Dollars, Height, Result, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
-use brk_vec::{AnyCollectableVec, AnyIterableVec, Computation, Format};
+use brk_vec::{AnyCollectableVec, AnyIterableVec, Computation, ImportMode};
 
 use crate::vecs::{Indexes, fetched, indexes, market};
 
@@ -13,7 +13,7 @@ pub trait CohortVecs: Sized {
         path: &Path,
         cohort_name: Option<&str>,
         computation: Computation,
-        format: Format,
+        mode: ImportMode,
         version: Version,
         fetched: Option<&fetched::Vecs>,
         states_path: &Path,
@@ -0,0 +1,206 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use brk_core::{DateIndex, Dollars, GroupFilter, GroupedByTerm, Result, Sats, Version};
+use brk_exit::Exit;
+use brk_vec::{AnyCollectableVec, AnyVec, EagerVec, ImportMode};
+use color_eyre::eyre::eyre;
+use log::info;
+use serde::Deserialize;
+
+const OVERRIDE_FILE_NAME: &str = "urpd.json";
+
+/// Price buckets of the URPD, from $0.01 to $10M with 10 buckets per power of 10 unless overridden by a `urpd.json`
+/// in the outputs directory
+///
+/// Bucket indexes are in the names of the vecs, so the version should be bumped on every edit as it's part of their
+/// version.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UrpdBuckets {
+    version: u64,
+    /// Inclusive lower bound of each bucket, the first one must be 0
+    bounds: Vec<Dollars>,
+}
+
+impl UrpdBuckets {
+    pub fn import(outputs_dir: &Path) -> color_eyre::Result<Self> {
+        let path = outputs_dir.join(OVERRIDE_FILE_NAME);
+
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        info!("Using URPD buckets from {}", path.display());
+
+        let this: Self = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+        if this.bounds.first() != Some(&Dollars::ZERO) {
+            return Err(eyre!("The first URPD bound must be 0"));
+        }
+        if this
+            .bounds
+            .windows(2)
+            .any(|w| !f64::from(w[1]).is_finite() || w[0] >= w[1])
+        {
+            return Err(eyre!("URPD bounds must be finite and strictly increasing"));
+        }
+
+        Ok(this)
+    }
+
+    pub fn version(&self) -> Version {
+        Version::new(self.version)
+    }
+
+    pub fn bounds(&self) -> &[Dollars] {
+        &self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    fn bucket(&self, price: Dollars) -> usize {
+        self.bounds
+            .partition_point(|bound| *bound <= price)
+            .saturating_sub(1)
+    }
+}
+
+impl Default for UrpdBuckets {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            bounds: [Dollars::ZERO]
+                .into_iter()
+                .chain((-20..=70).map(|i| Dollars::from(10_f64.powf(i as f64 / 10.0))))
+                .collect(),
+        }
+    }
+}
+
+/// Daily supply of each price bucket, by the price at which it last moved
+#[derive(Clone)]
+pub struct Vecs {
+    buckets: UrpdBuckets,
+    pub all: Vec<EagerVec<DateIndex, Sats>>,
+    pub by_term: GroupedByTerm<(GroupFilter, Vec<EagerVec<DateIndex, Sats>>)>,
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        mode: ImportMode,
+        buckets: UrpdBuckets,
+    ) -> color_eyre::Result<Self> {
+        let import = |prefix: &str| {
+            (0..buckets.len())
+                .map(|i| {
+                    EagerVec::forced_import(
+                        path,
+                        &format!("{prefix}urpd_bucket_{i}"),
+                        version + buckets.version() + Version::ZERO,
+                        mode,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(Self {
+            all: import("")?,
+            by_term: GroupedByTerm::from(GroupedByTerm {
+                short: import("sth_")?,
+                long: import("lth_")?,
+            }),
+            buckets,
+        })
+    }
+
+    pub fn buckets(&self) -> &UrpdBuckets {
+        &self.buckets
+    }
+
+    pub fn validate_computed_versions(&mut self, base_version: Version) -> Result<()> {
+        self.as_mut_vecs().try_for_each(|vec| {
+            vec.validate_computed_version_or_reset_file(base_version + vec.inner_version())
+        })
+    }
+
+    pub fn starting_dateindex(&self) -> DateIndex {
+        DateIndex::from(
+            self.all
+                .iter()
+                .chain(self.by_term.short.1.iter())
+                .chain(self.by_term.long.1.iter())
+                .map(|vec| vec.len())
+                .min()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Takes the price to amount maps of the date range cohorts, which all together hold the whole supply
+    pub fn compute_then_force_push<'a>(
+        &mut self,
+        dateindex: DateIndex,
+        by_date_range: impl Iterator<Item = (&'a GroupFilter, &'a BTreeMap<Dollars, Sats>)>,
+        exit: &Exit,
+    ) -> Result<()> {
+        let mut all = vec![Sats::ZERO; self.buckets.len()];
+        let mut short = all.clone();
+        let mut long = all.clone();
+
+        by_date_range.for_each(|(filter, price_to_amount)| {
+            let mut term = if self.by_term.short.0.includes(filter) {
+                Some(&mut short)
+            } else if self.by_term.long.0.includes(filter) {
+                Some(&mut long)
+            } else {
+                None
+            };
+
+            price_to_amount.iter().for_each(|(price, amount)| {
+                let i = self.buckets.bucket(*price);
+                all[i] += *amount;
+                if let Some(term) = term.as_mut() {
+                    term[i] += *amount;
+                }
+            });
+        });
+
+        [
+            (&mut self.all, all),
+            (&mut self.by_term.short.1, short),
+            (&mut self.by_term.long.1, long),
+        ]
+        .into_iter()
+        .try_for_each(|(vecs, amounts)| {
+            vecs.iter_mut()
+                .zip(amounts)
+                .try_for_each(|(vec, amount)| vec.forced_push_at(dateindex, amount, exit))
+        })
+    }
+
+    pub fn safe_flush(&mut self, exit: &Exit) -> Result<()> {
+        self.as_mut_vecs().try_for_each(|vec| vec.safe_flush(exit))
+    }
+
+    fn as_mut_vecs(&mut self) -> impl Iterator<Item = &mut EagerVec<DateIndex, Sats>> {
+        self.all
+            .iter_mut()
+            .chain(self.by_term.short.1.iter_mut())
+            .chain(self.by_term.long.1.iter_mut())
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        self.all
+            .iter()
+            .chain(self.by_term.short.1.iter())
+            .chain(self.by_term.long.1.iter())
+            .map(|vec| vec as &dyn AnyCollectableVec)
+            .collect::<Vec<_>>()
+    }
+}
@@ -4,7 +4,7 @@ use brk_core::{Bitcoin, DateIndex, Dollars, Height, Result, Version};
 use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_state::{CohortStateTrait, UTXOCohortState};
-use brk_vec::{AnyCollectableVec, AnyIterableVec, Computation, Format};
+use brk_vec::{AnyCollectableVec, AnyIterableVec, Computation, ImportMode};
 
 use crate::vecs::{
     Indexes, fetched, indexes, market,
@@ -26,7 +26,7 @@ impl CohortVecs for Vecs {
         path: &Path,
         cohort_name: Option<&str>,
         computation: Computation,
-        format: Format,
+        mode: ImportMode,
         version: Version,
         fetched: Option<&fetched::Vecs>,
         states_path: &Path,
@@ -47,7 +47,7 @@ impl CohortVecs for Vecs {
                 path,
                 cohort_name,
                 computation,
-                format,
+                mode,
                 version,
                 fetched,
                 compute_relative_to_all,
@@ -8,7 +8,7 @@ use brk_core::{
 };
 use brk_exit::Exit;
 use brk_state::{BlockState, CohortStateTrait, Transacted};
-use brk_vec::{Computation, Format, StoredIndex};
+use brk_vec::{Computation, ImportMode, StoredIndex};
 use derive_deref::{Deref, DerefMut};
 use rayon::prelude::*;
 
@@ -27,7 +27,7 @@ impl Vecs {
         path: &Path,
         version: Version,
         _computation: Computation,
-        format: Format,
+        mode: ImportMode,
         fetched: Option<&fetched::Vecs>,
         states_path: &Path,
     ) -> color_eyre::Result<Self> {
@@ -37,7 +37,7 @@ impl Vecs {
                     path,
                     None,
                     _computation,
-                    format,
+                    mode,
                     version + VERSION + Version::ZERO,
                     fetched,
                     states_path,
@@ -48,7 +48,7 @@ impl Vecs {
                         path,
                         Some("sth"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -58,7 +58,7 @@ impl Vecs {
                         path,
                         Some("lth"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -70,7 +70,7 @@ impl Vecs {
                         path,
                         Some("up_to_1d"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -80,7 +80,7 @@ impl Vecs {
                         path,
                         Some("up_to_1w"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -90,7 +90,7 @@ impl Vecs {
                         path,
                         Some("up_to_1m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -100,7 +100,7 @@ impl Vecs {
                         path,
                         Some("up_to_2m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -110,7 +110,7 @@ impl Vecs {
                         path,
                         Some("up_to_3m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -120,7 +120,7 @@ impl Vecs {
                         path,
                         Some("up_to_4m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -130,7 +130,7 @@ impl Vecs {
                         path,
                         Some("up_to_5m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -140,7 +140,7 @@ impl Vecs {
                         path,
                         Some("up_to_6m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -150,7 +150,7 @@ impl Vecs {
                         path,
                         Some("up_to_1y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -160,7 +160,7 @@ impl Vecs {
                         path,
                         Some("up_to_2y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -170,7 +170,7 @@ impl Vecs {
                         path,
                         Some("up_to_3y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -180,7 +180,7 @@ impl Vecs {
                         path,
                         Some("up_to_4y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -190,7 +190,7 @@ impl Vecs {
                         path,
                         Some("up_to_5y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -200,7 +200,7 @@ impl Vecs {
                         path,
                         Some("up_to_6y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -210,7 +210,7 @@ impl Vecs {
                         path,
                         Some("up_to_7y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -220,7 +220,7 @@ impl Vecs {
                         path,
                         Some("up_to_8y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -230,7 +230,7 @@ impl Vecs {
                         path,
                         Some("up_to_10y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -240,7 +240,7 @@ impl Vecs {
                         path,
                         Some("up_to_15y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -252,7 +252,7 @@ impl Vecs {
                         path,
                         Some("from_1d"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -262,7 +262,7 @@ impl Vecs {
                         path,
                         Some("from_1w"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -272,7 +272,7 @@ impl Vecs {
                         path,
                         Some("from_1m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -282,7 +282,7 @@ impl Vecs {
                         path,
                         Some("from_2m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -292,7 +292,7 @@ impl Vecs {
                         path,
                         Some("from_3m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -302,7 +302,7 @@ impl Vecs {
                         path,
                         Some("from_4m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -312,7 +312,7 @@ impl Vecs {
                         path,
                         Some("from_5m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -322,7 +322,7 @@ impl Vecs {
                         path,
                         Some("from_6m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -332,7 +332,7 @@ impl Vecs {
                         path,
                         Some("from_1y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -342,7 +342,7 @@ impl Vecs {
                         path,
                         Some("from_2y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -352,7 +352,7 @@ impl Vecs {
                         path,
                         Some("from_3y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -362,7 +362,7 @@ impl Vecs {
                         path,
                         Some("from_4y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -372,7 +372,7 @@ impl Vecs {
                         path,
                         Some("from_5y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -382,7 +382,7 @@ impl Vecs {
                         path,
                         Some("from_6y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -392,7 +392,7 @@ impl Vecs {
                         path,
                         Some("from_7y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -402,7 +402,7 @@ impl Vecs {
                         path,
                         Some("from_8y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -412,7 +412,7 @@ impl Vecs {
                         path,
                         Some("from_10y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -422,7 +422,7 @@ impl Vecs {
                         path,
                         Some("from_15y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -434,7 +434,7 @@ impl Vecs {
                         path,
                         Some("start_to_1d"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -444,7 +444,7 @@ impl Vecs {
                         path,
                         Some("from_1d_to_1w"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -454,7 +454,7 @@ impl Vecs {
                         path,
                         Some("from_1w_to_1m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -464,7 +464,7 @@ impl Vecs {
                         path,
                         Some("from_1m_to_2m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -474,7 +474,7 @@ impl Vecs {
                         path,
                         Some("from_2m_to_3m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -484,7 +484,7 @@ impl Vecs {
                         path,
                         Some("from_3m_to_4m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -494,7 +494,7 @@ impl Vecs {
                         path,
                         Some("from_4m_to_5m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -504,7 +504,7 @@ impl Vecs {
                         path,
                         Some("from_5m_to_6m"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -514,7 +514,7 @@ impl Vecs {
                         path,
                         Some("from_6m_to_1y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -524,7 +524,7 @@ impl Vecs {
                         path,
                         Some("from_1y_to_2y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -534,7 +534,7 @@ impl Vecs {
                         path,
                         Some("from_2y_to_3y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -544,7 +544,7 @@ impl Vecs {
                         path,
                         Some("from_3y_to_4y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -554,7 +554,7 @@ impl Vecs {
                         path,
                         Some("from_4y_to_5y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -564,7 +564,7 @@ impl Vecs {
                         path,
                         Some("from_5y_to_6y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -574,7 +574,7 @@ impl Vecs {
                         path,
                         Some("from_6y_to_7y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -584,7 +584,7 @@ impl Vecs {
                         path,
                         Some("from_7y_to_8y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -594,7 +594,7 @@ impl Vecs {
                         path,
                         Some("from_8y_to_10y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -604,7 +604,7 @@ impl Vecs {
                         path,
                         Some("from_10y_to_15y"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -614,7 +614,7 @@ impl Vecs {
                         path,
                         Some("from_15y_to_end"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -626,7 +626,7 @@ impl Vecs {
                         path,
                         Some("epoch_0"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -636,7 +636,7 @@ impl Vecs {
                         path,
                         Some("epoch_1"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -646,7 +646,7 @@ impl Vecs {
                         path,
                         Some("epoch_2"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -656,7 +656,7 @@ impl Vecs {
                         path,
                         Some("epoch_3"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -666,7 +666,7 @@ impl Vecs {
                         path,
                         Some("epoch_4"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -678,7 +678,7 @@ impl Vecs {
                         path,
                         Some("0sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -688,7 +688,7 @@ impl Vecs {
                         path,
                         Some("from_1sat_to_10sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -698,7 +698,7 @@ impl Vecs {
                         path,
                         Some("from_10sats_to_100sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -708,7 +708,7 @@ impl Vecs {
                         path,
                         Some("from_100sats_to_1_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -718,7 +718,7 @@ impl Vecs {
                         path,
                         Some("from_1_000sats_to_10_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -728,7 +728,7 @@ impl Vecs {
                         path,
                         Some("from_10_000sats_to_100_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -738,7 +738,7 @@ impl Vecs {
                         path,
                         Some("from_100_000sats_to_1_000_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -748,7 +748,7 @@ impl Vecs {
                         path,
                         Some("from_1_000_000sats_to_10_000_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -758,7 +758,7 @@ impl Vecs {
                         path,
                         Some("from_10_000_000sats_to_1btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -768,7 +768,7 @@ impl Vecs {
                         path,
                         Some("from_1btc_to_10btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -778,7 +778,7 @@ impl Vecs {
                         path,
                         Some("from_10btc_to_100btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -788,7 +788,7 @@ impl Vecs {
                         path,
                         Some("from_100btc_to_1_000btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -798,7 +798,7 @@ impl Vecs {
                         path,
                         Some("from_1_000btc_to_10_000btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -808,7 +808,7 @@ impl Vecs {
                         path,
                         Some("from_10_000btc_to_100_000btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -818,7 +818,7 @@ impl Vecs {
                         path,
                         Some("from_100_000btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -830,7 +830,7 @@ impl Vecs {
                         path,
                         Some("up_to_1_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -840,7 +840,7 @@ impl Vecs {
                         path,
                         Some("up_to_10_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -850,7 +850,7 @@ impl Vecs {
                         path,
                         Some("up_to_1btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -860,7 +860,7 @@ impl Vecs {
                         path,
                         Some("up_to_10btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -870,7 +870,7 @@ impl Vecs {
                         path,
                         Some("up_to_100btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -882,7 +882,7 @@ impl Vecs {
                         path,
                         Some("from_1_000sats"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -892,7 +892,7 @@ impl Vecs {
                         path,
                         Some("from_1btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -902,7 +902,7 @@ impl Vecs {
                         path,
                         Some("from_10btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -912,7 +912,7 @@ impl Vecs {
                         path,
                         Some("from_100btc"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -924,7 +924,7 @@ impl Vecs {
                         path,
                         Some("p2pk65"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -934,7 +934,7 @@ impl Vecs {
                         path,
                         Some("p2pk33"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -944,7 +944,7 @@ impl Vecs {
                         path,
                         Some("p2pkh"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -954,7 +954,7 @@ impl Vecs {
                         path,
                         Some("p2ms"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -964,7 +964,7 @@ impl Vecs {
                         path,
                         Some("p2sh"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -974,7 +974,7 @@ impl Vecs {
                         path,
                         Some("p2wpkh"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -984,7 +984,7 @@ impl Vecs {
                         path,
                         Some("p2wsh"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -994,7 +994,7 @@ impl Vecs {
                         path,
                         Some("p2tr"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -1004,7 +1004,7 @@ impl Vecs {
                         path,
                         Some("p2a"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -1014,7 +1014,7 @@ impl Vecs {
                         path,
                         Some("empty"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
@@ -1024,7 +1024,7 @@ impl Vecs {
                         path,
                         Some("unknown"),
                         _computation,
-                        format,
+                        mode,
                         version + VERSION + Version::ZERO,
                         fetched,
                         states_path,
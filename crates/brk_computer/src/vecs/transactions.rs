@@ -8,7 +8,8 @@ use brk_exit::Exit;
 use brk_indexer::Indexer;
 use brk_vec::{
     AnyCollectableVec, AnyIterableVec, CloneableAnyIterableVec, Computation, ComputedVec,
-    ComputedVecFrom1, ComputedVecFrom2, ComputedVecFrom3, Format, StoredIndex, VecIterator,
+    ComputedVecFrom1, ComputedVecFrom2, ComputedVecFrom3, ImportMode, SparseVec, StoredIndex,
+    VecIterator,
 };
 
 use super::{
@@ -55,6 +56,8 @@ pub struct Vecs {
         Sats,
     >,
     // pub indexes_to_output_value: ComputedVecsFromTxindex<Sats>,
+    /// Zero before the first P2A output in 2024, hence sparse
+    pub height_to_p2a_count: SparseVec<Height, StoredUsize>,
     pub indexes_to_p2a_count: ComputedVecsFromHeight<StoredUsize>,
     pub indexes_to_p2ms_count: ComputedVecsFromHeight<StoredUsize>,
     pub indexes_to_p2pk33_count: ComputedVecsFromHeight<StoredUsize>,
@@ -93,7 +96,7 @@ impl Vecs {
         indexer: &Indexer,
         indexes: &indexes::Vecs,
         computation: Computation,
-        format: Format,
+        mode: ImportMode,
         fetched: Option<&fetched::Vecs>,
     ) -> color_eyre::Result<Self> {
         let compute_dollars = fetched.is_some();
@@ -103,7 +106,7 @@ impl Vecs {
             path,
             "value",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.inputindex_to_outputindex.boxed_clone(),
             indexer.vecs.outputindex_to_value.boxed_clone(),
             |index: InputIndex, inputindex_to_outputindex_iter, outputindex_to_value_iter| {
@@ -130,7 +133,7 @@ impl Vecs {
             path,
             "weight",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.txindex_to_base_size.boxed_clone(),
             indexer.vecs.txindex_to_total_size.boxed_clone(),
             |index: TxIndex, txindex_to_base_size_iter, txindex_to_total_size_iter| {
@@ -158,7 +161,7 @@ impl Vecs {
             path,
             "vsize",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             txindex_to_weight.boxed_clone(),
             |index: TxIndex, iter| {
                 let index = index.unwrap_to_usize();
@@ -175,7 +178,7 @@ impl Vecs {
             path,
             "is_coinbase",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexes.txindex_to_height.boxed_clone(),
             indexer.vecs.height_to_first_txindex.boxed_clone(),
             |index: TxIndex, txindex_to_height_iter, height_to_first_txindex_iter| {
@@ -199,7 +202,7 @@ impl Vecs {
             path,
             "input_value",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.txindex_to_first_inputindex.boxed_clone(),
             indexes.txindex_to_input_count.boxed_clone(),
             inputindex_to_value.boxed_clone(),
@@ -236,7 +239,7 @@ impl Vecs {
         //         "input_value",
         //         true,
         //         version + VERSION + Version::ZERO,
-        //         format,
+        //         mode,
         //         StorableVecGeneatorOptions::default()
         //             .add_average()
         //             .add_sum()
@@ -248,7 +251,7 @@ impl Vecs {
             path,
             "output_value",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             indexer.vecs.txindex_to_first_outputindex.boxed_clone(),
             indexes.txindex_to_output_count.boxed_clone(),
             indexer.vecs.outputindex_to_value.boxed_clone(),
@@ -285,7 +288,7 @@ impl Vecs {
         //         "output_value",
         //         true,
         //         version + VERSION + Version::ZERO,
-        //         format,
+        //         mode,
         //         StorableVecGeneatorOptions::default()
         //             .add_average()
         //             .add_sum()
@@ -297,7 +300,7 @@ impl Vecs {
             path,
             "fee",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             txindex_to_input_value.boxed_clone(),
             txindex_to_output_value.boxed_clone(),
             |txindex: TxIndex, input_iter, output_iter| {
@@ -320,7 +323,7 @@ impl Vecs {
             path,
             "feerate",
             version + VERSION + Version::ZERO,
-            format,
+            mode,
             txindex_to_fee.boxed_clone(),
             txindex_to_vsize.boxed_clone(),
             |txindex: TxIndex, fee_iter, vsize_iter| {
@@ -341,7 +344,7 @@ impl Vecs {
                 "tx_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -354,7 +357,7 @@ impl Vecs {
                 "input_count",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -367,7 +370,7 @@ impl Vecs {
                 "output_count",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -380,7 +383,7 @@ impl Vecs {
                 "tx_v1",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -390,7 +393,7 @@ impl Vecs {
                 "tx_v2",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -400,7 +403,7 @@ impl Vecs {
                 "tx_v3",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
@@ -412,7 +415,7 @@ impl Vecs {
                 Some(txindex_to_fee.boxed_clone()),
                 version + VERSION + Version::ZERO,
                 computation,
-                format,
+                mode,
                 fetched,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
@@ -426,7 +429,7 @@ impl Vecs {
                 "feerate",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_percentiles()
                     .add_minmax()
@@ -437,7 +440,7 @@ impl Vecs {
                 "tx_vsize",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_percentiles()
                     .add_minmax()
@@ -448,7 +451,7 @@ impl Vecs {
                 "tx_weight",
                 false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_percentiles()
                     .add_minmax()
@@ -459,7 +462,7 @@ impl Vecs {
                 "subsidy",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_percentiles()
                     .add_sum()
@@ -473,7 +476,7 @@ impl Vecs {
                 "coinbase",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative()
@@ -487,18 +490,25 @@ impl Vecs {
                 "unclaimed_rewards",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_sum()
                     .add_cumulative(),
                 compute_dollars,
             )?,
+            height_to_p2a_count: SparseVec::forced_import(
+                path,
+                "p2a_count",
+                version + VERSION + Version::ZERO,
+                StoredUsize::default(),
+                mode,
+            )?,
             indexes_to_p2a_count: ComputedVecsFromHeight::forced_import(
                 path,
                 "p2a_count",
-                true,
+                false,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -511,7 +521,7 @@ impl Vecs {
                 "p2ms_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -524,7 +534,7 @@ impl Vecs {
                 "p2pk33_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -537,7 +547,7 @@ impl Vecs {
                 "p2pk65_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -550,7 +560,7 @@ impl Vecs {
                 "p2pkh_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -563,7 +573,7 @@ impl Vecs {
                 "p2sh_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -576,7 +586,7 @@ impl Vecs {
                 "p2tr_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -589,7 +599,7 @@ impl Vecs {
                 "p2wpkh_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -602,7 +612,7 @@ impl Vecs {
                 "p2wsh_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -615,7 +625,7 @@ impl Vecs {
                 "opreturn_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -628,7 +638,7 @@ impl Vecs {
                 "unknownoutput_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -641,7 +651,7 @@ impl Vecs {
                 "emptyoutput_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default()
                     .add_average()
                     .add_minmax()
@@ -654,7 +664,7 @@ impl Vecs {
                 "exact_utxo_count",
                 true,
                 version + VERSION + Version::ZERO,
-                format,
+                mode,
                 StorableVecGeneatorOptions::default().add_last(),
             )?,
             txindex_to_is_coinbase,
@@ -753,11 +763,16 @@ impl Vecs {
             exit,
         )?;
 
-        self.inputindex_to_value.compute_if_necessary(
-            starting_indexes.inputindex,
-            &indexer.vecs.inputindex_to_outputindex,
-            exit,
-        )?;
+        // Spent outputs are scattered, gathered reads decode each page only once
+        if let ComputedVec::Eager { vec, .. } = &mut self.inputindex_to_value {
+            vec.compute_gather(
+                starting_indexes.inputindex,
+                &indexer.vecs.inputindex_to_outputindex,
+                &indexer.vecs.outputindex_to_value,
+                |outputindex| (outputindex == OutputIndex::COINBASE).then_some(Sats::ZERO),
+                exit,
+            )?;
+        }
 
         self.txindex_to_output_value.compute_if_necessary(
             starting_indexes.txindex,
@@ -923,19 +938,17 @@ impl Vecs {
             },
         )?;
 
-        self.indexes_to_p2a_count.compute_all(
-            indexer,
+        self.height_to_p2a_count.compute_count_from_indexes(
+            starting_indexes.height,
+            &indexer.vecs.height_to_first_p2aaddressindex,
+            &indexer.vecs.p2aaddressindex_to_p2abytes,
+            exit,
+        )?;
+        self.indexes_to_p2a_count.compute_rest(
             indexes,
             starting_indexes,
             exit,
-            |v, indexer, _, starting_indexes, exit| {
-                v.compute_count_from_indexes(
-                    starting_indexes.height,
-                    &indexer.vecs.height_to_first_p2aaddressindex,
-                    &indexer.vecs.p2aaddressindex_to_p2abytes,
-                    exit,
-                )
-            },
+            Some(&self.height_to_p2a_count),
         )?;
 
         self.indexes_to_p2ms_count.compute_all(
@@ -1169,6 +1182,7 @@ impl Vecs {
                 &self.txindex_to_output_value,
                 &self.txindex_to_vsize,
                 &self.txindex_to_weight,
+                &self.height_to_p2a_count,
             ],
             self.indexes_to_coinbase.vecs(),
             self.indexes_to_emptyoutput_count.vecs(),
@@ -0,0 +1,346 @@
+use std::path::Path;
+
+use brk_core::{
+    Bitcoin, DateIndex, Dollars, OutputType, Result, Sats, StoredF64, TypeIndex, Version,
+};
+use brk_exit::Exit;
+use brk_indexer::Indexer;
+use brk_vec::{
+    AnyCollectableVec, AnyIterableVec, AnyVec, Computation, EagerVec, EagerVecGroup, ImportMode,
+    VecIterator,
+};
+
+use super::{
+    Indexes, fetched,
+    grouped::{ComputedValueVecsFromHeight, ComputedVecsFromDateIndex, StorableVecGeneatorOptions},
+    indexes, market, transactions,
+};
+
+const VERSION: Version = Version::ZERO;
+
+/// Transfer volume and the valuations built on it
+#[derive(Clone)]
+pub struct Vecs {
+    /// Value sent by non coinbase transactions, minus their outputs to an address they also spend from
+    pub indexes_to_adjusted_volume: ComputedValueVecsFromHeight,
+    pub dateindex_to_adjusted_volume_in_usd_90d_sma: Option<EagerVec<DateIndex, Dollars>>,
+    pub dateindex_to_adjusted_volume_365d_sma: EagerVec<DateIndex, Bitcoin>,
+    /// Market cap divided by the daily adjusted volume in dollars
+    pub indexes_to_nvt_ratio: Option<ComputedVecsFromDateIndex<StoredF64>>,
+    /// Market cap divided by the 90 day moving average of the daily adjusted volume in dollars
+    pub indexes_to_nvt_signal: Option<ComputedVecsFromDateIndex<StoredF64>>,
+    /// Adjusted volume of the last year divided by the supply
+    pub indexes_to_velocity: ComputedVecsFromDateIndex<StoredF64>,
+}
+
+impl Vecs {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        _computation: Computation,
+        mode: ImportMode,
+        fetched: Option<&fetched::Vecs>,
+    ) -> color_eyre::Result<Self> {
+        let compute_dollars = fetched.is_some();
+
+        Ok(Self {
+            indexes_to_adjusted_volume: ComputedValueVecsFromHeight::forced_import(
+                path,
+                "adjusted_volume",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default()
+                    .add_sum()
+                    .add_cumulative(),
+                compute_dollars,
+            )?,
+            dateindex_to_adjusted_volume_in_usd_90d_sma: compute_dollars.then(|| {
+                EagerVec::forced_import(
+                    path,
+                    "adjusted_volume_in_usd_90d_sma",
+                    version + VERSION + Version::ZERO,
+                    mode,
+                )
+                .unwrap()
+            }),
+            dateindex_to_adjusted_volume_365d_sma: EagerVec::forced_import(
+                path,
+                "adjusted_volume_365d_sma",
+                version + VERSION + Version::ZERO,
+                mode,
+            )?,
+            indexes_to_nvt_ratio: compute_dollars.then(|| {
+                ComputedVecsFromDateIndex::forced_import(
+                    path,
+                    "nvt_ratio",
+                    true,
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    StorableVecGeneatorOptions::default().add_last(),
+                )
+                .unwrap()
+            }),
+            indexes_to_nvt_signal: compute_dollars.then(|| {
+                ComputedVecsFromDateIndex::forced_import(
+                    path,
+                    "nvt_signal",
+                    true,
+                    version + VERSION + Version::ZERO,
+                    mode,
+                    StorableVecGeneatorOptions::default().add_last(),
+                )
+                .unwrap()
+            }),
+            indexes_to_velocity: ComputedVecsFromDateIndex::forced_import(
+                path,
+                "velocity",
+                true,
+                version + VERSION + Version::ZERO,
+                mode,
+                StorableVecGeneatorOptions::default().add_last(),
+            )?,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        transactions: &transactions::Vecs,
+        market: &market::Vecs,
+        starting_indexes: &Indexes,
+        fetched: Option<&fetched::Vecs>,
+        exit: &Exit,
+    ) -> color_eyre::Result<()> {
+        self.compute_adjusted_volume(indexer, indexes, transactions, starting_indexes, exit)?;
+
+        self.indexes_to_adjusted_volume.compute_rest(
+            indexer,
+            indexes,
+            fetched,
+            starting_indexes,
+            exit,
+            None as Option<&EagerVec<_, _>>,
+        )?;
+
+        let adjusted_volume = self
+            .indexes_to_adjusted_volume
+            .bitcoin
+            .dateindex
+            .unwrap_sum();
+        let supply = transactions
+            .indexes_to_subsidy
+            .bitcoin
+            .dateindex
+            .unwrap_cumulative();
+
+        self.dateindex_to_adjusted_volume_365d_sma.compute_sma(
+            starting_indexes.dateindex,
+            adjusted_volume,
+            365,
+            exit,
+        )?;
+
+        let mut sma_iter = self.dateindex_to_adjusted_volume_365d_sma.into_iter();
+        self.indexes_to_velocity.compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    supply,
+                    |(di, supply, ..)| {
+                        let supply = f64::from(supply);
+                        let velocity = if supply == 0.0 {
+                            f64::NAN
+                        } else {
+                            f64::from(sma_iter.unwrap_get_inner(di)) * 365.0 / supply
+                        };
+                        (di, StoredF64::from(velocity))
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        if fetched.is_none() {
+            return Ok(());
+        }
+
+        let adjusted_volume_in_usd = self
+            .indexes_to_adjusted_volume
+            .dollars
+            .as_ref()
+            .unwrap()
+            .dateindex
+            .unwrap_sum();
+        let marketcap = market.indexes_to_marketcap.dateindex.as_ref().unwrap();
+
+        self.dateindex_to_adjusted_volume_in_usd_90d_sma
+            .as_mut()
+            .unwrap()
+            .compute_sma(starting_indexes.dateindex, adjusted_volume_in_usd, 90, exit)?;
+
+        let mut adjusted_volume_in_usd_iter = adjusted_volume_in_usd.into_iter();
+        self.indexes_to_nvt_ratio.as_mut().unwrap().compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    marketcap,
+                    |(di, marketcap, ..)| {
+                        (
+                            di,
+                            nvt(marketcap, adjusted_volume_in_usd_iter.unwrap_get_inner(di)),
+                        )
+                    },
+                    exit,
+                )
+            },
+        )?;
+
+        let mut sma_iter = self
+            .dateindex_to_adjusted_volume_in_usd_90d_sma
+            .as_ref()
+            .unwrap()
+            .into_iter();
+        self.indexes_to_nvt_signal.as_mut().unwrap().compute_all(
+            indexer,
+            indexes,
+            starting_indexes,
+            exit,
+            |vec, _, _, starting_indexes, exit| {
+                vec.compute_transform(
+                    starting_indexes.dateindex,
+                    marketcap,
+                    |(di, marketcap, ..)| (di, nvt(marketcap, sma_iter.unwrap_get_inner(di))),
+                    exit,
+                )
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Outputs going back to an address spent from by the same transaction are considered as change
+    fn compute_adjusted_volume(
+        &mut self,
+        indexer: &Indexer,
+        indexes: &indexes::Vecs,
+        transactions: &transactions::Vecs,
+        starting_indexes: &Indexes,
+        exit: &Exit,
+    ) -> Result<()> {
+        let mut adjusted_volume = self
+            .indexes_to_adjusted_volume
+            .sats
+            .height
+            .as_mut()
+            .unwrap();
+
+        let mut height_to_tx_count_iter = transactions
+            .indexes_to_tx_count
+            .height
+            .as_ref()
+            .unwrap()
+            .iter();
+        let mut txindex_to_first_inputindex_iter = indexer.vecs.txindex_to_first_inputindex.iter();
+        let mut txindex_to_first_outputindex_iter =
+            indexer.vecs.txindex_to_first_outputindex.iter();
+        let mut txindex_to_input_count_iter = indexes.txindex_to_input_count.iter();
+        let mut txindex_to_output_count_iter = indexes.txindex_to_output_count.iter();
+        let mut inputindex_to_outputindex_iter = indexer.vecs.inputindex_to_outputindex.iter();
+        let mut outputindex_to_outputtype_iter = indexer.vecs.outputindex_to_outputtype.iter();
+        let mut outputindex_to_typeindex_iter = indexer.vecs.outputindex_to_typeindex.iter();
+        let mut outputindex_to_value_iter = indexer.vecs.outputindex_to_value.iter();
+
+        let mut spent_addresses: Vec<(OutputType, TypeIndex)> = vec![];
+
+        adjusted_volume.compute_from(
+            starting_indexes.height,
+            &indexer.vecs.height_to_first_txindex,
+            indexer.vecs.inputindex_to_outputindex.version()
+                + indexer.vecs.outputindex_to_outputtype.version()
+                + indexer.vecs.outputindex_to_typeindex.version()
+                + indexer.vecs.outputindex_to_value.version(),
+            |adjusted_volume, height, first_txindex| {
+                let tx_count = *height_to_tx_count_iter.unwrap_get_inner(height);
+
+                let volume = (1..tx_count)
+                    .map(|i| {
+                        let txindex = first_txindex + i;
+
+                        let first_inputindex =
+                            txindex_to_first_inputindex_iter.unwrap_get_inner(txindex);
+                        let input_count = *txindex_to_input_count_iter.unwrap_get_inner(txindex);
+                        spent_addresses.clear();
+                        spent_addresses.extend((0..input_count).map(|vin| {
+                            let outputindex = inputindex_to_outputindex_iter
+                                .unwrap_get_inner(first_inputindex + vin);
+                            (
+                                outputindex_to_outputtype_iter.unwrap_get_inner(outputindex),
+                                outputindex_to_typeindex_iter.unwrap_get_inner(outputindex),
+                            )
+                        }));
+
+                        let first_outputindex =
+                            txindex_to_first_outputindex_iter.unwrap_get_inner(txindex);
+                        let output_count = *txindex_to_output_count_iter.unwrap_get_inner(txindex);
+                        (0..output_count)
+                            .map(|vout| first_outputindex + vout)
+                            .filter(|outputindex| {
+                                let address = (
+                                    outputindex_to_outputtype_iter.unwrap_get_inner(*outputindex),
+                                    outputindex_to_typeindex_iter.unwrap_get_inner(*outputindex),
+                                );
+                                !spent_addresses.contains(&address)
+                            })
+                            .map(|outputindex| {
+                                outputindex_to_value_iter.unwrap_get_inner(outputindex)
+                            })
+                            .sum::<Sats>()
+                    })
+                    .sum::<Sats>();
+
+                adjusted_volume.forced_push_at(height, volume, exit)
+            },
+            exit,
+        )
+    }
+
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        [
+            self.indexes_to_adjusted_volume.vecs(),
+            self.dateindex_to_adjusted_volume_in_usd_90d_sma
+                .as_ref()
+                .map_or(vec![], |v| vec![v as &dyn AnyCollectableVec]),
+            vec![&self.dateindex_to_adjusted_volume_365d_sma as &dyn AnyCollectableVec],
+            self.indexes_to_nvt_ratio
+                .as_ref()
+                .map_or(vec![], |v| v.vecs()),
+            self.indexes_to_nvt_signal
+                .as_ref()
+                .map_or(vec![], |v| v.vecs()),
+            self.indexes_to_velocity.vecs(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    }
+}
+
+fn nvt(marketcap: Dollars, volume: Dollars) -> StoredF64 {
+    let volume = f64::from(volume);
+    StoredF64::from(if volume == 0.0 {
+        f64::NAN
+    } else {
+        f64::from(marketcap) / volume
+    })
+}
@@ -34,6 +34,8 @@ pub enum Error {
     WrongLength,
     WrongAddressType,
     UnindexableDate,
+    ChecksumMismatch { index: usize },
+    ReadOnly,
 
     String(&'static str),
 }
@@ -133,6 +135,10 @@ impl fmt::Display for Error {
                 f,
                 "Date cannot be indexed, must be 2009-01-03, 2009-01-09 or greater"
             ),
+            Error::ChecksumMismatch { index } => {
+                write!(f, "Checksum mismatch, data is corrupted from index {index}")
+            }
+            Error::ReadOnly => write!(f, "Opened in read-only mode, can't be modified"),
 
             Error::String(s) => write!(f, "{s}"),
         }
@@ -61,6 +61,12 @@ impl From<f64> for Bitcoin {
     }
 }
 
+impl From<f32> for Bitcoin {
+    fn from(value: f32) -> Self {
+        Self(value as f64)
+    }
+}
+
 impl From<StoredF64> for Bitcoin {
     fn from(value: StoredF64) -> Self {
         Self(*value)
@@ -73,6 +79,12 @@ impl From<Bitcoin> for f64 {
     }
 }
 
+impl From<Bitcoin> for f32 {
+    fn from(value: Bitcoin) -> Self {
+        value.0 as f32
+    }
+}
+
 impl From<usize> for Bitcoin {
     fn from(value: usize) -> Self {
         Self(value as f64)
@@ -0,0 +1,63 @@
+use std::ops::Add;
+
+use derive_deref::{Deref, DerefMut};
+use serde::Serialize;
+use zerocopy_derive::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+use crate::{CheckedSub, Printable, TypeIndex};
+
+/// Index of the inputs spending what looks like the funding output of a Lightning channel
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    Deref,
+    DerefMut,
+    Default,
+    FromBytes,
+    Immutable,
+    IntoBytes,
+    KnownLayout,
+    Serialize,
+)]
+pub struct ChannelCloseIndex(TypeIndex);
+impl From<TypeIndex> for ChannelCloseIndex {
+    fn from(value: TypeIndex) -> Self {
+        Self(value)
+    }
+}
+impl From<ChannelCloseIndex> for usize {
+    fn from(value: ChannelCloseIndex) -> Self {
+        Self::from(*value)
+    }
+}
+impl From<usize> for ChannelCloseIndex {
+    fn from(value: usize) -> Self {
+        Self(TypeIndex::from(value))
+    }
+}
+impl Add<usize> for ChannelCloseIndex {
+    type Output = Self;
+    fn add(self, rhs: usize) -> Self::Output {
+        Self(*self + rhs)
+    }
+}
+impl CheckedSub<ChannelCloseIndex> for ChannelCloseIndex {
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+}
+
+impl Printable for ChannelCloseIndex {
+    fn to_string() -> &'static str {
+        "channelcloseindex"
+    }
+
+    fn to_possible_strings() -> &'static [&'static str] {
+        &["chclose", "channelclose", "channelcloseindex"]
+    }
+}
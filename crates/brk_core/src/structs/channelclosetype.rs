@@ -0,0 +1,30 @@
+use serde::Serialize;
+use zerocopy_derive::{Immutable, IntoBytes, KnownLayout, TryFromBytes};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TryFromBytes,
+    Immutable,
+    IntoBytes,
+    KnownLayout,
+    Serialize,
+)]
+#[repr(u8)]
+pub enum ChannelCloseType {
+    Cooperative,
+    /// Spent by a commitment transaction, recognizable by the obscured commitment number split between its
+    /// `nSequence` (upper byte `0x80`) and `nLockTime` (upper byte `0x20`)
+    Force,
+}
+
+impl ChannelCloseType {
+    pub fn is_force(&self) -> bool {
+        *self == Self::Force
+    }
+}
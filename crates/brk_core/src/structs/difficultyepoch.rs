@@ -28,6 +28,15 @@ use super::Height;
 )]
 pub struct DifficultyEpoch(u16);
 
+impl DifficultyEpoch {
+    /// Blocks between two difficulty adjustments
+    pub const BLOCK_COUNT: u32 = 2016;
+
+    pub fn first_height(self) -> Height {
+        Height::from(self.0 as u32 * Self::BLOCK_COUNT)
+    }
+}
+
 impl From<u16> for DifficultyEpoch {
     fn from(value: u16) -> Self {
         Self(value)
@@ -71,7 +80,7 @@ impl Div<usize> for DifficultyEpoch {
 
 impl From<Height> for DifficultyEpoch {
     fn from(value: Height) -> Self {
-        Self((u32::from(value) / 2016) as u16)
+        Self((u32::from(value) / Self::BLOCK_COUNT) as u16)
     }
 }
 
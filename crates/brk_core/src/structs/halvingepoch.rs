@@ -29,9 +29,16 @@ use super::Height;
 pub struct HalvingEpoch(u8);
 
 impl HalvingEpoch {
+    /// Blocks between two halvings of the subsidy
+    pub const BLOCK_COUNT: u32 = 210_000;
+
     pub fn new(value: u8) -> Self {
         Self(value)
     }
+
+    pub fn first_height(self) -> Height {
+        Height::from(self.0 as u32 * Self::BLOCK_COUNT)
+    }
 }
 
 impl From<u8> for HalvingEpoch {
@@ -70,7 +77,7 @@ impl Add<usize> for HalvingEpoch {
 
 impl From<Height> for HalvingEpoch {
     fn from(value: Height) -> Self {
-        Self((u32::from(value) / 210_000) as u8)
+        Self((u32::from(value) / Self::BLOCK_COUNT) as u8)
     }
 }
 
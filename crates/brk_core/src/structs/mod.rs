@@ -5,6 +5,8 @@ mod bitcoin;
 mod blockhash;
 mod blockhashprefix;
 mod cents;
+mod channelcloseindex;
+mod channelclosetype;
 mod date;
 mod dateindex;
 mod decadeindex;
@@ -30,6 +32,7 @@ mod p2shaddressindex;
 mod p2traddressindex;
 mod p2wpkhaddressindex;
 mod p2wshaddressindex;
+mod poolid;
 mod quarterindex;
 mod rawlocktime;
 mod sats;
@@ -43,6 +46,7 @@ mod timestamp;
 mod txid;
 mod txidprefix;
 mod txindex;
+mod txpattern;
 mod txversion;
 mod typeindex;
 mod unit;
@@ -61,6 +65,8 @@ pub use bitcoin::*;
 pub use blockhash::*;
 pub use blockhashprefix::*;
 pub use cents::*;
+pub use channelcloseindex::*;
+pub use channelclosetype::*;
 pub use date::*;
 pub use dateindex::*;
 pub use decadeindex::*;
@@ -86,6 +92,7 @@ pub use p2shaddressindex::*;
 pub use p2traddressindex::*;
 pub use p2wpkhaddressindex::*;
 pub use p2wshaddressindex::*;
+pub use poolid::*;
 pub use quarterindex::*;
 pub use rawlocktime::*;
 pub use sats::*;
@@ -99,6 +106,7 @@ pub use timestamp::*;
 pub use txid::*;
 pub use txidprefix::*;
 pub use txindex::*;
+pub use txpattern::*;
 pub use txversion::*;
 pub use typeindex::*;
 pub use unit::*;
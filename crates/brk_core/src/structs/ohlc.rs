@@ -26,6 +26,12 @@ impl From<(Open<Cents>, High<Cents>, Low<Cents>, Close<Cents>)> for OHLCCents {
     }
 }
 
+impl From<OHLCCents> for (Open<Cents>, High<Cents>, Low<Cents>, Close<Cents>) {
+    fn from(value: OHLCCents) -> Self {
+        (value.open, value.high, value.low, value.close)
+    }
+}
+
 impl From<Close<Cents>> for OHLCCents {
     fn from(value: Close<Cents>) -> Self {
         Self {
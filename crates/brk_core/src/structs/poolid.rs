@@ -0,0 +1,48 @@
+use derive_deref::Deref;
+use serde::{Deserialize, Serialize};
+use zerocopy_derive::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+/// Id of a mining pool, as set in the pool definitions, ids are never reused
+#[derive(
+    Debug,
+    Default,
+    Deref,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Immutable,
+    IntoBytes,
+    KnownLayout,
+    FromBytes,
+    Serialize,
+    Deserialize,
+)]
+pub struct PoolId(u16);
+
+impl PoolId {
+    /// Blocks which couldn't be attributed to any pool
+    pub const UNKNOWN: Self = Self(0);
+
+    pub fn new(id: u16) -> Self {
+        Self(id)
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        *self == Self::UNKNOWN
+    }
+}
+
+impl From<u16> for PoolId {
+    fn from(value: u16) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PoolId> for usize {
+    fn from(value: PoolId) -> Self {
+        value.0 as usize
+    }
+}
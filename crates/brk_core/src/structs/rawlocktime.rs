@@ -11,6 +11,12 @@ impl From<LockTime> for RawLockTime {
     }
 }
 
+impl From<RawLockTime> for u32 {
+    fn from(value: RawLockTime) -> Self {
+        value.0
+    }
+}
+
 const CONSENSUS_DELIMITER: u32 = 500_000_000;
 
 impl From<RawLockTime> for LockTime {
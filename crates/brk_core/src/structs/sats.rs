@@ -54,7 +54,7 @@ impl Sats {
     pub const MAX: Self = Self(u64::MAX);
     pub const FIFTY_BTC: Self = Self(50_00_000_000);
 
-    pub fn new(sats: u64) -> Self {
+    pub const fn new(sats: u64) -> Self {
         Self(sats)
     }
 
@@ -7,10 +7,10 @@ use zerocopy_derive::{FromBytes, Immutable, IntoBytes, KnownLayout};
 use crate::{CheckedSub, Printable};
 
 use super::{
-    DateIndex, EmptyOutputIndex, Height, InputIndex, MonthIndex, OpReturnIndex, OutputIndex,
-    P2AAddressIndex, P2MSOutputIndex, P2PK33AddressIndex, P2PK65AddressIndex, P2PKHAddressIndex,
-    P2SHAddressIndex, P2TRAddressIndex, P2WPKHAddressIndex, P2WSHAddressIndex, TxIndex,
-    UnknownOutputIndex, YearIndex,
+    ChannelCloseIndex, DateIndex, EmptyOutputIndex, Height, InputIndex, MonthIndex, OpReturnIndex,
+    OutputIndex, P2AAddressIndex, P2MSOutputIndex, P2PK33AddressIndex, P2PK65AddressIndex,
+    P2PKHAddressIndex, P2SHAddressIndex, P2TRAddressIndex, P2WPKHAddressIndex, P2WSHAddressIndex,
+    TxIndex, UnknownOutputIndex, YearIndex,
 };
 
 #[derive(
@@ -189,6 +189,12 @@ impl From<UnknownOutputIndex> for StoredUsize {
     }
 }
 
+impl From<ChannelCloseIndex> for StoredUsize {
+    fn from(value: ChannelCloseIndex) -> Self {
+        Self::from(usize::from(value))
+    }
+}
+
 impl From<EmptyOutputIndex> for StoredUsize {
     fn from(value: EmptyOutputIndex) -> Self {
         Self::from(usize::from(value))
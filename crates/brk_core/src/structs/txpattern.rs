@@ -0,0 +1,84 @@
+use serde::Serialize;
+use zerocopy_derive::{Immutable, IntoBytes, KnownLayout, TryFromBytes};
+
+/// Heuristic classification of a transaction from the shape of its inputs and outputs
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TryFromBytes,
+    Immutable,
+    IntoBytes,
+    KnownLayout,
+    Serialize,
+)]
+#[repr(u8)]
+pub enum TxPattern {
+    Coinbase,
+    /// One or two outputs, usually a payment and its change
+    Payment,
+    /// Many outputs from a single transaction
+    BatchPayout,
+    /// Many inputs merged into a single output
+    Consolidation,
+    /// Several outputs of the same value, at most as many as there are inputs
+    CoinJoin,
+    /// A single output from one or two inputs, no change to tell the payee from the payer
+    SelfTransfer,
+}
+
+impl TxPattern {
+    pub const CONSOLIDATION_MIN_INPUTS: usize = 3;
+    pub const BATCH_PAYOUT_MIN_OUTPUTS: usize = 3;
+    pub const COINJOIN_MIN_EQUAL_OUTPUTS: usize = 3;
+
+    /// `equal_outputs` is the number of outputs sharing the most common non zero value
+    pub fn classify(
+        is_coinbase: bool,
+        input_count: usize,
+        output_count: usize,
+        equal_outputs: usize,
+    ) -> Self {
+        if is_coinbase {
+            Self::Coinbase
+        } else if equal_outputs >= Self::COINJOIN_MIN_EQUAL_OUTPUTS && input_count >= equal_outputs
+        {
+            Self::CoinJoin
+        } else if output_count == 1 && input_count >= Self::CONSOLIDATION_MIN_INPUTS {
+            Self::Consolidation
+        } else if output_count == 1 {
+            Self::SelfTransfer
+        } else if output_count >= Self::BATCH_PAYOUT_MIN_OUTPUTS {
+            Self::BatchPayout
+        } else {
+            Self::Payment
+        }
+    }
+
+    /// In the order of the discriminants, so `pattern as usize` indexes into it
+    pub fn as_vec() -> Vec<Self> {
+        vec![
+            Self::Coinbase,
+            Self::Payment,
+            Self::BatchPayout,
+            Self::Consolidation,
+            Self::CoinJoin,
+            Self::SelfTransfer,
+        ]
+    }
+
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Self::Coinbase => "coinbase",
+            Self::Payment => "payment",
+            Self::BatchPayout => "batch_payout",
+            Self::Consolidation => "consolidation",
+            Self::CoinJoin => "coinjoin",
+            Self::SelfTransfer => "self_transfer",
+        }
+    }
+}
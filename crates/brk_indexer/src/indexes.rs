@@ -1,9 +1,9 @@
 use bitcoincore_rpc::Client;
 use brk_core::{
-    BlockHash, CheckedSub, EmptyOutputIndex, Height, InputIndex, OpReturnIndex, OutputIndex,
-    OutputType, P2AAddressIndex, P2MSOutputIndex, P2PK33AddressIndex, P2PK65AddressIndex,
-    P2PKHAddressIndex, P2SHAddressIndex, P2TRAddressIndex, P2WPKHAddressIndex, P2WSHAddressIndex,
-    Result, TxIndex, TypeIndex, UnknownOutputIndex,
+    BlockHash, ChannelCloseIndex, CheckedSub, EmptyOutputIndex, Height, InputIndex, OpReturnIndex,
+    OutputIndex, OutputType, P2AAddressIndex, P2MSOutputIndex, P2PK33AddressIndex,
+    P2PK65AddressIndex, P2PKHAddressIndex, P2SHAddressIndex, P2TRAddressIndex, P2WPKHAddressIndex,
+    P2WSHAddressIndex, Result, TxIndex, TypeIndex, UnknownOutputIndex,
 };
 use brk_parser::NUMBER_OF_UNSAFE_BLOCKS;
 use brk_vec::{AnyIndexedVec, AnyIterableVec, AnyVec, IndexedVec, StoredIndex, StoredType};
@@ -13,6 +13,7 @@ use crate::{Stores, Vecs};
 
 #[derive(Debug, Default, Clone)]
 pub struct Indexes {
+    pub channelcloseindex: ChannelCloseIndex,
     pub emptyoutputindex: EmptyOutputIndex,
     pub height: Height,
     pub opreturnindex: OpReturnIndex,
@@ -81,41 +82,21 @@ impl Indexes {
             .push_if_needed(height, self.p2wpkhaddressindex)?;
         vecs.height_to_first_p2wshaddressindex
             .push_if_needed(height, self.p2wshaddressindex)?;
+        vecs.height_to_first_channelcloseindex
+            .push_if_needed(height, self.channelcloseindex)?;
 
         Ok(())
     }
-}
-
-impl TryFrom<(&mut Vecs, &Stores, &Client)> for Indexes {
-    type Error = color_eyre::Report;
-    fn try_from((vecs, stores, rpc): (&mut Vecs, &Stores, &Client)) -> color_eyre::Result<Self> {
-        // Height at which we wanna start: min last saved + 1 or 0
-        let starting_height = vecs.starting_height().min(stores.starting_height());
-
-        let range = u32::from(
-            starting_height
-                .checked_sub(NUMBER_OF_UNSAFE_BLOCKS as u32)
-                .unwrap_or_default(),
-        )..u32::from(starting_height);
-
-        // But we also need to check the chain and start earlier in case of a reorg
-        let height = range // ..= because of last saved + 1
-            .map(Height::from)
-            .find(|height| {
-                let rpc_blockhash = BlockHash::try_from((rpc, *height))
-                    .inspect_err(|e| {
-                        dbg!(e, height);
-                    })
-                    .unwrap();
-
-                vecs.height_to_blockhash
-                    .iter()
-                    .get(*height)
-                    .is_none_or(|saved_blockhash| &rpc_blockhash != saved_blockhash.as_ref())
-            })
-            .unwrap_or(starting_height);
 
+    /// First indexes of the block at `height`, which needs to be at most one past the last indexed block
+    pub fn at_height(vecs: &Vecs, height: Height) -> color_eyre::Result<Self> {
         Ok(Self {
+            channelcloseindex: starting_index(
+                &vecs.height_to_first_channelcloseindex,
+                &vecs.channelcloseindex_to_inputindex,
+                height,
+            )
+            .context("")?,
             emptyoutputindex: starting_index(
                 &vecs.height_to_first_emptyoutputindex,
                 &vecs.emptyoutputindex_to_txindex,
@@ -207,6 +188,39 @@ impl TryFrom<(&mut Vecs, &Stores, &Client)> for Indexes {
     }
 }
 
+impl TryFrom<(&mut Vecs, &Stores, &Client)> for Indexes {
+    type Error = color_eyre::Report;
+    fn try_from((vecs, stores, rpc): (&mut Vecs, &Stores, &Client)) -> color_eyre::Result<Self> {
+        // Height at which we wanna start: min last saved + 1 or 0
+        let starting_height = vecs.starting_height().min(stores.starting_height());
+
+        let range = u32::from(
+            starting_height
+                .checked_sub(NUMBER_OF_UNSAFE_BLOCKS as u32)
+                .unwrap_or_default(),
+        )..u32::from(starting_height);
+
+        // But we also need to check the chain and start earlier in case of a reorg
+        let height = range // ..= because of last saved + 1
+            .map(Height::from)
+            .find(|height| {
+                let rpc_blockhash = BlockHash::try_from((rpc, *height))
+                    .inspect_err(|e| {
+                        dbg!(e, height);
+                    })
+                    .unwrap();
+
+                vecs.height_to_blockhash
+                    .iter()
+                    .get(*height)
+                    .is_none_or(|saved_blockhash| &rpc_blockhash != saved_blockhash.as_ref())
+            })
+            .unwrap_or(starting_height);
+
+        Self::at_height(vecs, height)
+    }
+}
+
 pub fn starting_index<I, T>(
     height_to_index: &IndexedVec<Height, I>,
     index_to_else: &IndexedVec<I, T>,
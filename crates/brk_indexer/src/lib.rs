@@ -6,9 +6,9 @@
 use std::{collections::BTreeMap, path::Path, str::FromStr, thread};
 
 use brk_core::{
-    AddressBytes, AddressBytesHash, BlockHash, BlockHashPrefix, Height, InputIndex, OutputIndex,
-    OutputType, Sats, Timestamp, TxIndex, Txid, TxidPrefix, TypeIndex, Version, Vin, Vout,
-    setrlimit,
+    AddressBytes, AddressBytesHash, BlockHash, BlockHashPrefix, ChannelCloseType, Height,
+    InputIndex, OutputIndex, OutputType, Sats, Timestamp, TxIndex, Txid, TxidPrefix, TypeIndex,
+    Version, Vin, Vout, setrlimit,
 };
 
 use bitcoin::{Transaction, TxIn, TxOut};
@@ -33,18 +33,44 @@ const VERSION: Version = Version::ONE;
 #[derive(Clone)]
 pub struct Indexer {
     pub vecs: Vecs,
-    pub stores: Stores,
+    /// None when imported read-only, keyspaces can't be shared between processes
+    pub stores: Option<Stores>,
 }
 
 impl Indexer {
     pub fn forced_import(outputs_dir: &Path) -> color_eyre::Result<Self> {
         setrlimit()?;
         Ok(Self {
-            vecs: Vecs::forced_import(&outputs_dir.join("vecs/indexed"), VERSION + Version::ZERO)?,
-            stores: Stores::forced_import(&outputs_dir.join("stores"), VERSION + Version::ZERO)?,
+            vecs: Vecs::forced_import(
+                &outputs_dir.join("vecs/indexed"),
+                VERSION + Version::ZERO,
+                false,
+            )?,
+            stores: Some(Stores::forced_import(
+                &outputs_dir.join("stores"),
+                VERSION + Version::ZERO,
+            )?),
         })
     }
 
+    /// Imports the vecs of an indexer running in another process without ever writing to them, call `refresh` to follow its progress
+    pub fn import_read_only(outputs_dir: &Path) -> color_eyre::Result<Self> {
+        setrlimit()?;
+        Ok(Self {
+            vecs: Vecs::forced_import(
+                &outputs_dir.join("vecs/indexed"),
+                VERSION + Version::ZERO,
+                true,
+            )?,
+            stores: None,
+        })
+    }
+
+    /// Picks up the blocks indexed by another process since the last refresh, returns whether there were any
+    pub fn refresh(&self) -> color_eyre::Result<bool> {
+        self.vecs.refresh()
+    }
+
     pub fn index(
         &mut self,
         parser: &Parser,
@@ -52,17 +78,24 @@ impl Indexer {
         exit: &Exit,
         check_collisions: bool,
     ) -> color_eyre::Result<Indexes> {
-        let starting_indexes = Indexes::try_from((&mut self.vecs, &self.stores, rpc))
-            .unwrap_or_else(|_report| Indexes::default());
+        let stores = self
+            .stores
+            .as_mut()
+            .context("Can't index with a read-only indexer")?;
+        let vecs = &mut self.vecs;
 
         exit.block();
-        self.stores
-            .rollback_if_needed(&mut self.vecs, &starting_indexes)?;
-        self.vecs.rollback_if_needed(&starting_indexes)?;
+        // Before the starting indexes as they're based on the lengths of the vecs
+        vecs.rollback_to_last_commit(stores.height())?;
         exit.release();
 
-        let vecs = &mut self.vecs;
-        let stores = &mut self.stores;
+        let starting_indexes = Indexes::try_from((&mut *vecs, &*stores, rpc))
+            .unwrap_or_else(|_report| Indexes::default());
+
+        exit.block();
+        stores.rollback_if_needed(vecs, &starting_indexes)?;
+        vecs.rollback_if_needed(&starting_indexes)?;
+        exit.release();
 
         // Cloned because we want to return starting indexes for the computer
         let mut idxs = starting_indexes.clone();
@@ -91,8 +124,11 @@ impl Indexer {
 
             info!("Exporting...");
             exit.block();
-            stores.commit(height)?;
+            // The generation is only recorded once the vecs are synced and the stores committed, a crash in between is
+            // handled by `Vecs::rollback_to_last_commit` on restart
             vecs.flush(height)?;
+            stores.commit(height)?;
+            vecs.commit(height)?;
             exit.release();
             Ok(())
         };
@@ -125,6 +161,14 @@ impl Indexer {
                     .insert_if_needed(blockhash_prefix, height, height);
 
                 vecs.height_to_blockhash.push_if_needed(height, blockhash)?;
+                vecs.height_to_coinbase_scriptsig.push_if_needed(
+                    height,
+                    block
+                        .txdata
+                        .first()
+                        .and_then(|tx| tx.input.first())
+                        .map_or(&[][..], |txin| txin.script_sig.as_bytes()),
+                )?;
                 vecs.height_to_difficulty
                     .push_if_needed(height, block.header.difficulty_float().into())?;
                 vecs.height_to_timestamp
@@ -605,6 +649,29 @@ impl Indexer {
 
                 drop(new_txindexvout_to_outputindex);
 
+                let mut inputindex_to_outputindex_iter = vecs.inputindex_to_outputindex.into_iter();
+                let mut outputindex_to_outputtype_iter = vecs.outputindex_to_outputtype.into_iter();
+                let taproot_control_block_count = block
+                    .txdata
+                    .iter()
+                    .flat_map(|tx| &tx.input)
+                    .enumerate()
+                    .filter(|(block_inputindex, txin)| {
+                        if !has_taproot_control_block(txin) {
+                            return false;
+                        }
+                        let outputindex = inputindex_to_outputindex_iter
+                            .unwrap_get_inner(idxs.inputindex + InputIndex::from(*block_inputindex));
+                        outputindex != OutputIndex::COINBASE
+                            && outputindex_to_outputtype_iter.unwrap_get_inner(outputindex) == OutputType::P2TR
+                    })
+                    .count();
+                drop(inputindex_to_outputindex_iter);
+                drop(outputindex_to_outputtype_iter);
+
+                vecs.height_to_taproot_control_block_count
+                    .push_if_needed(height, taproot_control_block_count.into())?;
+
                 let mut txindex_to_tx_and_txid: BTreeMap<TxIndex, (&Transaction, Txid)> = BTreeMap::default();
 
                 let mut txindex_to_txid_iter = vecs
@@ -683,6 +750,37 @@ impl Indexer {
                         Ok(())
                     })?;
 
+                let mut inputindex_to_outputindex_iter = vecs.inputindex_to_outputindex.into_iter();
+                let mut outputindex_to_outputtype_iter = vecs.outputindex_to_outputtype.into_iter();
+                let channel_closes = block
+                    .txdata
+                    .iter()
+                    .flat_map(|tx| tx.input.iter().map(move |txin| (tx, txin)))
+                    .enumerate()
+                    .filter_map(|(block_inputindex, (tx, txin))| {
+                        let inputindex = idxs.inputindex + InputIndex::from(block_inputindex);
+                        channel_close_type(tx, txin, || {
+                            let outputindex = inputindex_to_outputindex_iter.unwrap_get_inner(inputindex);
+                            outputindex != OutputIndex::COINBASE
+                                && outputindex_to_outputtype_iter.unwrap_get_inner(outputindex) == OutputType::P2TR
+                        })
+                        .map(|closetype| (inputindex, closetype))
+                    })
+                    .collect::<Vec<_>>();
+                drop(inputindex_to_outputindex_iter);
+                drop(outputindex_to_outputtype_iter);
+
+                channel_closes.into_iter().try_for_each(
+                    |(inputindex, closetype)| -> color_eyre::Result<()> {
+                        vecs.channelcloseindex_to_inputindex
+                            .push_if_needed(idxs.channelcloseindex, inputindex)?;
+                        vecs.channelcloseindex_to_channelclosetype
+                            .push_if_needed(idxs.channelcloseindex, closetype)?;
+                        idxs.channelcloseindex.increment();
+                        Ok(())
+                    },
+                )?;
+
                 idxs.txindex += TxIndex::from(tx_len);
                 idxs.inputindex += InputIndex::from(inputs_len);
                 idxs.outputindex += OutputIndex::from(outputs_len);
@@ -706,3 +804,82 @@ enum InputSource<'a> {
     PreviousBlock((Vin, TxIndex, OutputIndex)),
     SameBlock((&'a Transaction, TxIndex, &'a TxIn, Vin)),
 }
+
+/// Whether the last element of the witness, once the annex is removed, is shaped like the control block of a Taproot script path spend
+///
+/// Doesn't know the type of the spent output, so a P2WSH witness script could be shaped the same way and the caller
+/// needs to check that a P2TR output is spent.
+fn has_taproot_control_block(txin: &TxIn) -> bool {
+    let witness = txin.witness.iter().collect::<Vec<_>>();
+
+    let witness = match witness.split_last() {
+        Some((last, rest)) if !rest.is_empty() && last.first() == Some(&0x50) => rest,
+        _ => &witness[..],
+    };
+
+    if witness.len() < 2 {
+        return false;
+    }
+
+    witness.last().is_some_and(|control_block| {
+        control_block.len() >= 33
+            && (control_block.len() - 33) % 32 == 0
+            && (control_block.len() - 33) / 32 <= 128
+            && control_block[0] & 0xfe == 0xc0
+    })
+}
+
+/// Whether the witness, once the annex is removed, is a single Schnorr signature, which is how a Taproot key path spend looks
+fn has_taproot_key_path_signature(txin: &TxIn) -> bool {
+    let witness = txin.witness.iter().collect::<Vec<_>>();
+
+    let witness = match witness.split_last() {
+        Some((last, rest)) if !rest.is_empty() && last.first() == Some(&0x50) => rest,
+        _ => &witness[..],
+    };
+
+    matches!(witness, [signature] if signature.len() == 64 || signature.len() == 65)
+}
+
+/// How the channel was closed if the input spends a P2WSH 2-of-2 multisig, which is what Lightning funding outputs are,
+/// or if it's the commitment transaction of a Taproot channel
+///
+/// Any other 2-of-2 P2WSH multisig is counted too. Taproot channels are spent through the key path with a MuSig2
+/// signature so only their force closes can be told apart, by the markers of the commitment transaction, `spends_p2tr`
+/// being only called for those.
+fn channel_close_type(
+    tx: &Transaction,
+    txin: &TxIn,
+    spends_p2tr: impl FnOnce() -> bool,
+) -> Option<ChannelCloseType> {
+    let witness = &txin.witness;
+
+    let is_commitment = tx.input.len() == 1
+        && txin.sequence.0 >> 24 == 0x80
+        && tx.lock_time.to_consensus_u32() >> 24 == 0x20;
+
+    // OP_0 dummy, 2 signatures, witness script
+    if witness.len() != 4 || !witness.nth(0).is_some_and(|dummy| dummy.is_empty()) {
+        return (is_commitment && has_taproot_key_path_signature(txin) && spends_p2tr())
+            .then_some(ChannelCloseType::Force);
+    }
+
+    // OP_2 <33 bytes pubkey> <33 bytes pubkey> OP_2 OP_CHECKMULTISIG
+    let is_2_of_2 = witness.last().is_some_and(|script| {
+        script.len() == 71
+            && script[0] == 0x52
+            && script[1] == 0x21
+            && script[35] == 0x21
+            && script[69] == 0x52
+            && script[70] == 0xae
+    });
+    if !is_2_of_2 {
+        return None;
+    }
+
+    Some(if is_commitment {
+        ChannelCloseType::Force
+    } else {
+        ChannelCloseType::Cooperative
+    })
+}
@@ -4,20 +4,23 @@ use brk_core::{
     AddressBytes, AddressBytesHash, BlockHashPrefix, Height, OutputType, Result, TxIndex,
     TxidPrefix, TypeIndex, Value, Version,
 };
-use brk_store::Store;
+use brk_store::{AnyStore, FjallBackend, KvBackend, Store, StoreStats};
 use brk_vec::AnyIterableVec;
-use fjall::{PersistMode, TransactionalKeyspace};
+use log::warn;
 
 use crate::Indexes;
 
 use super::Vecs;
 
 #[derive(Clone)]
-pub struct Stores {
-    pub keyspace: TransactionalKeyspace,
-    pub addressbyteshash_to_typeindex: Store<AddressBytesHash, TypeIndex>,
-    pub blockhashprefix_to_height: Store<BlockHashPrefix, Height>,
-    pub txidprefix_to_txindex: Store<TxidPrefix, TxIndex>,
+pub struct Stores<B = FjallBackend>
+where
+    B: KvBackend,
+{
+    pub backend: B,
+    pub addressbyteshash_to_typeindex: Store<AddressBytesHash, TypeIndex, B>,
+    pub blockhashprefix_to_height: Store<BlockHashPrefix, Height, B>,
+    pub txidprefix_to_txindex: Store<TxidPrefix, TxIndex, B>,
 }
 
 const VERSION: Version = Version::ZERO;
@@ -26,18 +29,31 @@ impl Stores {
     pub fn forced_import(path: &Path, version: Version) -> color_eyre::Result<Self> {
         fs::create_dir_all(path)?;
 
-        let keyspace = match brk_store::open_keyspace(path) {
-            Ok(keyspace) => keyspace,
-            Err(_) => {
+        let backend = match FjallBackend::open(path) {
+            Ok(backend) => backend,
+            Err(e) if FjallBackend::is_incompatible(&e) => {
+                warn!("Stores were written in an incompatible format, resetting them...");
                 fs::remove_dir_all(path)?;
-                return Self::forced_import(path, version);
+                fs::create_dir_all(path)?;
+                FjallBackend::open(path)?
             }
+            Err(e) => return Err(e.into()),
         };
 
+        Self::import(&backend, path, version)
+    }
+}
+
+impl<B> Stores<B>
+where
+    B: KvBackend,
+{
+    /// With a backend which isn't persistent, such as [`brk_store::MemoryBackend`], nothing is written to `path`
+    pub fn import(backend: &B, path: &Path, version: Version) -> color_eyre::Result<Self> {
         thread::scope(|scope| {
             let addressbyteshash_to_typeindex = scope.spawn(|| {
                 Store::import(
-                    &keyspace,
+                    backend,
                     path,
                     "addressbyteshash_to_typeindex",
                     version + VERSION + Version::ZERO,
@@ -46,7 +62,7 @@ impl Stores {
             });
             let blockhashprefix_to_height = scope.spawn(|| {
                 Store::import(
-                    &keyspace,
+                    backend,
                     path,
                     "blockhashprefix_to_height",
                     version + VERSION + Version::ZERO,
@@ -55,7 +71,7 @@ impl Stores {
             });
             let txidprefix_to_txindex = scope.spawn(|| {
                 Store::import(
-                    &keyspace,
+                    backend,
                     path,
                     "txidprefix_to_txindex",
                     version + VERSION + Version::ZERO,
@@ -63,15 +79,61 @@ impl Stores {
                 )
             });
 
-            Ok(Self {
-                keyspace: keyspace.clone(),
+            let this = Self {
+                backend: backend.clone(),
                 addressbyteshash_to_typeindex: addressbyteshash_to_typeindex.join().unwrap()?,
                 blockhashprefix_to_height: blockhashprefix_to_height.join().unwrap()?,
                 txidprefix_to_txindex: txidprefix_to_txindex.join().unwrap()?,
-            })
+            };
+
+            this.warn_if_heights_differ();
+
+            Ok(this)
         })
     }
 
+    /// Stores committed separately by older versions could be left at different heights by a crash,
+    /// they're reconciled by the rollback to the lowest one
+    fn warn_if_heights_differ(&self) {
+        let stores = self.as_any_stores();
+
+        if stores
+            .iter()
+            .any(|store| store.height() != stores[0].height())
+        {
+            warn!(
+                "Stores are at different heights, rolling back to the lowest: {}",
+                stores
+                    .iter()
+                    .map(|store| format!("{} at {:?}", store.name(), store.height()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    fn as_any_stores(&self) -> [&dyn AnyStore<B>; 3] {
+        [
+            &self.addressbyteshash_to_typeindex,
+            &self.blockhashprefix_to_height,
+            &self.txidprefix_to_txindex,
+        ]
+    }
+
+    pub fn stats(&self) -> Result<Vec<StoreStats>> {
+        self.as_any_stores()
+            .iter()
+            .map(|store| store.stats())
+            .collect()
+    }
+
+    /// Compacts every store one after the other, can take a while
+    pub fn compact(&self) -> Result<()> {
+        self.as_any_stores()
+            .iter()
+            .try_for_each(|store| store.compact())
+    }
+
     pub fn rollback_if_needed(
         &mut self,
         vecs: &mut Vecs,
@@ -87,10 +149,11 @@ impl Stores {
         if starting_indexes.height != Height::ZERO {
             vecs.height_to_blockhash
                 .iter_at(starting_indexes.height)
-                .for_each(|(_, v)| {
+                .try_for_each(|(_, v)| {
                     let blockhashprefix = BlockHashPrefix::from(Value::into_inner(v));
-                    self.blockhashprefix_to_height.remove(blockhashprefix);
-                });
+                    self.blockhashprefix_to_height
+                        .remove_if_committed(blockhashprefix)
+                })?;
 
             if let Some(mut index) = vecs
                 .height_to_first_p2pk65addressindex
@@ -107,7 +170,8 @@ impl Stores {
                 {
                     let bytes = AddressBytes::from(typedbytes);
                     let hash = AddressBytesHash::from((&bytes, OutputType::P2PK65));
-                    self.addressbyteshash_to_typeindex.remove(hash);
+                    self.addressbyteshash_to_typeindex
+                        .remove_if_committed(hash)?;
                     index.increment();
                 }
             }
@@ -127,7 +191,8 @@ impl Stores {
                 {
                     let bytes = AddressBytes::from(typedbytes);
                     let hash = AddressBytesHash::from((&bytes, OutputType::P2PK33));
-                    self.addressbyteshash_to_typeindex.remove(hash);
+                    self.addressbyteshash_to_typeindex
+                        .remove_if_committed(hash)?;
                     index.increment();
                 }
             }
@@ -147,7 +212,8 @@ impl Stores {
                 {
                     let bytes = AddressBytes::from(typedbytes);
                     let hash = AddressBytesHash::from((&bytes, OutputType::P2PKH));
-                    self.addressbyteshash_to_typeindex.remove(hash);
+                    self.addressbyteshash_to_typeindex
+                        .remove_if_committed(hash)?;
                     index.increment();
                 }
             }
@@ -167,7 +233,8 @@ impl Stores {
                 {
                     let bytes = AddressBytes::from(typedbytes);
                     let hash = AddressBytesHash::from((&bytes, OutputType::P2SH));
-                    self.addressbyteshash_to_typeindex.remove(hash);
+                    self.addressbyteshash_to_typeindex
+                        .remove_if_committed(hash)?;
                     index.increment();
                 }
             }
@@ -187,7 +254,8 @@ impl Stores {
                 {
                     let bytes = AddressBytes::from(typedbytes);
                     let hash = AddressBytesHash::from((&bytes, OutputType::P2TR));
-                    self.addressbyteshash_to_typeindex.remove(hash);
+                    self.addressbyteshash_to_typeindex
+                        .remove_if_committed(hash)?;
                     index.increment();
                 }
             }
@@ -207,7 +275,8 @@ impl Stores {
                 {
                     let bytes = AddressBytes::from(typedbytes);
                     let hash = AddressBytesHash::from((&bytes, OutputType::P2WPKH));
-                    self.addressbyteshash_to_typeindex.remove(hash);
+                    self.addressbyteshash_to_typeindex
+                        .remove_if_committed(hash)?;
                     index.increment();
                 }
             }
@@ -227,7 +296,8 @@ impl Stores {
                 {
                     let bytes = AddressBytes::from(typedbytes);
                     let hash = AddressBytesHash::from((&bytes, OutputType::P2WSH));
-                    self.addressbyteshash_to_typeindex.remove(hash);
+                    self.addressbyteshash_to_typeindex
+                        .remove_if_committed(hash)?;
                     index.increment();
                 }
             }
@@ -246,7 +316,8 @@ impl Stores {
                 {
                     let bytes = AddressBytes::from(typedbytes);
                     let hash = AddressBytesHash::from((&bytes, OutputType::P2A));
-                    self.addressbyteshash_to_typeindex.remove(hash);
+                    self.addressbyteshash_to_typeindex
+                        .remove_if_committed(hash)?;
                     index.increment();
                 }
             }
@@ -258,7 +329,7 @@ impl Stores {
         if starting_indexes.txindex != TxIndex::ZERO {
             vecs.txindex_to_txid
                 .iter_at(starting_indexes.txindex)
-                .for_each(|(txindex, txid)| {
+                .try_for_each(|(txindex, txid)| -> Result<()> {
                     let txidprefix = TxidPrefix::from(&txid.into_inner());
 
                     // "d5d27987d2a3dfc724e359870c6644b40e497bdc0589a033220fe15429d88599"
@@ -270,9 +341,11 @@ impl Stores {
                         || txidprefix != TxidPrefix::from([104, 180, 95, 88, 182, 116, 233, 78]);
 
                     if is_not_first_dup && is_not_second_dup {
-                        self.txidprefix_to_txindex.remove(txidprefix);
+                        self.txidprefix_to_txindex.remove_if_committed(txidprefix)?;
                     }
-                });
+
+                    Ok(())
+                })?;
         } else {
             self.txidprefix_to_txindex.reset_partition()?;
         }
@@ -282,6 +355,18 @@ impl Stores {
         Ok(())
     }
 
+    /// Height at which all the stores were last committed, None if any of them never was
+    pub fn height(&self) -> Option<Height> {
+        [
+            self.addressbyteshash_to_typeindex.height(),
+            self.blockhashprefix_to_height.height(),
+            self.txidprefix_to_txindex.height(),
+        ]
+        .into_iter()
+        .min()
+        .flatten()
+    }
+
     pub fn starting_height(&self) -> Height {
         [
             self.addressbyteshash_to_typeindex.height(),
@@ -294,27 +379,19 @@ impl Stores {
         .unwrap()
     }
 
+    /// All stores are committed in a single write, they can't end up at different heights
     pub fn commit(&mut self, height: Height) -> Result<()> {
-        thread::scope(|scope| -> Result<()> {
-            let addressbyteshash_to_typeindex_commit_handle =
-                scope.spawn(|| self.addressbyteshash_to_typeindex.commit(height));
-            let blockhashprefix_to_height_commit_handle =
-                scope.spawn(|| self.blockhashprefix_to_height.commit(height));
-            let txidprefix_to_txindex_commit_handle =
-                scope.spawn(|| self.txidprefix_to_txindex.commit(height));
-
-            addressbyteshash_to_typeindex_commit_handle
-                .join()
-                .unwrap()?;
-            blockhashprefix_to_height_commit_handle.join().unwrap()?;
-            txidprefix_to_txindex_commit_handle.join().unwrap()?;
-
-            Ok(())
-        })?;
-
-        self.keyspace
-            .persist(PersistMode::SyncAll)
-            .map_err(|e| e.into())
+        brk_store::commit_all(
+            &self.backend,
+            &mut [
+                &mut self.addressbyteshash_to_typeindex,
+                &mut self.blockhashprefix_to_height,
+                &mut self.txidprefix_to_txindex,
+            ],
+            height,
+        )?;
+
+        self.backend.persist()
     }
 
     pub fn rotate_memtables(&self) {
@@ -1,14 +1,17 @@
 use std::path::Path;
 
 use brk_core::{
-    AddressBytes, BlockHash, EmptyOutputIndex, Height, InputIndex, OpReturnIndex, OutputIndex,
-    OutputType, P2AAddressIndex, P2ABytes, P2MSOutputIndex, P2PK33AddressIndex, P2PK33Bytes,
-    P2PK65AddressIndex, P2PK65Bytes, P2PKHAddressIndex, P2PKHBytes, P2SHAddressIndex, P2SHBytes,
-    P2TRAddressIndex, P2TRBytes, P2WPKHAddressIndex, P2WPKHBytes, P2WSHAddressIndex, P2WSHBytes,
-    RawLockTime, Result, Sats, StoredF64, StoredU32, StoredUsize, Timestamp, TxIndex, TxVersion,
-    Txid, TypeIndex, UnknownOutputIndex, Version, Weight,
+    AddressBytes, BlockHash, ChannelCloseIndex, ChannelCloseType, EmptyOutputIndex, Height,
+    InputIndex, OpReturnIndex, OutputIndex, OutputType, P2AAddressIndex, P2ABytes, P2MSOutputIndex,
+    P2PK33AddressIndex, P2PK33Bytes, P2PK65AddressIndex, P2PK65Bytes, P2PKHAddressIndex,
+    P2PKHBytes, P2SHAddressIndex, P2SHBytes, P2TRAddressIndex, P2TRBytes, P2WPKHAddressIndex,
+    P2WPKHBytes, P2WSHAddressIndex, P2WSHBytes, RawLockTime, Result, Sats, StoredF64, StoredU32,
+    StoredUsize, Timestamp, TxIndex, TxVersion, Txid, TypeIndex, UnknownOutputIndex, Version,
+    Weight,
+};
+use brk_vec::{
+    AnyCollectableVec, AnyIndexedVec, CommitLog, Encoding, Format, ImportMode, IndexedVec, VarVec,
 };
-use brk_vec::{AnyCollectableVec, AnyIndexedVec, Format, IndexedVec};
 use rayon::prelude::*;
 
 use crate::Indexes;
@@ -17,9 +20,14 @@ const VERSION: Version = Version::ZERO;
 
 #[derive(Clone)]
 pub struct Vecs {
+    pub channelcloseindex_to_channelclosetype: IndexedVec<ChannelCloseIndex, ChannelCloseType>,
+    pub channelcloseindex_to_inputindex: IndexedVec<ChannelCloseIndex, InputIndex>,
     pub emptyoutputindex_to_txindex: IndexedVec<EmptyOutputIndex, TxIndex>,
     pub height_to_blockhash: IndexedVec<Height, BlockHash>,
+    /// Script of the input of the coinbase transaction, where pools put their tags
+    pub height_to_coinbase_scriptsig: VarVec<Height>,
     pub height_to_difficulty: IndexedVec<Height, StoredF64>,
+    pub height_to_first_channelcloseindex: IndexedVec<Height, ChannelCloseIndex>,
     pub height_to_first_emptyoutputindex: IndexedVec<Height, EmptyOutputIndex>,
     pub height_to_first_inputindex: IndexedVec<Height, InputIndex>,
     pub height_to_first_opreturnindex: IndexedVec<Height, OpReturnIndex>,
@@ -35,6 +43,8 @@ pub struct Vecs {
     pub height_to_first_p2wshaddressindex: IndexedVec<Height, P2WSHAddressIndex>,
     pub height_to_first_txindex: IndexedVec<Height, TxIndex>,
     pub height_to_first_unknownoutputindex: IndexedVec<Height, UnknownOutputIndex>,
+    /// Inputs whose witness ends with a Taproot control block, meaning a script path spend
+    pub height_to_taproot_control_block_count: IndexedVec<Height, StoredUsize>,
     /// Doesn't guarantee continuity due to possible reorgs
     pub height_to_timestamp: IndexedVec<Height, Timestamp>,
     pub height_to_total_size: IndexedVec<Height, StoredUsize>,
@@ -63,282 +73,346 @@ pub struct Vecs {
     pub txindex_to_txid: IndexedVec<TxIndex, Txid>,
     pub txindex_to_txversion: IndexedVec<TxIndex, TxVersion>,
     pub unknownoutputindex_to_txindex: IndexedVec<UnknownOutputIndex, TxIndex>,
+    commit_log: CommitLog,
 }
 
 impl Vecs {
-    pub fn forced_import(path: &Path, version: Version) -> color_eyre::Result<Self> {
+    pub fn forced_import(
+        path: &Path,
+        version: Version,
+        read_only: bool,
+    ) -> color_eyre::Result<Self> {
+        let mode = ImportMode {
+            format: Format::Raw,
+            read_only,
+        };
+
         Ok(Self {
+            channelcloseindex_to_channelclosetype: IndexedVec::forced_import(
+                path,
+                "channelclosetype",
+                version + VERSION + Version::ONE,
+                mode,
+            )?,
+            channelcloseindex_to_inputindex: IndexedVec::forced_import(
+                path,
+                "inputindex",
+                version + VERSION + Version::ONE,
+                mode,
+            )?,
             emptyoutputindex_to_txindex: IndexedVec::forced_import(
                 path,
                 "txindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_blockhash: IndexedVec::forced_import(
                 path,
                 "blockhash",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
+            )?,
+            height_to_coinbase_scriptsig: VarVec::forced_import(
+                path,
+                "coinbase_scriptsig",
+                version + VERSION + Version::ZERO,
+                Encoding::Hex,
+                mode,
             )?,
             height_to_difficulty: IndexedVec::forced_import(
                 path,
                 "difficulty",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
+            )?,
+            height_to_first_channelcloseindex: IndexedVec::forced_import(
+                path,
+                "first_channelcloseindex",
+                version + VERSION + Version::ONE,
+                mode,
             )?,
             height_to_first_emptyoutputindex: IndexedVec::forced_import(
                 path,
                 "first_emptyoutputindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_inputindex: IndexedVec::forced_import(
                 path,
                 "first_inputindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_opreturnindex: IndexedVec::forced_import(
                 path,
                 "first_opreturnindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_outputindex: IndexedVec::forced_import(
                 path,
                 "first_outputindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2aaddressindex: IndexedVec::forced_import(
                 path,
                 "first_p2aaddressindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2msoutputindex: IndexedVec::forced_import(
                 path,
                 "first_p2msoutputindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2pk33addressindex: IndexedVec::forced_import(
                 path,
                 "first_p2pk33addressindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2pk65addressindex: IndexedVec::forced_import(
                 path,
                 "first_p2pk65addressindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2pkhaddressindex: IndexedVec::forced_import(
                 path,
                 "first_p2pkhaddressindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2shaddressindex: IndexedVec::forced_import(
                 path,
                 "first_p2shaddressindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2traddressindex: IndexedVec::forced_import(
                 path,
                 "first_p2traddressindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2wpkhaddressindex: IndexedVec::forced_import(
                 path,
                 "first_p2wpkhaddressindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_p2wshaddressindex: IndexedVec::forced_import(
                 path,
                 "first_p2wshaddressindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_txindex: IndexedVec::forced_import(
                 path,
                 "first_txindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_first_unknownoutputindex: IndexedVec::forced_import(
                 path,
                 "first_unknownoutputindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
+            )?,
+            height_to_taproot_control_block_count: IndexedVec::forced_import(
+                path,
+                "taproot_control_block_count",
+                version + VERSION + Version::ZERO,
+                mode,
             )?,
             height_to_timestamp: IndexedVec::forced_import(
                 path,
                 "timestamp",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_total_size: IndexedVec::forced_import(
                 path,
                 "total_size",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             height_to_weight: IndexedVec::forced_import(
                 path,
                 "weight",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             inputindex_to_outputindex: IndexedVec::forced_import(
                 path,
                 "outputindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             opreturnindex_to_txindex: IndexedVec::forced_import(
                 path,
                 "txindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             outputindex_to_outputtype: IndexedVec::forced_import(
                 path,
                 "outputtype",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             outputindex_to_typeindex: IndexedVec::forced_import(
                 path,
                 "typeindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             outputindex_to_value: IndexedVec::forced_import(
                 path,
                 "value",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2aaddressindex_to_p2abytes: IndexedVec::forced_import(
                 path,
                 "p2abytes",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2msoutputindex_to_txindex: IndexedVec::forced_import(
                 path,
                 "txindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2pk33addressindex_to_p2pk33bytes: IndexedVec::forced_import(
                 path,
                 "p2pk33bytes",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2pk65addressindex_to_p2pk65bytes: IndexedVec::forced_import(
                 path,
                 "p2pk65bytes",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2pkhaddressindex_to_p2pkhbytes: IndexedVec::forced_import(
                 path,
                 "p2pkhbytes",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2shaddressindex_to_p2shbytes: IndexedVec::forced_import(
                 path,
                 "p2shbytes",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2traddressindex_to_p2trbytes: IndexedVec::forced_import(
                 path,
                 "p2trbytes",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2wpkhaddressindex_to_p2wpkhbytes: IndexedVec::forced_import(
                 path,
                 "p2wpkhbytes",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             p2wshaddressindex_to_p2wshbytes: IndexedVec::forced_import(
                 path,
                 "p2wshbytes",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             txindex_to_base_size: IndexedVec::forced_import(
                 path,
                 "base_size",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             txindex_to_first_inputindex: IndexedVec::forced_import(
                 path,
                 "first_inputindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             txindex_to_first_outputindex: IndexedVec::forced_import(
                 path,
                 "first_outputindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             txindex_to_is_explicitly_rbf: IndexedVec::forced_import(
                 path,
                 "is_explicitly_rbf",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             txindex_to_rawlocktime: IndexedVec::forced_import(
                 path,
                 "rawlocktime",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             txindex_to_total_size: IndexedVec::forced_import(
                 path,
                 "total_size",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             txindex_to_txid: IndexedVec::forced_import(
                 path,
                 "txid",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             txindex_to_txversion: IndexedVec::forced_import(
                 path,
                 "txversion",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
             unknownoutputindex_to_txindex: IndexedVec::forced_import(
                 path,
                 "txindex",
                 version + VERSION + Version::ZERO,
-                Format::Raw,
+                mode,
             )?,
+            commit_log: CommitLog::import(&path.join("commit"))?,
         })
     }
 
+    /// Truncates the vecs which were flushed after the last commit, which can only happen after a crash
+    ///
+    /// The stores are committed after the vecs are synced and before the commit is written, so if they're ahead of it
+    /// only the write of the commit was lost and it's written again instead.
+    pub fn rollback_to_last_commit(&mut self, stores_height: Option<Height>) -> Result<()> {
+        let Some(commit) = self.commit_log.last().cloned() else {
+            return Ok(());
+        };
+
+        if let Some(stores_height) = stores_height.filter(|height| *height > commit.height) {
+            return self.commit(stores_height);
+        }
+
+        self.mut_vecs()
+            .into_par_iter()
+            .try_for_each(|vec| match commit.len(&*vec) {
+                Some(len) if len < vec.len() => vec.truncate_if_needed_(len, commit.height),
+                _ => Ok(()),
+            })
+    }
+
     pub fn rollback_if_needed(&mut self, starting_indexes: &Indexes) -> Result<()> {
         let saved_height = starting_indexes.height.decremented().unwrap_or_default();
 
         let &Indexes {
+            channelcloseindex,
             emptyoutputindex,
             height,
             inputindex,
@@ -357,12 +431,20 @@ impl Vecs {
             unknownoutputindex,
         } = starting_indexes;
 
+        self.channelcloseindex_to_channelclosetype
+            .truncate_if_needed(channelcloseindex, saved_height)?;
+        self.channelcloseindex_to_inputindex
+            .truncate_if_needed(channelcloseindex, saved_height)?;
         self.emptyoutputindex_to_txindex
             .truncate_if_needed(emptyoutputindex, saved_height)?;
         self.height_to_blockhash
             .truncate_if_needed(height, saved_height)?;
+        self.height_to_coinbase_scriptsig
+            .truncate_if_needed_(usize::from(height), saved_height)?;
         self.height_to_difficulty
             .truncate_if_needed(height, saved_height)?;
+        self.height_to_first_channelcloseindex
+            .truncate_if_needed(height, saved_height)?;
         self.height_to_first_emptyoutputindex
             .truncate_if_needed(height, saved_height)?;
         self.height_to_first_inputindex
@@ -393,6 +475,8 @@ impl Vecs {
             .truncate_if_needed(height, saved_height)?;
         self.height_to_first_unknownoutputindex
             .truncate_if_needed(height, saved_height)?;
+        self.height_to_taproot_control_block_count
+            .truncate_if_needed(height, saved_height)?;
         self.height_to_timestamp
             .truncate_if_needed(height, saved_height)?;
         self.height_to_total_size
@@ -478,10 +562,34 @@ impl Vecs {
         }
     }
 
+    /// Flushes and syncs all vecs, they're only part of a generation once `commit` is called
     pub fn flush(&mut self, height: Height) -> Result<()> {
         self.mut_vecs()
             .into_par_iter()
-            .try_for_each(|vec| vec.flush(height))
+            .try_for_each(|vec| vec.flush(height))?;
+
+        self.mut_vecs()
+            .into_par_iter()
+            .try_for_each(|vec| vec.sync())
+    }
+
+    /// Records the flushed vecs as a new generation in the commit log
+    pub fn commit(&mut self, height: Height) -> Result<()> {
+        let mut commit_log = self.commit_log.clone();
+        commit_log.commit(height, self.vecs())?;
+        self.commit_log = commit_log;
+
+        Ok(())
+    }
+
+    pub fn refresh(&self) -> color_eyre::Result<bool> {
+        Ok(self
+            .vecs()
+            .into_par_iter()
+            .map(|vec| vec.refresh())
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .any(|refreshed| refreshed))
     }
 
     pub fn starting_height(&mut self) -> Height {
@@ -497,9 +605,13 @@ impl Vecs {
 
     pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
         vec![
+            &self.channelcloseindex_to_channelclosetype,
+            &self.channelcloseindex_to_inputindex,
             &self.emptyoutputindex_to_txindex,
             &self.height_to_blockhash,
+            &self.height_to_coinbase_scriptsig,
             &self.height_to_difficulty,
+            &self.height_to_first_channelcloseindex,
             &self.height_to_first_emptyoutputindex,
             &self.height_to_first_inputindex,
             &self.height_to_first_opreturnindex,
@@ -515,6 +627,7 @@ impl Vecs {
             &self.height_to_first_p2wshaddressindex,
             &self.height_to_first_txindex,
             &self.height_to_first_unknownoutputindex,
+            &self.height_to_taproot_control_block_count,
             &self.height_to_timestamp,
             &self.height_to_total_size,
             &self.height_to_weight,
@@ -546,9 +659,13 @@ impl Vecs {
 
     fn mut_vecs(&mut self) -> Vec<&mut dyn AnyIndexedVec> {
         vec![
+            &mut self.channelcloseindex_to_channelclosetype,
+            &mut self.channelcloseindex_to_inputindex,
             &mut self.emptyoutputindex_to_txindex,
             &mut self.height_to_blockhash,
+            &mut self.height_to_coinbase_scriptsig,
             &mut self.height_to_difficulty,
+            &mut self.height_to_first_channelcloseindex,
             &mut self.height_to_first_emptyoutputindex,
             &mut self.height_to_first_inputindex,
             &mut self.height_to_first_opreturnindex,
@@ -564,6 +681,7 @@ impl Vecs {
             &mut self.height_to_first_p2wshaddressindex,
             &mut self.height_to_first_txindex,
             &mut self.height_to_first_unknownoutputindex,
+            &mut self.height_to_taproot_control_block_count,
             &mut self.height_to_timestamp,
             &mut self.height_to_total_size,
             &mut self.height_to_weight,
@@ -0,0 +1,95 @@
+use brk_computer::Computer;
+use brk_core::{Result, StoredF32};
+use brk_vec::{AnyVec, CollectableVec, EagerVec, Predicate, StoredIndex};
+use serde::{Deserialize, Serialize};
+
+use crate::ParamsOpt;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DrawdownIndex {
+    Height,
+    DateIndex,
+}
+
+/// Bound on the drawdown, in percents under the all time high (`lt=-50` for more than half of it), exactly one is expected
+#[derive(Debug, Default, Deserialize)]
+pub struct DrawdownBound {
+    lt: Option<f32>,
+    gt: Option<f32>,
+    /// Only the first match of the whole vec, `from` and `to` are ignored
+    #[serde(default)]
+    first: bool,
+}
+
+/// Heights or dateindexes whose drawdown is within a bound, along with it
+#[derive(Debug, Clone, Serialize)]
+pub struct DrawdownMatches {
+    pub indexes: Vec<usize>,
+    pub drawdowns: Vec<StoredF32>,
+}
+
+impl DrawdownMatches {
+    /// None if the bound isn't either `lt` or `gt`, pages whose statistics are out of it are skipped,
+    /// `from` and `to` behave like in `ParamsOpt`
+    pub fn get(
+        computer: &Computer,
+        index: DrawdownIndex,
+        bound: &DrawdownBound,
+        params: &ParamsOpt,
+    ) -> Option<Result<Self>> {
+        let predicate = match (bound.lt, bound.gt) {
+            (Some(lt), None) => Predicate::Lt(StoredF32::from(lt)),
+            (None, Some(gt)) => Predicate::Gt(StoredF32::from(gt)),
+            _ => return None,
+        };
+
+        let market = &computer.vecs.market;
+
+        Some(match index {
+            DrawdownIndex::Height => {
+                Self::find(&market.height_to_drawdown, &predicate, bound.first, params)
+            }
+            DrawdownIndex::DateIndex => Self::find(
+                market.indexes_to_drawdown.dateindex.as_ref().unwrap(),
+                &predicate,
+                bound.first,
+                params,
+            ),
+        })
+    }
+
+    fn find<I>(
+        vec: &EagerVec<I, StoredF32>,
+        predicate: &Predicate<StoredF32>,
+        first: bool,
+        params: &ParamsOpt,
+    ) -> Result<Self>
+    where
+        I: StoredIndex,
+    {
+        let matches = if first {
+            match vec.find_first_where(predicate)? {
+                Some(index) => {
+                    vec.range_where(predicate, index, I::from(index.unwrap_to_usize() + 1))?
+                }
+                None => vec![],
+            }
+        } else {
+            let len = vec.len();
+            let from = params
+                .from()
+                .map_or(0, |from| EagerVec::<I, StoredF32>::i64_to_usize(from, len));
+            let to = params
+                .to()
+                .map_or(len, |to| EagerVec::<I, StoredF32>::i64_to_usize(to, len));
+
+            vec.range_where(predicate, I::from(from), I::from(to))?
+        };
+
+        Ok(Self {
+            indexes: matches.iter().map(|(i, _)| i.unwrap_to_usize()).collect(),
+            drawdowns: matches.into_iter().map(|(_, drawdown)| drawdown).collect(),
+        })
+    }
+}
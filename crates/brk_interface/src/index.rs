@@ -1,10 +1,11 @@
 use std::fmt::{self, Debug};
 
 use brk_core::{
-    DateIndex, DecadeIndex, DifficultyEpoch, EmptyOutputIndex, HalvingEpoch, Height, InputIndex,
-    MonthIndex, OpReturnIndex, OutputIndex, P2AAddressIndex, P2MSOutputIndex, P2PK33AddressIndex,
-    P2PK65AddressIndex, P2PKHAddressIndex, P2SHAddressIndex, P2TRAddressIndex, P2WPKHAddressIndex,
-    P2WSHAddressIndex, Printable, QuarterIndex, TxIndex, UnknownOutputIndex, WeekIndex, YearIndex,
+    ChannelCloseIndex, DateIndex, DecadeIndex, DifficultyEpoch, EmptyOutputIndex, HalvingEpoch,
+    Height, InputIndex, MonthIndex, OpReturnIndex, OutputIndex, P2AAddressIndex, P2MSOutputIndex,
+    P2PK33AddressIndex, P2PK65AddressIndex, P2PKHAddressIndex, P2SHAddressIndex, P2TRAddressIndex,
+    P2WPKHAddressIndex, P2WSHAddressIndex, Printable, QuarterIndex, TxIndex, UnknownOutputIndex,
+    WeekIndex, YearIndex,
 };
 use color_eyre::eyre::eyre;
 use schemars::JsonSchema;
@@ -12,6 +13,8 @@ use serde::{Deserialize, de::Error};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
 pub enum Index {
+    #[schemars(description = "Lightning channel close index")]
+    ChannelCloseIndex,
     #[schemars(description = "Date/day index")]
     DateIndex,
     #[schemars(description = "Decade index")]
@@ -63,8 +66,9 @@ pub enum Index {
 }
 
 impl Index {
-    pub fn all() -> [Self; 24] {
+    pub fn all() -> [Self; 25] {
         [
+            Self::ChannelCloseIndex,
             Self::DateIndex,
             Self::DecadeIndex,
             Self::DifficultyEpoch,
@@ -94,6 +98,7 @@ impl Index {
 
     pub fn possible_values(&self) -> &'static [&'static str] {
         match self {
+            Self::ChannelCloseIndex => ChannelCloseIndex::to_possible_strings(),
             Self::DateIndex => DateIndex::to_possible_strings(),
             Self::DecadeIndex => DecadeIndex::to_possible_strings(),
             Self::DifficultyEpoch => DifficultyEpoch::to_possible_strings(),
@@ -144,6 +149,9 @@ impl TryFrom<&str> for Index {
     type Error = color_eyre::Report;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         Ok(match value.to_lowercase().as_str() {
+            v if (Self::ChannelCloseIndex).possible_values().contains(&v) => {
+                Self::ChannelCloseIndex
+            }
             v if (Self::DateIndex).possible_values().contains(&v) => Self::DateIndex,
             v if (Self::DecadeIndex).possible_values().contains(&v) => Self::DecadeIndex,
             v if (Self::DifficultyEpoch).possible_values().contains(&v) => Self::DifficultyEpoch,
@@ -8,41 +8,48 @@ use std::collections::BTreeMap;
 use brk_computer::Computer;
 use brk_core::Result;
 use brk_indexer::Indexer;
+use brk_store::StoreStats;
 use brk_vec::AnyCollectableVec;
 use tabled::settings::Style;
 
 mod deser;
+mod drawdown;
 mod format;
 mod index;
 mod maybe_ids;
 mod output;
 mod pagination;
 mod params;
+mod projections;
 mod table;
+mod urpd;
 mod vecs;
 
+pub use drawdown::{DrawdownBound, DrawdownIndex, DrawdownMatches};
 pub use format::Format;
 pub use index::Index;
 pub use output::{Output, Value};
 pub use pagination::{PaginatedIndexParam, PaginationParam};
 pub use params::{IdParam, Params, ParamsOpt};
+pub use projections::{DifficultyAdjustment, Halving};
 pub use table::Tabled;
+pub use urpd::{Urpd, UrpdCohort};
 use vecs::Vecs;
 
 use crate::vecs::{IdToVec, IndexToVec};
 
 pub struct Interface<'a> {
     vecs: Vecs<'a>,
-    _indexer: &'a Indexer,
-    _computer: &'a Computer,
+    indexer: &'a Indexer,
+    computer: &'a Computer,
 }
 
 impl<'a> Interface<'a> {
     pub fn build(indexer: &'a Indexer, computer: &'a Computer) -> Self {
         Self {
             vecs: Vecs::build(indexer, computer),
-            _indexer: indexer,
-            _computer: computer,
+            indexer,
+            computer,
         }
     }
 
@@ -187,6 +194,39 @@ impl<'a> Interface<'a> {
         self.vecs.vec_count
     }
 
+    /// Empty if the indexer was imported read-only, without its stores
+    pub fn get_store_stats(&self) -> Result<Vec<StoreStats>> {
+        self.indexer
+            .stores
+            .as_ref()
+            .map_or_else(|| Ok(vec![]), |stores| stores.stats())
+    }
+
+    /// None until the blocks vecs have been computed
+    pub fn get_difficulty_adjustment(&self) -> Option<DifficultyAdjustment> {
+        DifficultyAdjustment::last(self.computer)
+    }
+
+    /// None until the blocks vecs have been computed
+    pub fn get_halving(&self) -> Option<Halving> {
+        Halving::last(self.computer)
+    }
+
+    /// None if dollars aren't computed
+    pub fn get_urpd(&self, cohort: UrpdCohort, params: &ParamsOpt) -> Option<Result<Urpd>> {
+        Urpd::get(self.computer, cohort, params)
+    }
+
+    /// None if the bound is invalid, skips the pages whose drawdowns are all out of it
+    pub fn get_drawdown_matches(
+        &self,
+        index: DrawdownIndex,
+        bound: &DrawdownBound,
+        params: &ParamsOpt,
+    ) -> Option<Result<DrawdownMatches>> {
+        DrawdownMatches::get(self.computer, index, bound, params)
+    }
+
     pub fn get_indexes(&self) -> &[&'static str] {
         &self.vecs.indexes
     }
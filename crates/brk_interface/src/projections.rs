@@ -0,0 +1,117 @@
+use brk_computer::Computer;
+use brk_core::{DifficultyEpoch, HalvingEpoch, Height, Timestamp};
+use brk_vec::{AnyVec, EagerVec, StoredType, VecIterator};
+use serde::Serialize;
+
+/// Status of the current difficulty epoch, as of the last computed block
+#[derive(Debug, Clone, Serialize)]
+pub struct DifficultyAdjustment {
+    pub height: Height,
+    pub epoch: DifficultyEpoch,
+    /// Percentage of the blocks of the epoch already mined
+    pub progress: f64,
+    pub remaining_blocks: u32,
+    pub next_height: Height,
+    /// Percentage by which the difficulty would change at the current pace
+    pub estimated_adjustment: f64,
+    pub estimated_timestamp: Timestamp,
+}
+
+/// Status of the current halving epoch, as of the last computed block
+#[derive(Debug, Clone, Serialize)]
+pub struct Halving {
+    pub height: Height,
+    pub epoch: HalvingEpoch,
+    /// Percentage of the blocks of the epoch already mined
+    pub progress: f64,
+    pub remaining_blocks: u32,
+    pub next_height: Height,
+    pub estimated_timestamp: Timestamp,
+}
+
+impl DifficultyAdjustment {
+    /// None until the projections have been computed
+    pub fn last(computer: &Computer) -> Option<Self> {
+        let blocks = &computer.vecs.blocks;
+
+        let (height, remaining_blocks) = last(
+            blocks
+                .indexes_to_blocks_before_next_difficulty_adjustment
+                .height
+                .as_ref()?,
+        )?;
+        let remaining_blocks = *remaining_blocks;
+
+        Some(Self {
+            height,
+            epoch: DifficultyEpoch::from(height),
+            progress: progress(remaining_blocks, DifficultyEpoch::BLOCK_COUNT),
+            remaining_blocks,
+            next_height: Height::from(u32::from(height) + remaining_blocks),
+            estimated_adjustment: f64::from(get(
+                blocks
+                    .indexes_to_estimated_difficulty_adjustment
+                    .height
+                    .as_ref()?,
+                height,
+            )?),
+            estimated_timestamp: get(
+                blocks
+                    .indexes_to_estimated_next_difficulty_adjustment_timestamp
+                    .height
+                    .as_ref()?,
+                height,
+            )?,
+        })
+    }
+}
+
+impl Halving {
+    /// None until the projections have been computed
+    pub fn last(computer: &Computer) -> Option<Self> {
+        let blocks = &computer.vecs.blocks;
+
+        let (height, remaining_blocks) = last(
+            blocks
+                .indexes_to_blocks_before_next_halving
+                .height
+                .as_ref()?,
+        )?;
+        let remaining_blocks = *remaining_blocks;
+
+        Some(Self {
+            height,
+            epoch: HalvingEpoch::from(height),
+            progress: progress(remaining_blocks, HalvingEpoch::BLOCK_COUNT),
+            remaining_blocks,
+            next_height: Height::from(u32::from(height) + remaining_blocks),
+            estimated_timestamp: get(
+                blocks
+                    .indexes_to_estimated_next_halving_timestamp
+                    .height
+                    .as_ref()?,
+                height,
+            )?,
+        })
+    }
+}
+
+/// The block at `height` is already mined, hence the `+ 1`
+fn progress(remaining_blocks: u32, block_count: u32) -> f64 {
+    (block_count - remaining_blocks + 1) as f64 / block_count as f64 * 100.0
+}
+
+fn last<T>(vec: &EagerVec<Height, T>) -> Option<(Height, T)>
+where
+    T: StoredType,
+{
+    let height = Height::from(vec.len().checked_sub(1)?);
+    Some((height, get(vec, height)?))
+}
+
+fn get<T>(vec: &EagerVec<Height, T>, height: Height) -> Option<T>
+where
+    T: StoredType,
+{
+    vec.into_iter().get_inner(height)
+}
@@ -0,0 +1,72 @@
+use brk_computer::Computer;
+use brk_core::{DateIndex, Dollars, Result, Sats};
+use brk_vec::{AnyVec, CollectableVec, EagerVec};
+use serde::{Deserialize, Serialize};
+
+use crate::ParamsOpt;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UrpdCohort {
+    All,
+    Sth,
+    Lth,
+}
+
+/// UTXO realized price distribution of a cohort: its supply by the price at which it last moved, one row per date
+#[derive(Debug, Clone, Serialize)]
+pub struct Urpd {
+    /// Inclusive lower bound of each bucket
+    pub bounds: Vec<Dollars>,
+    pub dateindexes: Vec<DateIndex>,
+    /// Supply of each bucket, `rows[i][j]` being the one of `bounds[j]` at `dateindexes[i]`
+    pub rows: Vec<Vec<Sats>>,
+}
+
+impl Urpd {
+    /// None if the computer doesn't compute dollars, `from` and `to` are dateindexes and behave like in `ParamsOpt`
+    pub fn get(
+        computer: &Computer,
+        cohort: UrpdCohort,
+        params: &ParamsOpt,
+    ) -> Option<Result<Self>> {
+        let urpd = computer.vecs.stateful.urpd.as_ref()?;
+
+        let vecs = match cohort {
+            UrpdCohort::All => &urpd.all,
+            UrpdCohort::Sth => &urpd.by_term.short.1,
+            UrpdCohort::Lth => &urpd.by_term.long.1,
+        };
+
+        Some(Self::collect(urpd.buckets().bounds(), vecs, params))
+    }
+
+    fn collect(
+        bounds: &[Dollars],
+        vecs: &[EagerVec<DateIndex, Sats>],
+        params: &ParamsOpt,
+    ) -> Result<Self> {
+        let len = vecs.iter().map(|vec| vec.len()).min().unwrap_or_default();
+        let from = params.from().map_or(0, |from| {
+            EagerVec::<DateIndex, Sats>::i64_to_usize(from, len)
+        });
+        let to = params
+            .to()
+            .map_or(len, |to| EagerVec::<DateIndex, Sats>::i64_to_usize(to, len));
+
+        let columns = vecs
+            .iter()
+            .map(|vec| vec.collect_range(Some(from), Some(to)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let row_count = columns.first().map_or(0, |column| column.len());
+
+        Ok(Self {
+            bounds: bounds.to_vec(),
+            dateindexes: (from..from + row_count).map(DateIndex::from).collect(),
+            rows: (0..row_count)
+                .map(|i| columns.iter().map(|column| column[i]).collect())
+                .collect(),
+        })
+    }
+}
@@ -1,11 +1,14 @@
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
-    http::HeaderMap,
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Redirect, Response},
     routing::get,
 };
-use brk_interface::{IdParam, Index, PaginatedIndexParam, PaginationParam, Params, ParamsOpt};
+use brk_interface::{
+    DrawdownBound, DrawdownIndex, IdParam, Index, PaginatedIndexParam, PaginationParam, Params,
+    ParamsOpt, UrpdCohort,
+};
 
 use super::AppState;
 
@@ -82,6 +85,75 @@ impl ApiRoutes for Router<AppState> {
                 },
             ),
         )
+        .route(
+            "/api/stores/stats",
+            get(async |State(app_state): State<AppState>| -> Response {
+                match app_state.interface.get_store_stats() {
+                    Ok(stats) => Json(stats).into_response(),
+                    Err(error) => {
+                        (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+                    }
+                }
+            }),
+        )
+        .route(
+            "/api/mining/difficulty-adjustment",
+            get(async |State(app_state): State<AppState>| -> Response {
+                match app_state.interface.get_difficulty_adjustment() {
+                    Some(status) => Json(status).into_response(),
+                    None => (StatusCode::SERVICE_UNAVAILABLE, "Not computed yet").into_response(),
+                }
+            }),
+        )
+        .route(
+            "/api/mining/halving",
+            get(async |State(app_state): State<AppState>| -> Response {
+                match app_state.interface.get_halving() {
+                    Some(status) => Json(status).into_response(),
+                    None => (StatusCode::SERVICE_UNAVAILABLE, "Not computed yet").into_response(),
+                }
+            }),
+        )
+        .route(
+            "/api/urpd/{cohort}",
+            get(
+                async |State(app_state): State<AppState>,
+                       Path(cohort): Path<UrpdCohort>,
+                       Query(params_opt): Query<ParamsOpt>|
+                       -> Response {
+                    match app_state.interface.get_urpd(cohort, &params_opt) {
+                        Some(Ok(urpd)) => Json(urpd).into_response(),
+                        Some(Err(error)) => {
+                            (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+                        }
+                        None => (StatusCode::SERVICE_UNAVAILABLE, "Not computed").into_response(),
+                    }
+                },
+            ),
+        )
+        .route(
+            "/api/drawdown/{index}",
+            get(
+                async |State(app_state): State<AppState>,
+                       Path(index): Path<DrawdownIndex>,
+                       Query(bound): Query<DrawdownBound>,
+                       Query(params_opt): Query<ParamsOpt>|
+                       -> Response {
+                    match app_state
+                        .interface
+                        .get_drawdown_matches(index, &bound, &params_opt)
+                    {
+                        Some(Ok(matches)) => Json(matches).into_response(),
+                        Some(Err(error)) => {
+                            (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+                        }
+                        None => {
+                            (StatusCode::BAD_REQUEST, "Expected either lt or gt").into_response()
+                        }
+                    }
+                },
+            ),
+        )
         // .route("/api/vecs/variants", get(variants_handler))
         .route("/api/vecs/query", get(interface::handler))
         .route(
@@ -10,6 +10,7 @@ pub struct CohortState {
     pub realized: Option<RealizedState>,
     pub satblocks_destroyed: Sats,
     pub satdays_destroyed: Sats,
+    pub sent: Sats,
     pub price_to_amount: PriceToAmount,
 }
 
@@ -20,12 +21,14 @@ impl CohortStateTrait for CohortState {
             realized: compute_dollars.then_some(RealizedState::NAN),
             satblocks_destroyed: Sats::ZERO,
             satdays_destroyed: Sats::ZERO,
+            sent: Sats::ZERO,
             price_to_amount: PriceToAmount::forced_import(path, name),
         })
     }
 
     fn reset_single_iteration_values(&mut self) {
         self.satdays_destroyed = Sats::ZERO;
+        self.sent = Sats::ZERO;
         self.satblocks_destroyed = Sats::ZERO;
         if let Some(realized) = self.realized.as_mut() {
             realized.reset_single_iteration_values();
@@ -89,6 +92,7 @@ impl CohortStateTrait for CohortState {
             self.supply -= supply_state;
 
             if supply_state.value > Sats::ZERO {
+                self.sent += supply_state.value;
                 self.satblocks_destroyed += supply_state.value * blocks_old;
 
                 self.satdays_destroyed +=
@@ -0,0 +1,140 @@
+use std::{ops::Bound, path::Path};
+
+use brk_core::{Error, Result};
+use byteview::ByteView;
+use fjall::{
+    PartitionCreateOptions, PersistMode, ReadTransaction, TransactionalKeyspace,
+    TransactionalPartitionHandle,
+};
+
+use super::{BoxedKvIterator, KvBackend, PartitionStats, PartitionWrite};
+
+/// Backend persisted to disk by a fjall keyspace
+#[derive(Clone)]
+pub struct FjallBackend(TransactionalKeyspace);
+
+impl FjallBackend {
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(Self(
+            fjall::Config::new(path.join("fjall"))
+                .max_write_buffer_size(32 * 1024 * 1024)
+                .open_transactional()?,
+        ))
+    }
+
+    /// Whether an error of [`Self::open`] comes from a keyspace written in a format this version of fjall can't read,
+    /// the only case where it should be deleted
+    pub fn is_incompatible(error: &Error) -> bool {
+        matches!(error, Error::Fjall(fjall::Error::InvalidVersion(_)))
+    }
+
+    pub fn keyspace(&self) -> &TransactionalKeyspace {
+        &self.0
+    }
+
+    fn map_iter(
+        iter: impl DoubleEndedIterator<Item = fjall::Result<fjall::KvPair>> + 'static,
+    ) -> BoxedKvIterator {
+        Box::new(iter.map(|res| {
+            let (key, value) = res?;
+            Ok((ByteView::from(key), ByteView::from(value)))
+        }))
+    }
+}
+
+impl KvBackend for FjallBackend {
+    type Partition = TransactionalPartitionHandle;
+    type Snapshot = ReadTransaction;
+
+    const PERSISTENT: bool = true;
+
+    fn open_partition(&self, name: &str, bloom_filter_bits: Option<u8>) -> Result<Self::Partition> {
+        self.0
+            .open_partition(
+                name,
+                PartitionCreateOptions::default()
+                    .bloom_filter_bits(bloom_filter_bits)
+                    .max_memtable_size(8 * 1024 * 1024)
+                    .manual_journal_persist(true),
+            )
+            .map_err(|e| e.into())
+    }
+
+    fn delete_partition(&self, partition: Self::Partition) -> Result<()> {
+        self.0.delete_partition(partition)?;
+        self.persist()
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.0.read_tx()
+    }
+
+    fn get(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        key: &[u8],
+    ) -> Result<Option<ByteView>> {
+        Ok(snapshot.get(partition, key)?.map(ByteView::from))
+    }
+
+    fn range(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        range: (Bound<ByteView>, Bound<ByteView>),
+    ) -> BoxedKvIterator {
+        Self::map_iter(snapshot.range(partition, range))
+    }
+
+    fn prefix(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        prefix: &[u8],
+    ) -> BoxedKvIterator {
+        Self::map_iter(snapshot.prefix(partition, prefix))
+    }
+
+    fn write(&self, writes: Vec<PartitionWrite<Self::Partition>>) -> Result<()> {
+        let mut wtx = self.0.write_tx();
+
+        writes.into_iter().for_each(|write| {
+            let PartitionWrite {
+                partition,
+                dels,
+                puts,
+            } = write;
+
+            dels.into_iter().for_each(|key| wtx.remove(&partition, key));
+
+            puts.into_iter()
+                .for_each(|(key, value)| wtx.insert(&partition, key, value));
+        });
+
+        wtx.commit()?;
+
+        Ok(())
+    }
+
+    fn stats(&self, partition: &Self::Partition) -> PartitionStats {
+        let partition = partition.inner();
+        PartitionStats {
+            approximate_len: partition.approximate_len(),
+            disk_bytes: partition.disk_space(),
+            segment_count: partition.segment_count(),
+        }
+    }
+
+    fn compact(&self, partition: &Self::Partition) -> Result<()> {
+        partition.inner().major_compact().map_err(|e| e.into())
+    }
+
+    fn persist(&self) -> Result<()> {
+        self.0.persist(PersistMode::SyncAll).map_err(|e| e.into())
+    }
+
+    fn rotate_memtable(&self, partition: &Self::Partition) {
+        let _ = partition.inner().rotate_memtable();
+    }
+}
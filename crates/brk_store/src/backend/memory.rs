@@ -0,0 +1,161 @@
+use std::{
+    collections::BTreeMap,
+    ops::Bound,
+    sync::{Arc, RwLock},
+};
+
+use brk_core::Result;
+use byteview::ByteView;
+
+use super::{BoxedKvIterator, KvBackend, PartitionStats, PartitionWrite};
+
+type Partition = BTreeMap<Vec<u8>, ByteView>;
+
+/// Backend living in memory only, nothing outlives the process
+///
+/// Partitions are copied on write, so snapshots are only clones of their handles.
+#[derive(Default, Clone)]
+pub struct MemoryBackend {
+    partitions: Arc<RwLock<BTreeMap<String, Arc<Partition>>>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MemoryPartition(String);
+
+#[derive(Clone)]
+pub struct MemorySnapshot(BTreeMap<String, Arc<Partition>>);
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn collect<'a>(iter: impl Iterator<Item = (&'a Vec<u8>, &'a ByteView)>) -> BoxedKvIterator {
+        Box::new(
+            iter.map(|(key, value)| Ok((ByteView::new(key), value.clone())))
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )
+    }
+}
+
+impl KvBackend for MemoryBackend {
+    type Partition = MemoryPartition;
+    type Snapshot = MemorySnapshot;
+
+    const PERSISTENT: bool = false;
+
+    fn open_partition(&self, name: &str, _: Option<u8>) -> Result<Self::Partition> {
+        self.partitions
+            .write()
+            .unwrap()
+            .entry(name.to_owned())
+            .or_default();
+        Ok(MemoryPartition(name.to_owned()))
+    }
+
+    fn delete_partition(&self, partition: Self::Partition) -> Result<()> {
+        self.partitions.write().unwrap().remove(&partition.0);
+        Ok(())
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        MemorySnapshot(self.partitions.read().unwrap().clone())
+    }
+
+    fn get(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        key: &[u8],
+    ) -> Result<Option<ByteView>> {
+        Ok(snapshot
+            .0
+            .get(&partition.0)
+            .and_then(|partition| partition.get(key))
+            .cloned())
+    }
+
+    fn range(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        (start, end): (Bound<ByteView>, Bound<ByteView>),
+    ) -> BoxedKvIterator {
+        let Some(partition) = snapshot.0.get(&partition.0) else {
+            return Box::new(std::iter::empty());
+        };
+
+        // Unlike fjall, a BTreeMap panics on inverted or empty exclusive ranges
+        let is_empty = match (&start, &end) {
+            (Bound::Included(start), Bound::Included(end)) => start[..] > end[..],
+            (Bound::Included(start) | Bound::Excluded(start), Bound::Excluded(end))
+            | (Bound::Excluded(start), Bound::Included(end)) => start[..] >= end[..],
+            _ => false,
+        };
+        if is_empty {
+            return Box::new(std::iter::empty());
+        }
+
+        Self::collect(partition.range::<[u8], _>((
+            start.as_ref().map(|key| &**key),
+            end.as_ref().map(|key| &**key),
+        )))
+    }
+
+    fn prefix(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        prefix: &[u8],
+    ) -> BoxedKvIterator {
+        let Some(partition) = snapshot.0.get(&partition.0) else {
+            return Box::new(std::iter::empty());
+        };
+
+        Self::collect(
+            partition
+                .range::<[u8], _>((Bound::Included(prefix), Bound::Unbounded))
+                .take_while(|(key, _)| key.starts_with(prefix)),
+        )
+    }
+
+    fn write(&self, writes: Vec<PartitionWrite<Self::Partition>>) -> Result<()> {
+        // A single lock for all of them, so no snapshot can see only some of the writes
+        let mut partitions = self.partitions.write().unwrap();
+
+        writes.into_iter().for_each(|write| {
+            let partition = Arc::make_mut(partitions.entry(write.partition.0).or_default());
+
+            write.dels.into_iter().for_each(|key| {
+                partition.remove(&*key);
+            });
+
+            write.puts.into_iter().for_each(|(key, value)| {
+                partition.insert(key.to_vec(), value);
+            });
+        });
+
+        Ok(())
+    }
+
+    fn stats(&self, partition: &Self::Partition) -> PartitionStats {
+        PartitionStats {
+            approximate_len: self
+                .partitions
+                .read()
+                .unwrap()
+                .get(&partition.0)
+                .map_or(0, |partition| partition.len()),
+            ..Default::default()
+        }
+    }
+
+    fn compact(&self, _: &Self::Partition) -> Result<()> {
+        Ok(())
+    }
+
+    fn persist(&self) -> Result<()> {
+        Ok(())
+    }
+}
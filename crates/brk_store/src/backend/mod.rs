@@ -0,0 +1,86 @@
+use std::ops::Bound;
+
+use brk_core::Result;
+use byteview::ByteView;
+use serde::Serialize;
+
+mod keyspace;
+mod memory;
+
+pub use keyspace::*;
+pub use memory::*;
+
+pub type KvPair = (ByteView, ByteView);
+pub type BoxedKvIterator = Box<dyn DoubleEndedIterator<Item = Result<KvPair>>>;
+
+/// Removals and insertions of one partition, part of a write which may span several
+#[derive(Clone)]
+pub struct PartitionWrite<P> {
+    pub partition: P,
+    pub dels: Vec<ByteView>,
+    pub puts: Vec<KvPair>,
+}
+
+/// Statistics of a partition, as reported by its backend
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct PartitionStats {
+    /// Can count overwritten and removed keys until they're compacted away
+    pub approximate_len: usize,
+    pub disk_bytes: u64,
+    pub segment_count: usize,
+}
+
+/// Ordered key-value storage behind a [`crate::Store`]
+///
+/// Backends only deal with bytes, versions, heights and rollbacks are handled by the store.
+/// Keys are compared lexicographically as bytes, which isn't the order of the keys themselves unless they're encoded
+/// big endian.
+pub trait KvBackend: Clone + Send + Sync {
+    type Partition: Clone + Send + Sync;
+    /// Consistent view of every partition at a point in time
+    type Snapshot: Send + Sync;
+
+    /// Whether the data outlives the process, the metadata of the stores is only written to disk if so
+    const PERSISTENT: bool;
+
+    fn open_partition(&self, name: &str, bloom_filter_bits: Option<u8>) -> Result<Self::Partition>;
+
+    fn delete_partition(&self, partition: Self::Partition) -> Result<()>;
+
+    fn snapshot(&self) -> Self::Snapshot;
+
+    fn get(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        key: &[u8],
+    ) -> Result<Option<ByteView>>;
+
+    fn range(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        range: (Bound<ByteView>, Bound<ByteView>),
+    ) -> BoxedKvIterator;
+
+    fn prefix(
+        &self,
+        snapshot: &Self::Snapshot,
+        partition: &Self::Partition,
+        prefix: &[u8],
+    ) -> BoxedKvIterator;
+
+    /// Applies the removals then the insertions of every partition atomically, visible to snapshots taken afterwards
+    fn write(&self, writes: Vec<PartitionWrite<Self::Partition>>) -> Result<()>;
+
+    fn stats(&self, partition: &Self::Partition) -> PartitionStats;
+
+    /// Merges all the segments of the partition, which drops overwritten and removed keys, blocking and expensive
+    fn compact(&self, partition: &Self::Partition) -> Result<()>;
+
+    /// Makes every write durable
+    fn persist(&self) -> Result<()>;
+
+    /// Hint that a batch of writes is done, a no-op unless the backend buffers them in memory
+    fn rotate_memtable(&self, _: &Self::Partition) {}
+}
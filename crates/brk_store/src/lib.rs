@@ -7,25 +7,32 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
     mem,
+    ops::{Bound, RangeBounds},
     path::Path,
 };
 
 use brk_core::{Height, Result, Value, Version};
 use byteview::ByteView;
-use fjall::{
-    PartitionCreateOptions, PersistMode, ReadTransaction, TransactionalKeyspace,
-    TransactionalPartitionHandle,
-};
 
+mod backend;
+mod merged;
 mod meta;
+mod stats;
+pub use backend::*;
+use merged::*;
 use meta::*;
+pub use stats::*;
 
-pub struct Store<Key, Value> {
+pub struct Store<Key, Value, B = FjallBackend>
+where
+    B: KvBackend,
+{
     meta: StoreMeta,
     name: String,
-    keyspace: TransactionalKeyspace,
-    partition: Option<TransactionalPartitionHandle>,
-    rtx: ReadTransaction,
+    backend: B,
+    partition: Option<B::Partition>,
+    meta_partition: B::Partition,
+    snapshot: B::Snapshot,
     puts: BTreeMap<Key, Value>,
     dels: BTreeSet<Key>,
     bloom_filter_bits: Option<Option<u8>>,
@@ -33,48 +40,61 @@ pub struct Store<Key, Value> {
 
 /// Use default if will read
 const DEFAULT_BLOOM_FILTER_BITS: Option<u8> = Some(5);
-// const CHECK_COLLISISONS: bool = true;
 const MAJOR_FJALL_VERSION: Version = Version::TWO;
+/// Partition shared by all stores, with the height and length of each one, keyed by name
+const META_PARTITION: &str = "_meta";
 
-pub fn open_keyspace(path: &Path) -> fjall::Result<TransactionalKeyspace> {
-    fjall::Config::new(path.join("fjall"))
-        .max_write_buffer_size(32 * 1024 * 1024)
-        .open_transactional()
-}
-
-impl<'a, K, V> Store<K, V>
+impl<'a, K, V, B> Store<K, V, B>
 where
     K: Debug + Clone + From<ByteView> + Ord + 'a,
     V: Debug + Clone + From<ByteView>,
     ByteView: From<K> + From<&'a K> + From<V>,
+    B: KvBackend,
 {
+    /// The metadata is stored in `path`, unless the backend isn't persistent in which case the store starts empty
+    ///
+    /// `bloom_filter_bits` are per key, `None` for the default and `Some(None)` to disable them
     pub fn import(
-        keyspace: &TransactionalKeyspace,
+        backend: &B,
         path: &Path,
         name: &str,
         version: Version,
         bloom_filter_bits: Option<Option<u8>>,
     ) -> Result<Self> {
-        let (meta, partition) = StoreMeta::checked_open(
-            keyspace,
-            &path.join(format!("meta/{name}")),
+        let meta_path = path.join(format!("meta/{name}"));
+
+        let (mut meta, is_same_version) = StoreMeta::checked_open(
+            B::PERSISTENT.then_some(meta_path.as_path()),
             MAJOR_FJALL_VERSION + version,
-            || {
-                Self::open_partition_handle(keyspace, name, bloom_filter_bits).inspect_err(|e| {
-                    eprintln!("{e}");
-                    eprintln!("Delete {path:?} and try again");
-                })
-            },
         )?;
 
-        let rtx = keyspace.read_tx();
+        let open_partition = || {
+            Self::open_partition(backend, name, bloom_filter_bits).inspect_err(|e| {
+                eprintln!("{e}");
+                eprintln!("Delete {path:?} and try again");
+            })
+        };
+
+        let meta_partition = backend.open_partition(META_PARTITION, DEFAULT_BLOOM_FILTER_BITS)?;
+        let mut partition = open_partition()?;
+
+        if !is_same_version || !B::PERSISTENT {
+            backend.delete_partition(partition)?;
+            backend.write(vec![Self::meta_del(&meta_partition, name)])?;
+            partition = open_partition()?;
+        } else if let Some(bytes) =
+            backend.get(&backend.snapshot(), &meta_partition, name.as_bytes())?
+        {
+            meta.restore(&bytes);
+        }
 
         Ok(Self {
             meta,
             name: name.to_owned(),
-            keyspace: keyspace.clone(),
+            backend: backend.clone(),
             partition: Some(partition),
-            rtx,
+            meta_partition,
+            snapshot: backend.snapshot(),
             puts: BTreeMap::new(),
             dels: BTreeSet::new(),
             bloom_filter_bits,
@@ -84,44 +104,136 @@ where
     pub fn get(&self, key: &'a K) -> Result<Option<Value<V>>> {
         if let Some(v) = self.puts.get(key) {
             Ok(Some(Value::Ref(v)))
-        } else if let Some(slice) = self
-            .rtx
-            .get(self.partition.as_ref().unwrap(), ByteView::from(key))?
-        {
-            Ok(Some(Value::Owned(V::from(ByteView::from(slice)))))
+        } else if let Some(bytes) = self.backend.get(
+            &self.snapshot,
+            self.partition.as_ref().unwrap(),
+            &ByteView::from(key),
+        )? {
+            Ok(Some(Value::Owned(V::from(bytes))))
         } else {
             Ok(None)
         }
     }
 
-    // pub fn puts_first_key_value(&self) -> Option<(&K, &V)> {
-    //     self.puts.first_key_value()
-    // }
+    /// Entries with a key within `range`, pending puts and dels included
+    ///
+    /// Keys are compared and returned in the order of their encoded bytes, which is the order of the keys themselves
+    /// only if they're encoded big endian, which isn't the case of the integer indexes.
+    pub fn range<R>(&self, range: R) -> impl Iterator<Item = Result<(K, Value<'_, V>)>>
+    where
+        R: RangeBounds<K>,
+    {
+        let range = Self::encode_range(&range);
+        Merged::new(
+            self.committed_range(range.clone()),
+            self.puts_range(range).into_iter(),
+            &self.dels,
+            false,
+        )
+    }
 
-    // pub fn puts_last_key_value(&self) -> Option<(&K, &V)> {
-    //     self.puts.last_key_value()
-    // }
+    /// Same as [`Self::range`] but from the last key to the first
+    pub fn range_rev<R>(&self, range: R) -> impl Iterator<Item = Result<(K, Value<'_, V>)>>
+    where
+        R: RangeBounds<K>,
+    {
+        let range = Self::encode_range(&range);
+        Merged::new(
+            self.committed_range(range.clone()).rev(),
+            self.puts_range(range).into_iter().rev(),
+            &self.dels,
+            true,
+        )
+    }
 
-    // pub fn rtx_first_key_value(&self) -> Result<Option<(K, V)>> {
-    //     Ok(self
-    //         .rtx
-    //         .first_key_value(&self.partition.load())?
-    //         .map(|(k, v)| (K::from(ByteView::from(k)), V::from(ByteView::from(v)))))
-    // }
+    /// Entries whose encoded key starts with `prefix`, in the order of their encoded bytes, pending puts and dels
+    /// included
+    ///
+    /// Useful for composite keys, where the prefix is the bytes of their first part
+    pub fn prefix(&self, prefix: &[u8]) -> impl Iterator<Item = Result<(K, Value<'_, V>)>> {
+        Merged::new(
+            self.committed_prefix(prefix),
+            self.puts_prefix(prefix).into_iter(),
+            &self.dels,
+            false,
+        )
+    }
 
-    // pub fn rtx_last_key_value(&self) -> Result<Option<(K, V)>> {
-    //     Ok(self
-    //         .rtx
-    //         .last_key_value(&self.partition.load())?
-    //         .map(|(k, v)| (K::from(ByteView::from(k)), V::from(ByteView::from(v)))))
-    // }
+    /// Same as [`Self::prefix`] but from the last key to the first
+    pub fn prefix_rev(&self, prefix: &[u8]) -> impl Iterator<Item = Result<(K, Value<'_, V>)>> {
+        Merged::new(
+            self.committed_prefix(prefix).rev(),
+            self.puts_prefix(prefix).into_iter().rev(),
+            &self.dels,
+            true,
+        )
+    }
 
-    // pub fn tx_iter(&self) -> impl Iterator<Item = (K, V)> {
-    //     self.rtx
-    //         .iter(&self.partition.load())
-    //         .map(|res| res.unwrap())
-    //         .map(|(k, v)| (K::from(ByteView::from(k)), V::from(ByteView::from(v))))
-    // }
+    pub fn iter(&self) -> impl Iterator<Item = Result<(K, Value<'_, V>)>> {
+        self.range(..)
+    }
+
+    pub fn iter_rev(&self) -> impl Iterator<Item = Result<(K, Value<'_, V>)>> {
+        self.range_rev(..)
+    }
+
+    pub fn first_key_value(&self) -> Result<Option<(K, Value<'_, V>)>> {
+        self.iter().next().transpose()
+    }
+
+    pub fn last_key_value(&self) -> Result<Option<(K, Value<'_, V>)>> {
+        self.iter_rev().next().transpose()
+    }
+
+    fn encode_range<R>(range: &R) -> (Bound<ByteView>, Bound<ByteView>)
+    where
+        R: RangeBounds<K>,
+    {
+        let to_bytes = |key: &K| ByteView::from(key.clone());
+        (
+            range.start_bound().map(to_bytes),
+            range.end_bound().map(to_bytes),
+        )
+    }
+
+    fn committed_range(
+        &self,
+        range: (Bound<ByteView>, Bound<ByteView>),
+    ) -> impl DoubleEndedIterator<Item = Result<KvPair>> + use<K, V, B> {
+        self.backend
+            .range(&self.snapshot, self.partition.as_ref().unwrap(), range)
+    }
+
+    fn committed_prefix(
+        &self,
+        prefix: &[u8],
+    ) -> impl DoubleEndedIterator<Item = Result<KvPair>> + use<K, V, B> {
+        self.backend
+            .prefix(&self.snapshot, self.partition.as_ref().unwrap(), prefix)
+    }
+
+    fn puts_range(&self, range: (Bound<ByteView>, Bound<ByteView>)) -> Vec<(ByteView, &K, &V)> {
+        self.sorted_puts(|bytes| range.contains(bytes))
+    }
+
+    fn puts_prefix(&self, prefix: &[u8]) -> Vec<(ByteView, &K, &V)> {
+        self.sorted_puts(|bytes| bytes.starts_with(prefix))
+    }
+
+    /// Puts are sorted by key, which may not be the order of their bytes
+    fn sorted_puts<F>(&self, filter: F) -> Vec<(ByteView, &K, &V)>
+    where
+        F: Fn(&ByteView) -> bool,
+    {
+        let mut puts = self
+            .puts
+            .iter()
+            .map(|(key, value)| (ByteView::from(key.clone()), key, value))
+            .filter(|(bytes, ..)| filter(bytes))
+            .collect::<Vec<_>>();
+        puts.sort_unstable_by(|(a, ..), (b, ..)| a[..].cmp(&b[..]));
+        puts
+    }
 
     pub fn insert_if_needed(&mut self, key: K, value: V, height: Height) {
         if self.needs(height) {
@@ -161,54 +273,122 @@ where
     //     });
     // }
 
-    pub fn commit(&mut self, height: Height) -> Result<()> {
-        if self.has(height) && self.puts.is_empty() && self.dels.is_empty() {
+    /// Removes the key only if it's committed, for rollbacks of stores which may be behind the others
+    pub fn remove_if_committed(&mut self, key: K) -> Result<()> {
+        if self.dels.contains(&key)
+            || self
+                .backend
+                .get(
+                    &self.snapshot,
+                    self.partition.as_ref().unwrap(),
+                    &ByteView::from(key.clone()),
+                )?
+                .is_none()
+        {
             return Ok(());
         }
 
-        self.meta.export(self.len(), height)?;
-
-        let mut wtx = self.keyspace.write_tx();
+        self.remove(key);
 
-        let partition = self.partition.as_ref().unwrap();
+        Ok(())
+    }
 
-        mem::take(&mut self.dels)
-            .into_iter()
-            .for_each(|key| wtx.remove(partition, ByteView::from(key)));
+    /// Use [`commit_all`] to commit several stores at once
+    pub fn commit(&mut self, height: Height) -> Result<()> {
+        let writes = self.take_writes(height);
 
-        mem::take(&mut self.puts)
-            .into_iter()
-            .for_each(|(key, value)| {
-                // if CHECK_COLLISISONS {
-                //     #[allow(unused_must_use)]
-                //     if let Ok(Some(value)) = wtx.get(&self.partition, key.as_bytes()) {
-                //         dbg!(
-                //             &key,
-                //             V::try_from(value.as_bytes().into()).unwrap(),
-                //             &self.meta,
-                //             self.rtx.get(&self.partition, key.as_bytes())
-                //         );
-                //         unreachable!();
-                //     }
-                // }
-                wtx.insert(partition, ByteView::from(key), ByteView::from(value))
-            });
+        if writes.is_empty() {
+            return Ok(());
+        }
 
-        wtx.commit()?;
+        self.backend.write(writes)?;
 
-        self.rtx = self.keyspace.read_tx();
+        self.refresh_snapshot();
 
         Ok(())
     }
 
     pub fn rotate_memtable(&self) {
-        let _ = self.partition.as_ref().unwrap().inner().rotate_memtable();
+        self.backend
+            .rotate_memtable(self.partition.as_ref().unwrap());
     }
 
     pub fn height(&self) -> Option<Height> {
         self.meta.height()
     }
 
+    pub fn has(&self, height: Height) -> bool {
+        self.meta.has(height)
+    }
+    pub fn needs(&self, height: Height) -> bool {
+        self.meta.needs(height)
+    }
+
+    pub fn reset_partition(&mut self) -> Result<()> {
+        let partition = self.partition.take().unwrap();
+
+        self.backend.delete_partition(partition)?;
+
+        self.backend
+            .write(vec![Self::meta_del(&self.meta_partition, &self.name)])?;
+
+        self.meta.reset();
+
+        let partition = Self::open_partition(&self.backend, &self.name, self.bloom_filter_bits)?;
+
+        self.partition.replace(partition);
+
+        self.snapshot = self.backend.snapshot();
+
+        Ok(())
+    }
+}
+
+impl<K, V, B> Store<K, V, B>
+where
+    K: Debug,
+    ByteView: From<K> + From<V>,
+    B: KvBackend,
+{
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn bloom_filter_bits(&self) -> Option<u8> {
+        self.bloom_filter_bits.unwrap_or(DEFAULT_BLOOM_FILTER_BITS)
+    }
+
+    pub fn stats(&self) -> Result<StoreStats> {
+        Ok(StoreStats::new(
+            &self.name,
+            self.meta.height(),
+            self.len(),
+            self.bloom_filter_bits(),
+            self.backend.stats(&self.partition()?),
+        ))
+    }
+
+    /// Merges all the segments of the store's partition, blocking and expensive
+    pub fn compact(&self) -> Result<()> {
+        self.backend.compact(&self.partition()?)
+    }
+
+    /// Clones don't hold the partition, it's reopened for them
+    fn partition(&self) -> Result<B::Partition> {
+        match self.partition.as_ref() {
+            Some(partition) => Ok(partition.clone()),
+            None => Self::open_partition(&self.backend, &self.name, self.bloom_filter_bits),
+        }
+    }
+
+    fn open_partition(
+        backend: &B,
+        name: &str,
+        bloom_filter_bits: Option<Option<u8>>,
+    ) -> Result<B::Partition> {
+        backend.open_partition(name, bloom_filter_bits.unwrap_or(DEFAULT_BLOOM_FILTER_BITS))
+    }
+
     pub fn len(&self) -> usize {
         let len = self.meta.len() + self.puts.len() - self.dels.len();
         if len > 18440000000000000000 {
@@ -227,62 +407,132 @@ where
         self.len() == 0
     }
 
-    pub fn has(&self, height: Height) -> bool {
-        self.meta.has(height)
-    }
-    pub fn needs(&self, height: Height) -> bool {
-        self.meta.needs(height)
-    }
+    /// Takes the pending puts and dels along with the metadata at `height`, empty if there's nothing to commit
+    ///
+    /// Applied by a [`KvBackend::write`], after which the snapshot needs to be refreshed to read them
+    pub fn take_writes(&mut self, height: Height) -> Vec<PartitionWrite<B::Partition>> {
+        // A height lower than the current one is a rollback, the metadata needs to be written even without changes
+        if self.meta.height() == Some(height) && self.puts.is_empty() && self.dels.is_empty() {
+            return vec![];
+        }
 
-    fn open_partition_handle(
-        keyspace: &TransactionalKeyspace,
-        name: &str,
-        bloom_filter_bits: Option<Option<u8>>,
-    ) -> Result<TransactionalPartitionHandle> {
-        keyspace
-            .open_partition(
-                name,
-                PartitionCreateOptions::default()
-                    .bloom_filter_bits(bloom_filter_bits.unwrap_or(DEFAULT_BLOOM_FILTER_BITS))
-                    .max_memtable_size(8 * 1024 * 1024)
-                    .manual_journal_persist(true),
-            )
-            .map_err(|e| e.into())
+        self.meta.export(self.len(), height);
+
+        vec![
+            PartitionWrite {
+                partition: self.partition.clone().unwrap(),
+                dels: mem::take(&mut self.dels)
+                    .into_iter()
+                    .map(ByteView::from)
+                    .collect(),
+                puts: mem::take(&mut self.puts)
+                    .into_iter()
+                    .map(|(key, value)| (ByteView::from(key), ByteView::from(value)))
+                    .collect(),
+            },
+            PartitionWrite {
+                partition: self.meta_partition.clone(),
+                dels: vec![],
+                puts: vec![(ByteView::new(self.name.as_bytes()), self.meta.to_bytes())],
+            },
+        ]
     }
 
-    pub fn reset_partition(&mut self) -> Result<()> {
-        let partition: TransactionalPartitionHandle = self.partition.take().unwrap();
-
-        self.keyspace.delete_partition(partition)?;
-
-        self.keyspace.persist(PersistMode::SyncAll)?;
-
-        self.meta.reset();
-
-        let partition =
-            Self::open_partition_handle(&self.keyspace, &self.name, self.bloom_filter_bits)?;
-
-        self.partition.replace(partition);
+    pub fn refresh_snapshot(&mut self) {
+        self.snapshot = self.backend.snapshot();
+    }
 
-        Ok(())
+    fn meta_del(meta_partition: &B::Partition, name: &str) -> PartitionWrite<B::Partition> {
+        PartitionWrite {
+            partition: meta_partition.clone(),
+            dels: vec![ByteView::new(name.as_bytes())],
+            puts: vec![],
+        }
     }
 }
 
-impl<Key, Value> Clone for Store<Key, Value>
+impl<Key, Value, B> Clone for Store<Key, Value, B>
 where
     Key: Clone,
     Value: Clone,
+    B: KvBackend,
 {
     fn clone(&self) -> Self {
         Self {
             meta: self.meta.clone(),
             name: self.name.clone(),
-            keyspace: self.keyspace.clone(),
+            backend: self.backend.clone(),
             partition: None,
-            rtx: self.keyspace.read_tx(),
+            meta_partition: self.meta_partition.clone(),
+            snapshot: self.backend.snapshot(),
             puts: self.puts.clone(),
             dels: self.dels.clone(),
             bloom_filter_bits: self.bloom_filter_bits,
         }
     }
 }
+
+/// Part of a store independent of its key and value types, to commit stores of different types together
+pub trait AnyStore<B>
+where
+    B: KvBackend,
+{
+    fn name(&self) -> &str;
+    fn height(&self) -> Option<Height>;
+    fn take_writes(&mut self, height: Height) -> Vec<PartitionWrite<B::Partition>>;
+    fn refresh_snapshot(&mut self);
+    fn stats(&self) -> Result<StoreStats>;
+    fn compact(&self) -> Result<()>;
+}
+
+impl<K, V, B> AnyStore<B> for Store<K, V, B>
+where
+    K: Debug,
+    ByteView: From<K> + From<V>,
+    B: KvBackend,
+{
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    fn height(&self) -> Option<Height> {
+        self.meta.height()
+    }
+
+    fn take_writes(&mut self, height: Height) -> Vec<PartitionWrite<B::Partition>> {
+        self.take_writes(height)
+    }
+
+    fn refresh_snapshot(&mut self) {
+        self.refresh_snapshot()
+    }
+
+    fn stats(&self) -> Result<StoreStats> {
+        self.stats()
+    }
+
+    fn compact(&self) -> Result<()> {
+        self.compact()
+    }
+}
+
+/// Commits the stores at `height` in a single write, along with their metadata, so a crash can't leave them at different heights
+pub fn commit_all<B>(backend: &B, stores: &mut [&mut dyn AnyStore<B>], height: Height) -> Result<()>
+where
+    B: KvBackend,
+{
+    let writes = stores
+        .iter_mut()
+        .flat_map(|store| store.take_writes(height))
+        .collect::<Vec<_>>();
+
+    if writes.is_empty() {
+        return Ok(());
+    }
+
+    backend.write(writes)?;
+
+    stores.iter_mut().for_each(|store| store.refresh_snapshot());
+
+    Ok(())
+}
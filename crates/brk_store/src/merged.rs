@@ -0,0 +1,95 @@
+use std::{cmp::Ordering, collections::BTreeSet, iter::Peekable, marker::PhantomData};
+
+use brk_core::{Result, Value};
+use byteview::ByteView;
+
+use crate::KvPair;
+
+/// Merges the committed entries of a store with its pending puts and dels, both sorted by their encoded keys in the
+/// same direction
+///
+/// Keys are compared as bytes since that's how the backend orders them, which only matches the order of the keys
+/// themselves if they're encoded big endian.
+/// Puts override committed entries with the same key and committed entries with a pending del are skipped
+pub struct Merged<'a, K, V, C, P>
+where
+    C: Iterator<Item = Result<KvPair>>,
+    P: Iterator<Item = (ByteView, &'a K, &'a V)>,
+    K: 'a,
+    V: 'a,
+{
+    committed: Peekable<C>,
+    puts: Peekable<P>,
+    dels: &'a BTreeSet<K>,
+    rev: bool,
+    value: PhantomData<V>,
+}
+
+impl<'a, K, V, C, P> Merged<'a, K, V, C, P>
+where
+    C: Iterator<Item = Result<KvPair>>,
+    P: Iterator<Item = (ByteView, &'a K, &'a V)>,
+{
+    pub fn new(committed: C, puts: P, dels: &'a BTreeSet<K>, rev: bool) -> Self {
+        Self {
+            committed: committed.peekable(),
+            puts: puts.peekable(),
+            dels,
+            rev,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V, C, P> Iterator for Merged<'a, K, V, C, P>
+where
+    K: Ord + Clone + From<ByteView>,
+    V: From<ByteView>,
+    C: Iterator<Item = Result<KvPair>>,
+    P: Iterator<Item = (ByteView, &'a K, &'a V)>,
+{
+    type Item = Result<(K, Value<'a, V>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Less means the put comes first in the direction of the iteration
+            let ordering = match (self.committed.peek(), self.puts.peek()) {
+                (None, None) => return None,
+                (Some(Err(_)), _) => {
+                    let Some(Err(e)) = self.committed.next() else {
+                        unreachable!()
+                    };
+                    return Some(Err(e));
+                }
+                (None, Some(_)) => Ordering::Less,
+                (Some(Ok(_)), None) => Ordering::Greater,
+                (Some(Ok((committed_key, _))), Some((put_key, ..))) => {
+                    let ordering = put_key[..].cmp(&committed_key[..]);
+                    if self.rev {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                }
+            };
+
+            if ordering == Ordering::Equal {
+                self.committed.next();
+            }
+
+            if ordering != Ordering::Greater {
+                let (_, key, value) = self.puts.next().unwrap();
+                return Some(Ok((key.clone(), Value::Ref(value))));
+            }
+
+            let (key, value) = self.committed.next().unwrap().unwrap();
+            let key = K::from(key);
+
+            if self.dels.contains(&key) {
+                continue;
+            }
+
+            return Some(Ok((key, Value::Owned(V::from(value)))));
+        }
+    }
+}
@@ -1,31 +1,38 @@
 use std::{
-    fs, io,
+    fs,
     path::{Path, PathBuf},
 };
 
 use brk_core::{Result, Version, copy_first_8bytes};
-use fjall::{TransactionalKeyspace, TransactionalPartitionHandle};
+use byteview::ByteView;
 
 use super::Height;
 
 #[derive(Debug, Clone)]
 pub struct StoreMeta {
-    pathbuf: PathBuf,
+    pathbuf: Option<PathBuf>,
     version: Version,
     height: Option<Height>,
     len: usize,
 }
 
 impl StoreMeta {
-    pub fn checked_open<F>(
-        keyspace: &TransactionalKeyspace,
-        path: &Path,
-        version: Version,
-        open_partition_handle: F,
-    ) -> Result<(Self, TransactionalPartitionHandle)>
-    where
-        F: Fn() -> Result<TransactionalPartitionHandle>,
-    {
+    /// Reads the metadata at `path` and whether its version is unchanged, if not the caller has to reset its partition
+    ///
+    /// Without a path, nothing is ever read from or written to disk and the store starts empty
+    pub fn checked_open(path: Option<&Path>, version: Version) -> Result<(Self, bool)> {
+        let Some(path) = path else {
+            return Ok((
+                Self {
+                    pathbuf: None,
+                    version,
+                    height: None,
+                    len: 0,
+                },
+                true,
+            ));
+        };
+
         fs::create_dir_all(path)?;
 
         let read_version = Version::try_from(Self::path_version_(path).as_path());
@@ -34,28 +41,22 @@ impl StoreMeta {
             .as_ref()
             .is_ok_and(|prev_version| &version == prev_version);
 
-        let mut partition = open_partition_handle()?;
-
         if !is_same_version {
             fs::remove_dir_all(path)?;
             fs::create_dir(path)?;
-            keyspace.delete_partition(partition)?;
-            keyspace.persist(fjall::PersistMode::SyncAll)?;
-            partition = open_partition_handle()?;
         }
 
-        let len = Self::read_length_(path);
-
+        // Heights and lengths used to be written next to the version, they're now committed with the data
         let slf = Self {
-            pathbuf: path.to_owned(),
+            pathbuf: Some(path.to_owned()),
             version,
             height: Height::try_from(Self::path_height_(path).as_path()).ok(),
-            len,
+            len: Self::read_length_(path),
         };
 
-        slf.version.write(&slf.path_version())?;
+        slf.version.write(&Self::path_version_(path))?;
 
-        Ok((slf, partition))
+        Ok((slf, is_same_version))
     }
 
     pub fn len(&self) -> usize {
@@ -70,11 +71,24 @@ impl StoreMeta {
     //     self.version
     // }
 
-    pub fn export(&mut self, len: usize, height: Height) -> io::Result<()> {
+    pub fn export(&mut self, len: usize, height: Height) {
         self.len = len;
-        self.write_length()?;
         self.height = Some(height);
-        height.write(&self.path_height())
+    }
+
+    /// Height and length, committed in the same write as the data of the store
+    pub fn to_bytes(&self) -> ByteView {
+        let height = u32::from(self.height.unwrap());
+        let mut bytes = height.to_be_bytes().to_vec();
+        bytes.extend((self.len as u64).to_be_bytes());
+        ByteView::new(&bytes)
+    }
+
+    pub fn restore(&mut self, bytes: &[u8]) {
+        self.height = Some(Height::new(u32::from_be_bytes(
+            bytes[..4].try_into().unwrap(),
+        )));
+        self.len = u64::from_be_bytes(copy_first_8bytes(&bytes[4..]).unwrap()) as usize;
     }
 
     pub fn reset(&mut self) {
@@ -82,13 +96,10 @@ impl StoreMeta {
         self.len = 0
     }
 
-    pub fn path(&self) -> &Path {
-        &self.pathbuf
+    pub fn path(&self) -> Option<&Path> {
+        self.pathbuf.as_deref()
     }
 
-    fn path_version(&self) -> PathBuf {
-        Self::path_version_(&self.pathbuf)
-    }
     fn path_version_(path: &Path) -> PathBuf {
         path.join("version")
     }
@@ -102,9 +113,6 @@ impl StoreMeta {
     pub fn has(&self, height: Height) -> bool {
         !self.needs(height)
     }
-    fn path_height(&self) -> PathBuf {
-        Self::path_height_(&self.pathbuf)
-    }
     fn path_height_(path: &Path) -> PathBuf {
         path.join("height")
     }
@@ -114,12 +122,6 @@ impl StoreMeta {
             .map(|v| usize::from_ne_bytes(copy_first_8bytes(v.as_slice()).unwrap()))
             .unwrap_or_default()
     }
-    fn write_length(&self) -> io::Result<()> {
-        Self::write_length_(&self.pathbuf, self.len)
-    }
-    fn write_length_(path: &Path, len: usize) -> io::Result<()> {
-        fs::write(Self::path_length(path), len.to_ne_bytes())
-    }
     fn path_length(path: &Path) -> PathBuf {
         path.join("length")
     }
@@ -0,0 +1,46 @@
+use std::f64::consts::LN_2;
+
+use brk_core::Height;
+use serde::Serialize;
+
+use crate::PartitionStats;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreStats {
+    pub name: String,
+    pub height: Option<Height>,
+    /// Exact, unlike the approximate length of the partition
+    pub len: usize,
+    pub bloom_filter_bits: Option<u8>,
+    /// Expected share of lookups of absent keys which still read a segment from disk
+    pub bloom_false_positive_rate: f64,
+    /// Disk bytes per key, grows with overwritten and removed keys until a compaction
+    pub bytes_per_key: f64,
+    #[serde(flatten)]
+    pub partition: PartitionStats,
+}
+
+impl StoreStats {
+    pub fn new(
+        name: &str,
+        height: Option<Height>,
+        len: usize,
+        bloom_filter_bits: Option<u8>,
+        partition: PartitionStats,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            height,
+            len,
+            bloom_filter_bits,
+            bloom_false_positive_rate: Self::bloom_false_positive_rate(bloom_filter_bits),
+            bytes_per_key: partition.disk_bytes as f64 / len.max(1) as f64,
+            partition,
+        }
+    }
+
+    /// With the optimal number of hash functions, `bits * ln(2)`, each one halving the rate
+    fn bloom_false_positive_rate(bits: Option<u8>) -> f64 {
+        bits.map_or(1.0, |bits| 0.5_f64.powf(bits as f64 * LN_2))
+    }
+}
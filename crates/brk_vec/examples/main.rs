@@ -1,7 +1,9 @@
 use std::{fs, path::Path};
 
 use brk_core::{DateIndex, Height, Version};
-use brk_vec::{AnyVec, CollectableVec, Format, GenericStoredVec, StoredVec, VecIterator};
+use brk_vec::{
+    AnyVec, CollectableVec, Format, GenericStoredVec, ImportMode, StoredVec, VecIterator,
+};
 
 #[allow(clippy::upper_case_acronyms)]
 type VEC = StoredVec<DateIndex, u32>;
@@ -10,10 +12,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _ = fs::remove_dir_all("./vec");
 
     let version = Version::TWO;
-    let format = Format::Compressed;
+    let mode = ImportMode::read_write(Format::Compressed);
 
     {
-        let mut vec: VEC = StoredVec::forced_import(Path::new("."), "vec", version, format)?;
+        let mut vec: VEC = StoredVec::forced_import(Path::new("."), "vec", version, mode)?;
 
         (0..21_u32).for_each(|v| {
             vec.push(v);
@@ -26,11 +28,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         vec.flush()?;
 
+        match vec.verify()? {
+            Some(index) => println!("Corrupted from index {index}"),
+            None => println!("Ok, {} values verified", vec.len()),
+        }
+
         // dbg!(vec.header());
     }
 
     {
-        let mut vec: VEC = StoredVec::forced_import(Path::new("."), "vec", version, format)?;
+        let mut vec: VEC = StoredVec::forced_import(Path::new("."), "vec", version, mode)?;
 
         vec.mut_header().update_height(Height::new(100));
 
@@ -56,7 +63,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     {
-        let mut vec: VEC = StoredVec::forced_import(Path::new("."), "vec", version, format)?;
+        let mut vec: VEC = StoredVec::forced_import(Path::new("."), "vec", version, mode)?;
         let mut iter = vec.into_iter();
 
         dbg!(iter.get(0.into()));
@@ -81,7 +88,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     {
-        let mut vec: VEC = StoredVec::forced_import(Path::new("."), "vec", version, format)?;
+        let mut vec: VEC = StoredVec::forced_import(Path::new("."), "vec", version, mode)?;
 
         vec.reset()?;
 
@@ -0,0 +1,442 @@
+use std::{
+    fs::{self, File},
+    ops::Add,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use brk_core::{
+    Bitcoin, BlockHash, Cents, Date, DateIndex, Dollars, Error, Feerate, Height, InputIndex,
+    OHLCCents, OHLCDollars, OHLCSats, OutputIndex, Printable, Result, Sats, StoredF32, StoredF64,
+    StoredU8, StoredU32, StoredU64, StoredUsize, Timestamp, TxIndex, Txid, Weight,
+};
+use brk_vec::{
+    AnyVec, CollectableVec, CompressedVec, Format, GenericStoredVec, Header, Mmap, RawVec,
+    StoredType, StoredVec, VecIterator,
+};
+use clap::Parser;
+use clap_derive::{Parser, Subcommand};
+use serde::Serialize;
+
+/// Inspect a vec stored on disk, without ever modifying it
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Directory of the vec, the one with a file named after its index (`height`, `dateindex`...)
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// Type of the values (`u64`, `Sats`, `Dollars`, `StoredF32`...), it isn't stored in the vec
+    #[arg(short, long, value_name = "TYPE")]
+    value: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Header, length, sizes, first and last values and range of the values
+    Info,
+    /// Metadata of every compressed page
+    Pages,
+    /// Values of a range of indexes, negative indexes start from the end
+    Dump {
+        #[arg(long, allow_negative_numbers = true)]
+        from: Option<i64>,
+        #[arg(long, allow_negative_numbers = true)]
+        to: Option<i64>,
+        /// Print `index,value` lines instead of `index: value`
+        #[arg(long)]
+        csv: bool,
+    },
+    /// Checks the checksums of the whole vec and prints the first corrupted index if any
+    Verify,
+    /// Compares the headers and the values of two vecs with the same index and value types
+    Diff {
+        #[arg(value_name = "OTHER_PATH")]
+        other: PathBuf,
+        /// Maximum number of different values printed
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+}
+
+static INDEX: OnceLock<&'static str> = OnceLock::new();
+
+/// Stands for the index type of the vec, named after its file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Index(usize);
+
+impl From<usize> for Index {
+    fn from(value: usize) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Index> for usize {
+    fn from(value: Index) -> Self {
+        value.0
+    }
+}
+
+impl Add<usize> for Index {
+    type Output = Self;
+    fn add(self, rhs: usize) -> Self::Output {
+        Self(self.0 + rhs)
+    }
+}
+
+impl Printable for Index {
+    fn to_string() -> &'static str {
+        INDEX.get().unwrap()
+    }
+
+    fn to_possible_strings() -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// Smallest and largest values of a vec, `None` for the value types which can't be ordered
+trait ValueRange: StoredType {
+    fn range(vec: &StoredVec<Index, Self>) -> Option<(Self, Self)>;
+}
+
+macro_rules! impl_value_range {
+    (@fold $vec:expr) => {
+        $vec.fold(None, |range, value| match range {
+            None => Some((value.clone(), value)),
+            Some((min, max)) => Some((
+                if value < min { value.clone() } else { min },
+                if value > max { value } else { max },
+            )),
+        })
+    };
+    (ordered: $($T:ident),+; floats: $($F:ident),+; unordered: $($U:ident),+ $(,)?) => {
+        $(impl ValueRange for $T {
+            fn range(vec: &StoredVec<Index, Self>) -> Option<(Self, Self)> {
+                impl_value_range!(@fold vec.into_iter().map(|(_, value)| value.into_inner()))
+            }
+        })+
+        $(impl ValueRange for $F {
+            /// NaNs are skipped, some types order them before every other value
+            fn range(vec: &StoredVec<Index, Self>) -> Option<(Self, Self)> {
+                impl_value_range!(@fold vec
+                    .into_iter()
+                    .map(|(_, value)| value.into_inner())
+                    .filter(|value| !f64::from(*value).is_nan()))
+            }
+        })+
+        $(impl ValueRange for $U {
+            fn range(_: &StoredVec<Index, Self>) -> Option<(Self, Self)> {
+                None
+            }
+        })+
+    };
+}
+
+impl_value_range!(
+    ordered: u8, u16, u32, u64, usize, Cents, Date, DateIndex, Height, InputIndex, OutputIndex,
+    Sats, StoredU8, StoredU32, StoredU64, StoredUsize, Timestamp, TxIndex, Weight;
+    floats: f32, f64, Bitcoin, Dollars, Feerate, StoredF32, StoredF64;
+    unordered: BlockHash, OHLCCents, OHLCDollars, OHLCSats, Txid,
+);
+
+macro_rules! run_with_value_type {
+    ($args:expr, $($T:ident),+ $(,)?) => {
+        match $args.value.as_str() {
+            $(stringify!($T) => run::<$T>($args),)+
+            _ => {
+                eprintln!("Supported value types: {}", [$(stringify!($T)),+].join(", "));
+                Err(Error::String("Unsupported value type"))
+            }
+        }
+    };
+}
+
+fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    INDEX.set(index_name(&args.path)?).unwrap();
+
+    run_with_value_type!(
+        &args,
+        u8,
+        u16,
+        u32,
+        u64,
+        usize,
+        f32,
+        f64,
+        Bitcoin,
+        BlockHash,
+        Cents,
+        Date,
+        DateIndex,
+        Dollars,
+        Feerate,
+        Height,
+        InputIndex,
+        OHLCCents,
+        OHLCDollars,
+        OHLCSats,
+        OutputIndex,
+        Sats,
+        StoredF32,
+        StoredF64,
+        StoredU8,
+        StoredU32,
+        StoredU64,
+        StoredUsize,
+        Timestamp,
+        TxIndex,
+        Txid,
+        Weight,
+    )?;
+
+    Ok(())
+}
+
+/// The only file of a vec directory without a `-` is the vec itself, named after its index
+fn index_name(path: &Path) -> Result<&'static str> {
+    fs::read_dir(path)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .find(|name| !name.contains('-'))
+        .map(|name| &*name.leak())
+        .ok_or(Error::String("No vec file found in the directory"))
+}
+
+fn open<T>(path: &Path) -> Result<StoredVec<Index, T>>
+where
+    T: StoredType,
+{
+    if index_name(path)? != Index::to_string() {
+        return Err(Error::String("Vecs have different index types"));
+    }
+
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or(Error::String("Invalid vec path"))?;
+
+    // The version is read first since the vec is opened without knowing it
+    let file = File::open(path.join(Index::to_string()))?;
+    let header = Header::read(&unsafe { Mmap::map(&file)? })?;
+
+    // Opened read-only as the vec may belong to a running instance
+    Ok(match header.format() {
+        Format::Raw => StoredVec::Raw(RawVec::import(parent, name, header.vec_version(), true)?),
+        Format::Compressed => StoredVec::Compressed(CompressedVec::import(
+            parent,
+            name,
+            header.vec_version(),
+            true,
+        )?),
+    })
+}
+
+fn to_string<T>(value: &T) -> String
+where
+    T: Serialize,
+{
+    serde_json::to_string(value).unwrap_or_else(|e| e.to_string())
+}
+
+fn to_csv_field(value: String) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn run<T>(args: &Args) -> Result<()>
+where
+    T: ValueRange,
+{
+    let vec = open::<T>(&args.path)?;
+
+    match &args.command {
+        Command::Info => info(&args.path, &args.value, &vec),
+        Command::Pages => pages(&vec),
+        Command::Dump { from, to, csv } => dump(&vec, *from, *to, *csv),
+        Command::Verify => verify(&vec),
+        Command::Diff { other, limit } => diff(&vec, &open::<T>(other)?, *limit),
+    }
+}
+
+fn info<T>(path: &Path, value: &str, vec: &StoredVec<Index, T>) -> Result<()>
+where
+    T: ValueRange,
+{
+    let header = vec.header();
+    let len = vec.len();
+
+    println!("path: {}", path.display());
+    println!("index: {}", Index::to_string());
+    println!("value: {value} ({} bytes)", size_of::<T>());
+    println!("format: {:?}", header.format());
+    println!("vec version: {:?}", header.vec_version());
+    println!("computed version: {:?}", header.computed_version());
+    println!("height: {}", header.height());
+    println!("length: {len}");
+    println!("file size: {} bytes", fs::metadata(vec.path())?.len());
+
+    if let StoredVec::Compressed(vec) = vec {
+        let pages_meta = vec.pages_meta();
+        let compressed = (0..pages_meta.len())
+            .map(|i| pages_meta.get(i).unwrap().bytes_len as usize)
+            .sum::<usize>();
+        println!("pages: {}", pages_meta.len());
+        println!(
+            "compressed: {compressed} bytes for {} bytes of values ({:.2}%)",
+            len * size_of::<T>(),
+            compressed as f64 / (len * size_of::<T>()).max(1) as f64 * 100.0
+        );
+    }
+
+    if len > 0 {
+        let mut iter = vec.into_iter();
+        let first = iter.get_(0).map(|v| to_string(&*v)).unwrap_or_default();
+        let last = iter
+            .get_(len - 1)
+            .map(|v| to_string(&*v))
+            .unwrap_or_default();
+        println!("first: {first}");
+        println!("last: {last}");
+    }
+
+    if let Some((min, max)) = T::range(vec) {
+        println!("min: {}", to_string(&min));
+        println!("max: {}", to_string(&max));
+    }
+
+    Ok(())
+}
+
+fn pages<T>(vec: &StoredVec<Index, T>) -> Result<()>
+where
+    T: StoredType,
+{
+    let StoredVec::Compressed(vec) = vec else {
+        println!("Raw vecs aren't paged");
+        return Ok(());
+    };
+
+    let pages_meta = vec.pages_meta();
+
+    println!("page,start,bytes_len,values_len,checksum,values_checksum");
+    (0..pages_meta.len()).for_each(|i| {
+        let page = pages_meta.get(i).unwrap();
+        println!(
+            "{i},{},{},{},{:08x},{:08x}",
+            page.start, page.bytes_len, page.values_len, page.checksum, page.values_checksum
+        );
+    });
+
+    Ok(())
+}
+
+fn dump<T>(vec: &StoredVec<Index, T>, from: Option<i64>, to: Option<i64>, csv: bool) -> Result<()>
+where
+    T: StoredType,
+{
+    let len = vec.len();
+    let from = from.map_or(0, |i| StoredVec::<Index, T>::i64_to_usize(i, len));
+    let to = to.map_or(len, |i| StoredVec::<Index, T>::i64_to_usize(i, len));
+
+    if csv {
+        println!("{},value", Index::to_string());
+    }
+
+    let mut iter = vec.into_iter();
+    (from..to).for_each(|i| {
+        let Some(value) = iter.get_(i) else {
+            return;
+        };
+        let value = to_string(&*value);
+        if csv {
+            println!("{i},{}", to_csv_field(value));
+        } else {
+            println!("{i}: {value}");
+        }
+    });
+
+    Ok(())
+}
+
+fn verify<T>(vec: &StoredVec<Index, T>) -> Result<()>
+where
+    T: StoredType,
+{
+    match vec.verify()? {
+        Some(index) => Err(Error::ChecksumMismatch { index }),
+        None => {
+            println!("Ok, {} values verified", vec.len());
+            Ok(())
+        }
+    }
+}
+
+fn diff<T>(vec: &StoredVec<Index, T>, other: &StoredVec<Index, T>, limit: usize) -> Result<()>
+where
+    T: StoredType,
+{
+    let (header, other_header) = (vec.header(), other.header());
+
+    if header.format() != other_header.format() {
+        println!(
+            "format: {:?} != {:?}",
+            header.format(),
+            other_header.format()
+        );
+    }
+    if header.vec_version() != other_header.vec_version() {
+        println!(
+            "vec version: {:?} != {:?}",
+            header.vec_version(),
+            other_header.vec_version()
+        );
+    }
+    if header.computed_version() != other_header.computed_version() {
+        println!(
+            "computed version: {:?} != {:?}",
+            header.computed_version(),
+            other_header.computed_version()
+        );
+    }
+    if header.height() != other_header.height() {
+        println!("height: {} != {}", header.height(), other_header.height());
+    }
+    if vec.len() != other.len() {
+        println!("length: {} != {}", vec.len(), other.len());
+    }
+
+    let mut other_iter = other.into_iter();
+
+    let mut count = 0;
+    vec.into_iter()
+        .take(other.len())
+        .for_each(|(index, value)| {
+            let other_value = other_iter.unwrap_get_inner(index);
+            if (*value).as_bytes() == other_value.as_bytes() {
+                return;
+            }
+            if count < limit {
+                println!(
+                    "{}: {} != {}",
+                    index.0,
+                    to_string(&*value),
+                    to_string(&other_value)
+                );
+            }
+            count += 1;
+        });
+
+    println!("{count} different values");
+
+    Ok(())
+}
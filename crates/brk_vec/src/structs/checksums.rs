@@ -0,0 +1,92 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use brk_core::Result;
+use zerocopy::{FromBytes, IntoBytes};
+
+#[derive(Debug, Clone)]
+pub struct Checksums {
+    vec: Vec<u32>,
+    change_at: Option<usize>,
+    path: PathBuf,
+}
+
+impl Checksums {
+    const SIZE: usize = size_of::<u32>();
+
+    pub fn read(path: &Path) -> Result<Checksums> {
+        let this = Self {
+            vec: fs::read(path)
+                .unwrap_or_default()
+                // A partially written trailing checksum is dropped, the chunk will be reported by verify
+                .chunks_exact(Self::SIZE)
+                .map(|bytes| u32::read_from_bytes(bytes).unwrap())
+                .collect::<Vec<_>>(),
+            path: path.to_owned(),
+            change_at: None,
+        };
+
+        Ok(this)
+    }
+
+    pub fn write(&mut self) -> io::Result<()> {
+        if self.change_at.is_none() {
+            return Ok(());
+        }
+
+        let change_at = self.change_at.take().unwrap();
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .create(true)
+            .truncate(false)
+            .append(true)
+            .open(&self.path)?;
+
+        file.set_len((change_at * Self::SIZE) as u64)?;
+        file.seek(SeekFrom::End(0))?;
+
+        file.write_all(self.vec[change_at..].as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, chunk_index: usize) -> Option<&u32> {
+        self.vec.get(chunk_index)
+    }
+
+    pub fn push(&mut self, chunk_index: usize, checksum: u32) {
+        if chunk_index != self.vec.len() {
+            panic!();
+        }
+
+        self.set_changed_at(chunk_index);
+
+        self.vec.push(checksum);
+    }
+
+    fn set_changed_at(&mut self, chunk_index: usize) {
+        if self.change_at.is_none_or(|ci| ci > chunk_index) {
+            self.change_at.replace(chunk_index);
+        }
+    }
+
+    pub fn truncate(&mut self, chunk_index: usize) {
+        if chunk_index >= self.vec.len() {
+            return;
+        }
+        self.vec.truncate(chunk_index);
+        self.set_changed_at(chunk_index);
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use brk_core::{Height, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::AnyVec;
+
+/// Lengths of a group of vecs which were all flushed and synced to disk at a given height
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit {
+    pub generation: u64,
+    pub height: Height,
+    pub lens: BTreeMap<String, usize>,
+}
+
+impl Commit {
+    pub fn key<V>(vec: &V) -> String
+    where
+        V: AnyVec + ?Sized,
+    {
+        format!("{}_to_{}", vec.index_type_to_string(), vec.name())
+    }
+
+    pub fn len<V>(&self, vec: &V) -> Option<usize>
+    where
+        V: AnyVec + ?Sized,
+    {
+        self.lens.get(&Self::key(vec)).copied()
+    }
+}
+
+/// Write-ahead commit record of a group of vecs
+///
+/// Vecs are flushed and synced first and only then is the new generation written, atomically by renaming a temporary file.
+/// After a crash, any vec longer than its length in the last commit was only partially flushed and needs to be truncated back to it.
+#[derive(Debug, Clone)]
+pub struct CommitLog {
+    path: PathBuf,
+    last: Option<Commit>,
+}
+
+impl CommitLog {
+    pub fn import(path: &Path) -> Result<Self> {
+        let last = match fs::read(path) {
+            Ok(bytes) => Some(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            path: path.to_owned(),
+            last,
+        })
+    }
+
+    pub fn last(&self) -> Option<&Commit> {
+        self.last.as_ref()
+    }
+
+    /// Records a new generation, every vec in `vecs` must have been flushed and synced before
+    pub fn commit<'a, V>(
+        &mut self,
+        height: Height,
+        vecs: impl IntoIterator<Item = &'a V>,
+    ) -> Result<()>
+    where
+        V: AnyVec + ?Sized + 'a,
+    {
+        let commit = Commit {
+            generation: self.last.as_ref().map_or(0, |last| last.generation + 1),
+            height,
+            lens: vecs
+                .into_iter()
+                .map(|vec| (Commit::key(vec), vec.len()))
+                .collect(),
+        };
+
+        let tmp_path = self.path.with_extension("tmp");
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&serde_json::to_vec(&commit)?)?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&tmp_path, &self.path)?;
+
+        if let Some(parent) = self.path.parent() {
+            File::open(parent)?.sync_all()?;
+        }
+
+        self.last.replace(commit);
+
+        Ok(())
+    }
+}
@@ -1,18 +1,31 @@
 use zerocopy_derive::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
-#[derive(Debug, Clone, IntoBytes, Immutable, FromBytes, KnownLayout)]
+#[derive(Debug, Clone, PartialEq, Eq, IntoBytes, Immutable, FromBytes, KnownLayout)]
+#[repr(C)]
 pub struct CompressedPageMetadata {
     pub start: u64,
     pub bytes_len: u32,
     pub values_len: u32,
+    /// Checksum of the compressed bytes, verified before every decode
+    pub checksum: u32,
+    /// Checksum of the decoded bytes, verified on demand
+    pub values_checksum: u32,
 }
 
 impl CompressedPageMetadata {
-    pub fn new(start: u64, bytes_len: u32, values_len: u32) -> Self {
+    pub fn new(
+        start: u64,
+        bytes_len: u32,
+        values_len: u32,
+        checksum: u32,
+        values_checksum: u32,
+    ) -> Self {
         Self {
             start,
             bytes_len,
             values_len,
+            checksum,
+            values_checksum,
         }
     }
 }
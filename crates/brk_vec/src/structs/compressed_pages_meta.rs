@@ -1,6 +1,7 @@
 use std::{
     fs::{self, OpenOptions},
-    io::{self, Seek, SeekFrom, Write},
+    io,
+    os::unix::fs::FileExt,
     path::{Path, PathBuf},
 };
 
@@ -24,13 +25,9 @@ impl CompressedPagesMetadata {
         let this = Self {
             vec: fs::read(path)
                 .unwrap_or_default()
-                .chunks(Self::PAGE_SIZE)
-                .map(|bytes| {
-                    if bytes.len() != Self::PAGE_SIZE {
-                        panic!()
-                    }
-                    CompressedPageMetadata::try_read_from_bytes(bytes).unwrap()
-                })
+                // A partially written trailing page is dropped, its data can't be trusted anyway
+                .chunks_exact(Self::PAGE_SIZE)
+                .map(|bytes| CompressedPageMetadata::try_read_from_bytes(bytes).unwrap())
                 .collect::<Vec<_>>(),
             path: path.to_owned(),
             change_at: None,
@@ -57,17 +54,16 @@ impl CompressedPagesMetadata {
             .enumerate()
             .for_each(|(i, v)| unsafe_bytes.copy_slice(i * Self::PAGE_SIZE, v.as_bytes()));
 
-        let mut file = OpenOptions::new()
+        let file = OpenOptions::new()
             .read(true)
+            .write(true)
             .create(true)
             .truncate(false)
-            .append(true)
             .open(&self.path)?;
 
-        file.set_len((change_at * Self::PAGE_SIZE) as u64)?;
-        file.seek(SeekFrom::End(0))?;
-
-        file.write_all(&bytes)?;
+        // Overwritten in place and only then cut, so a crash in between leaves every entry pointing to written pages
+        file.write_all_at(&bytes, (change_at * Self::PAGE_SIZE) as u64)?;
+        file.set_len((self.vec.len() * Self::PAGE_SIZE) as u64)?;
 
         Ok(())
     }
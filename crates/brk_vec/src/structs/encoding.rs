@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// How the bytes of a variable length value are served
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Encoding {
+    #[default]
+    Hex,
+    String,
+}
+
+impl Encoding {
+    pub fn is_hex(&self) -> bool {
+        *self == Self::Hex
+    }
+
+    pub fn is_string(&self) -> bool {
+        *self == Self::String
+    }
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Self::Hex => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+            Self::String => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Counter bumped every time the stored data of a vec may have changed, shared by its clones
+///
+/// Values are taken from a global counter so they never repeat across vecs, which makes the max over the sources of
+/// a lazy vec enough to know whether any of them changed, even if their lengths are the same.
+#[derive(Debug, Clone)]
+pub struct Generation(Arc<AtomicU64>);
+
+impl Generation {
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Acquire)
+    }
+
+    pub fn bump(&self) {
+        self.0.store(Self::next(), Ordering::Release);
+    }
+
+    fn next() -> u64 {
+        NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl Default for Generation {
+    fn default() -> Self {
+        Self(Arc::new(AtomicU64::new(Self::next())))
+    }
+}
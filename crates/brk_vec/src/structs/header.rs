@@ -1,7 +1,8 @@
 use std::{
-    fs::File,
-    io::{self, Seek, SeekFrom},
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom, Write},
     os::unix::fs::FileExt,
+    path::Path,
     sync::Arc,
 };
 
@@ -13,7 +14,7 @@ use zerocopy_derive::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
 use crate::Format;
 
-const HEADER_VERSION: Version = Version::ONE;
+const HEADER_VERSION: Version = Version::TWO;
 pub const HEADER_OFFSET: usize = size_of::<HeaderInner>();
 
 #[derive(Debug, Clone)]
@@ -39,6 +40,70 @@ impl Header {
         })
     }
 
+    /// Reads the header without verifying the version and the format of the vec, for inspection only
+    pub fn read(mmap: &Mmap) -> Result<Self> {
+        let inner = HeaderInner::read(mmap)?;
+        Ok(Self {
+            inner: Arc::new(ArcSwap::from_pointee(inner)),
+            modified: false,
+        })
+    }
+
+    /// Rewrites a raw vec created before its length was kept in the header with the current header, the values
+    /// are copied to a new file which replaces the old one so that a crash in between loses nothing
+    pub fn upgrade_raw_if_needed(path: &Path, size_of_t: usize) -> Result<()> {
+        let Ok(mut file) = File::open(path) else {
+            return Ok(());
+        };
+
+        let mut bytes = [0; size_of::<HeaderInnerV1>()];
+        if file.read_exact(&mut bytes).is_err() {
+            return Ok(());
+        }
+
+        let old = HeaderInnerV1::read_from_bytes(&bytes)?;
+        if old.header_version != Version::ONE || !old.compressed.is_false() {
+            return Ok(());
+        }
+
+        let stored_len = (file.metadata()?.len() as usize - bytes.len()) / size_of_t;
+
+        let header = HeaderInner {
+            header_version: HEADER_VERSION,
+            vec_version: old.vec_version,
+            computed_version: old.computed_version,
+            height: old.height,
+            compressed: old.compressed,
+            stored_len: stored_len as u64,
+        };
+
+        let upgraded_path = path.with_extension("upgrade");
+        let mut upgraded = File::create(&upgraded_path)?;
+        upgraded.write_all(header.as_bytes())?;
+        io::copy(
+            &mut file.take((stored_len * size_of_t) as u64),
+            &mut upgraded,
+        )?;
+        upgraded.sync_all()?;
+
+        fs::rename(upgraded_path, path)?;
+
+        Ok(())
+    }
+
+    /// Reloads the header from a more recent mmap, returns whether it changed
+    pub fn reload(&self, mmap: &Mmap) -> Result<bool> {
+        if mmap.len() < HEADER_OFFSET {
+            return Err(Error::WrongLength);
+        }
+        let header = HeaderInner::read_from_bytes(&mmap[..HEADER_OFFSET])?;
+        if header.as_bytes() == self.inner.load().as_bytes() {
+            return Ok(false);
+        }
+        self.inner.store(Arc::new(header));
+        Ok(true)
+    }
+
     pub fn update_height(&mut self, height: Height) {
         self.modified = true;
         self.inner.rcu(|header| {
@@ -57,6 +122,15 @@ impl Header {
         });
     }
 
+    pub fn update_stored_len(&mut self, len: usize) {
+        self.modified = true;
+        self.inner.rcu(|header| {
+            let mut header = (**header).clone();
+            header.stored_len = len as u64;
+            header
+        });
+    }
+
     pub fn modified(&self) -> bool {
         self.modified
     }
@@ -73,6 +147,18 @@ impl Header {
         self.inner.load().height
     }
 
+    pub fn stored_len(&self) -> usize {
+        self.inner.load().stored_len as usize
+    }
+
+    pub fn format(&self) -> Format {
+        if self.inner.load().compressed.is_true() {
+            Format::Compressed
+        } else {
+            Format::Raw
+        }
+    }
+
     pub fn write(&mut self, file: &mut File) -> io::Result<()> {
         self.inner.load().write(file)?;
         self.modified = false;
@@ -88,6 +174,19 @@ struct HeaderInner {
     pub computed_version: Version,
     pub height: Height,
     pub compressed: ZeroCopyBool,
+    /// Number of values stored by a raw vec, its file is never shrunk so it can be longer
+    pub stored_len: u64,
+}
+
+/// Header of the vecs created before it had the length of raw vecs
+#[repr(C)]
+#[derive(Debug, Clone, FromBytes, Immutable, KnownLayout)]
+struct HeaderInnerV1 {
+    pub header_version: Version,
+    pub vec_version: Version,
+    pub computed_version: Version,
+    pub height: Height,
+    pub compressed: ZeroCopyBool,
 }
 
 impl HeaderInner {
@@ -98,6 +197,7 @@ impl HeaderInner {
             computed_version: Version::default(),
             height: Height::default(),
             compressed: ZeroCopyBool::from(format),
+            stored_len: 0,
         };
         header.write(file)?;
         // dbg!(file.bytes().map(|b| b.unwrap()).collect::<Vec<_>>());
@@ -109,7 +209,7 @@ impl HeaderInner {
         file.write_all_at(self.as_bytes(), 0)
     }
 
-    pub fn import_and_verify(mmap: &Mmap, vec_version: Version, format: Format) -> Result<Self> {
+    pub fn read(mmap: &Mmap) -> Result<Self> {
         if mmap.len() < HEADER_OFFSET {
             return Err(Error::WrongLength);
         }
@@ -122,15 +222,20 @@ impl HeaderInner {
                 expected: HEADER_VERSION,
             });
         }
+        if header.compressed.is_broken() {
+            return Err(Error::WrongEndian);
+        }
+        Ok(header)
+    }
+
+    pub fn import_and_verify(mmap: &Mmap, vec_version: Version, format: Format) -> Result<Self> {
+        let header = Self::read(mmap)?;
         if header.vec_version != vec_version {
             return Err(Error::DifferentVersion {
                 found: header.vec_version,
                 expected: vec_version,
             });
         }
-        if header.compressed.is_broken() {
-            return Err(Error::WrongEndian);
-        }
         if (header.compressed.is_true() && format.is_raw())
             || (header.compressed.is_false() && format.is_compressed())
         {
@@ -0,0 +1,33 @@
+use super::Format;
+
+/// How vecs are imported, given to every one of them
+///
+/// Read-only vecs never create, reset or write files and can follow the writes of another process with `refresh`,
+/// which is how separate processes can read the data of a running instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportMode {
+    pub format: Format,
+    pub read_only: bool,
+}
+
+impl ImportMode {
+    pub fn read_write(format: Format) -> Self {
+        Self {
+            format,
+            read_only: false,
+        }
+    }
+
+    pub fn read_only(format: Format) -> Self {
+        Self {
+            format,
+            read_only: true,
+        }
+    }
+}
+
+impl From<Format> for ImportMode {
+    fn from(format: Format) -> Self {
+        Self::read_write(format)
+    }
+}
@@ -1,13 +1,33 @@
+mod checksums;
+mod commit_log;
 mod compressed_page_meta;
 mod compressed_pages_meta;
+mod encoding;
 mod format;
+mod generation;
 mod header;
+mod import_mode;
+mod order_statistic_tree;
+mod page_cache;
+mod page_stats;
+mod predicate;
+mod rolling_moments;
 // mod length;
 mod unsafe_slice;
 
+pub use checksums::*;
+pub use commit_log::*;
 pub use compressed_page_meta::*;
 pub use compressed_pages_meta::*;
+pub use encoding::*;
 pub use format::*;
+pub use generation::*;
 pub use header::*;
+pub use import_mode::*;
+pub use order_statistic_tree::*;
+pub use page_cache::*;
+pub use page_stats::*;
+pub use predicate::*;
+pub use rolling_moments::*;
 // pub use length::*;
 pub use unsafe_slice::*;
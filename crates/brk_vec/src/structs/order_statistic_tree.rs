@@ -0,0 +1,105 @@
+/// An order statistic tree, as a Fenwick tree over the ranks of a set of values known in advance
+///
+/// Insertions, removals and k-th smallest lookups are all `O(log n)`, which makes it a good fit for rolling percentiles.
+#[derive(Debug, Clone)]
+pub struct OrderStatisticTree {
+    values: Vec<f32>,
+    tree: Vec<usize>,
+    len: usize,
+}
+
+impl OrderStatisticTree {
+    /// Values that will be inserted later on, NaNs are ignored
+    pub fn new(mut values: Vec<f32>) -> Self {
+        values.retain(|v| !v.is_nan());
+        values.sort_unstable_by(f32::total_cmp);
+        values.dedup_by(|a, b| a.total_cmp(b).is_eq());
+        let tree = vec![0; values.len() + 1];
+        Self {
+            values,
+            tree,
+            len: 0,
+        }
+    }
+
+    fn rank(&self, value: f32) -> usize {
+        self.values
+            .binary_search_by(|v| v.total_cmp(&value))
+            .unwrap_or_else(|_| panic!("{value} wasn't given to the tree"))
+    }
+
+    fn update(&mut self, rank: usize, insert: bool) {
+        let mut i = rank + 1;
+        while i < self.tree.len() {
+            if insert {
+                self.tree[i] += 1;
+            } else {
+                self.tree[i] -= 1;
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    pub fn insert(&mut self, value: f32) {
+        if value.is_nan() {
+            return;
+        }
+        self.update(self.rank(value), true);
+        self.len += 1;
+    }
+
+    pub fn remove(&mut self, value: f32) {
+        if value.is_nan() {
+            return;
+        }
+        self.update(self.rank(value), false);
+        self.len -= 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the k-th smallest value, starting at 0
+    pub fn get(&self, k: usize) -> Option<f32> {
+        if k >= self.len {
+            return None;
+        }
+
+        let mut pos = 0;
+        let mut remaining = k + 1;
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+
+        while step > 0 {
+            let next = pos + step;
+            if next < self.tree.len() && self.tree[next] < remaining {
+                pos = next;
+                remaining -= self.tree[next];
+            }
+            step >>= 1;
+        }
+
+        Some(self.values[pos])
+    }
+
+    /// Same interpolation as the one used for the expanding percentiles
+    pub fn percentile(&self, percentile: f64) -> f32 {
+        if self.is_empty() {
+            return f32::NAN;
+        }
+
+        let index = (self.len - 1) as f64 * percentile;
+
+        if index.fract() != 0.0 {
+            let left = self.get(index as usize).unwrap();
+            let right = self.get(index.ceil() as usize).unwrap();
+            left / 2.0 + right / 2.0
+        } else {
+            self.get(index as usize).unwrap()
+        }
+    }
+}
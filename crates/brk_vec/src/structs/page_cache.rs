@@ -0,0 +1,241 @@
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use brk_core::Version;
+use zerocopy::IntoBytes;
+
+use crate::{MAX_PAGE_SIZE, StoredType};
+
+static PAGE_CACHE: PageCache = PageCache::new();
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Lengths, versions and generations of the sources of a lazy vec, its cached pages are only valid for the ones they
+/// were computed with
+///
+/// The generation catches the sources rewritten without changing their length, like after a truncate and a push.
+pub type PageCacheStamp = Vec<(usize, Version, u64)>;
+
+type PageKey = (usize, usize);
+
+/// Bounded LRU cache of the pages computed by lazy vecs, shared by all of them
+///
+/// Pages are stored as bytes, with the same layout as a raw vec, and are keyed by the id of the lazy vec and the page index.
+/// Disabled (a max size of 0) by default.
+#[derive(Debug)]
+pub struct PageCache(Mutex<PageCacheInner>);
+
+#[derive(Debug)]
+struct PageCacheInner {
+    max_size: usize,
+    size: usize,
+    tick: u64,
+    stamps: BTreeMap<usize, PageCacheStamp>,
+    pages: BTreeMap<PageKey, (u64, Arc<[u8]>)>,
+    lru: BTreeMap<u64, PageKey>,
+}
+
+impl PageCache {
+    const fn new() -> Self {
+        Self(Mutex::new(PageCacheInner {
+            max_size: 0,
+            size: 0,
+            tick: 0,
+            stamps: BTreeMap::new(),
+            pages: BTreeMap::new(),
+            lru: BTreeMap::new(),
+        }))
+    }
+
+    pub fn global() -> &'static Self {
+        &PAGE_CACHE
+    }
+
+    pub(crate) fn next_id() -> usize {
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Sets the total memory in bytes that the cached pages can use, 0 disables the cache
+    pub fn set_max_size(&self, max_size: usize) {
+        let mut inner = self.0.lock().unwrap();
+        inner.max_size = max_size;
+        inner.evict(0);
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.0.lock().unwrap().max_size
+    }
+
+    /// Memory in bytes currently used by the cached pages
+    pub fn size(&self) -> usize {
+        self.0.lock().unwrap().size
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_size() != 0
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.0.lock().unwrap();
+        inner.stamps.clear();
+        inner.pages.clear();
+        inner.lru.clear();
+        inner.size = 0;
+    }
+
+    fn get(&self, id: usize, page_index: usize, stamp: &PageCacheStamp) -> Option<Arc<[u8]>> {
+        let mut inner = self.0.lock().unwrap();
+
+        if inner.stamps.get(&id).is_none_or(|s| s != stamp) {
+            inner.invalidate(id);
+            return None;
+        }
+
+        let tick = inner.next_tick();
+        let (last_used, bytes) = inner.pages.get_mut(&(id, page_index))?;
+        let prev = *last_used;
+        *last_used = tick;
+        let bytes = bytes.clone();
+
+        inner.lru.remove(&prev);
+        inner.lru.insert(tick, (id, page_index));
+
+        Some(bytes)
+    }
+
+    fn insert(&self, id: usize, page_index: usize, stamp: &PageCacheStamp, bytes: Arc<[u8]>) {
+        let mut inner = self.0.lock().unwrap();
+
+        if bytes.len() > inner.max_size {
+            return;
+        }
+
+        if inner.stamps.get(&id).is_none_or(|s| s != stamp) {
+            inner.invalidate(id);
+            inner.stamps.insert(id, stamp.clone());
+        }
+
+        inner.remove((id, page_index));
+        inner.evict(bytes.len());
+
+        let tick = inner.next_tick();
+        inner.size += bytes.len();
+        inner.lru.insert(tick, (id, page_index));
+        inner.pages.insert((id, page_index), (tick, bytes));
+    }
+}
+
+impl PageCacheInner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, key: PageKey) {
+        if let Some((last_used, bytes)) = self.pages.remove(&key) {
+            self.lru.remove(&last_used);
+            self.size -= bytes.len();
+        }
+    }
+
+    /// Removes all the pages of a lazy vec, called when its sources changed
+    fn invalidate(&mut self, id: usize) {
+        self.stamps.remove(&id);
+
+        let keys = self
+            .pages
+            .range((id, 0)..=(id, usize::MAX))
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        keys.into_iter().for_each(|key| self.remove(key));
+    }
+
+    /// Evicts the least recently used pages until `additional` bytes can fit
+    fn evict(&mut self, additional: usize) {
+        while self.size + additional > self.max_size {
+            let Some((_, key)) = self.lru.first_key_value() else {
+                break;
+            };
+            self.remove(*key);
+        }
+    }
+}
+
+/// Page of a lazy vec kept by its iterator, to avoid going through the global cache for every value
+#[derive(Debug)]
+pub struct PageCacheReader<T> {
+    id: usize,
+    stamp: PageCacheStamp,
+    page: Option<(usize, Arc<[u8]>)>,
+    phantom: PhantomData<T>,
+}
+
+impl<T> PageCacheReader<T>
+where
+    T: StoredType,
+{
+    const SIZE_OF_T: usize = size_of::<T>();
+    const PER_PAGE: usize = MAX_PAGE_SIZE
+        / if Self::SIZE_OF_T == 0 {
+            1
+        } else {
+            Self::SIZE_OF_T
+        };
+
+    pub fn new(id: usize, stamp: PageCacheStamp) -> Self {
+        Self {
+            id,
+            stamp,
+            page: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the value at `index` from its cached page, computing the whole page with `compute` on a miss.
+    /// Falls back to `compute` alone when the cache is disabled.
+    pub fn get(
+        &mut self,
+        index: usize,
+        len: usize,
+        mut compute: impl FnMut(usize) -> Option<T>,
+    ) -> Option<T> {
+        let cache = PageCache::global();
+
+        if !cache.is_enabled() || Self::SIZE_OF_T == 0 {
+            return compute(index);
+        }
+
+        let page_index = index / Self::PER_PAGE;
+
+        if self.page.as_ref().is_none_or(|(i, _)| *i != page_index) {
+            let bytes = cache
+                .get(self.id, page_index, &self.stamp)
+                .unwrap_or_else(|| {
+                    let start = page_index * Self::PER_PAGE;
+                    let end = (start + Self::PER_PAGE).min(len);
+
+                    let values = (start..end).map_while(&mut compute).collect::<Vec<_>>();
+                    let bytes: Arc<[u8]> = Arc::from(values.as_bytes());
+
+                    cache.insert(self.id, page_index, &self.stamp, bytes.clone());
+
+                    bytes
+                });
+
+            self.page.replace((page_index, bytes));
+        }
+
+        let (_, bytes) = self.page.as_ref().unwrap();
+        let start = (index % Self::PER_PAGE) * Self::SIZE_OF_T;
+
+        bytes
+            .get(start..start + Self::SIZE_OF_T)
+            .map(|slice| T::try_read_from_bytes(slice).unwrap())
+    }
+}
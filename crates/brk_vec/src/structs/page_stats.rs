@@ -0,0 +1,186 @@
+use std::{
+    cmp::Ordering,
+    fs::{self, OpenOptions},
+    io::{self, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use brk_core::Result;
+use zerocopy::{FromBytes, IntoBytes};
+
+use crate::{MAX_PAGE_SIZE, StoredType};
+
+/// Statistics of the values of a page (compressed vecs) or a chunk (raw vecs)
+///
+/// `count` is the number of comparable values, those which aren't are ignored (NaNs), `min` and `max` are meaningless if it's 0
+#[derive(Debug, Clone)]
+pub struct PageStat<T> {
+    pub min: T,
+    pub max: T,
+    pub len: u32,
+    pub count: u32,
+}
+
+impl<T> PageStat<T>
+where
+    T: StoredType,
+{
+    pub fn compute(values: &[T], cmp: fn(&T, &T) -> Option<Ordering>) -> Option<Self> {
+        let first = values.first()?;
+
+        let mut min = first;
+        let mut max = first;
+        let mut count = 0;
+
+        values.iter().for_each(|v| {
+            // NaN-like values aren't equal to themselves
+            if cmp(v, v).is_none() {
+                return;
+            }
+            if count == 0 || cmp(v, min) == Some(Ordering::Less) {
+                min = v;
+            }
+            if count == 0 || cmp(v, max) == Some(Ordering::Greater) {
+                max = v;
+            }
+            count += 1;
+        });
+
+        Some(Self {
+            min: min.clone(),
+            max: max.clone(),
+            len: values.len() as u32,
+            count,
+        })
+    }
+}
+
+/// Per page statistics of a vec, used to skip pages which can't match a [`Predicate`](crate::Predicate)
+#[derive(Debug, Clone)]
+pub struct PageStats<T> {
+    vec: Vec<PageStat<T>>,
+    change_at: Option<usize>,
+    path: PathBuf,
+    cmp: fn(&T, &T) -> Option<Ordering>,
+}
+
+impl<T> PageStats<T>
+where
+    T: StoredType,
+{
+    pub const PER_PAGE: usize = MAX_PAGE_SIZE / size_of::<T>();
+    const SIZE_OF_T: usize = size_of::<T>();
+    const SIZE: usize = Self::SIZE_OF_T * 2 + size_of::<u32>() * 2;
+
+    pub fn read(path: &Path, cmp: fn(&T, &T) -> Option<Ordering>) -> Result<Self> {
+        Ok(Self {
+            vec: fs::read(path)
+                .unwrap_or_default()
+                // A partially written trailing page is dropped and will be recomputed
+                .chunks_exact(Self::SIZE)
+                .map(|bytes| {
+                    let (min, rest) = bytes.split_at(Self::SIZE_OF_T);
+                    let (max, rest) = rest.split_at(Self::SIZE_OF_T);
+                    let (len, count) = rest.split_at(size_of::<u32>());
+                    PageStat {
+                        min: T::try_read_from_bytes(min).unwrap(),
+                        max: T::try_read_from_bytes(max).unwrap(),
+                        len: u32::read_from_bytes(len).unwrap(),
+                        count: u32::read_from_bytes(count).unwrap(),
+                    }
+                })
+                .collect::<Vec<_>>(),
+            change_at: None,
+            path: path.to_owned(),
+            cmp,
+        })
+    }
+
+    pub fn write(&mut self) -> io::Result<()> {
+        let Some(change_at) = self.change_at.take() else {
+            return Ok(());
+        };
+
+        let bytes = self.vec[change_at..]
+            .iter()
+            .flat_map(|stat| {
+                [
+                    stat.min.as_bytes(),
+                    stat.max.as_bytes(),
+                    stat.len.as_bytes(),
+                    stat.count.as_bytes(),
+                ]
+                .concat()
+            })
+            .collect::<Vec<_>>();
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .create(true)
+            .truncate(false)
+            .append(true)
+            .open(&self.path)?;
+
+        file.set_len((change_at * Self::SIZE) as u64)?;
+        file.seek(SeekFrom::End(0))?;
+
+        file.write_all(&bytes)?;
+
+        Ok(())
+    }
+
+    /// Number of values covered by the stats
+    pub fn values_len(&self) -> usize {
+        self.vec.last().map_or(0, |last| {
+            (self.vec.len() - 1) * Self::PER_PAGE + last.len as usize
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, page_index: usize) -> Option<&PageStat<T>> {
+        self.vec.get(page_index)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn cmp(&self) -> fn(&T, &T) -> Option<Ordering> {
+        self.cmp
+    }
+
+    pub fn push(&mut self, page_index: usize, values: &[T]) {
+        if page_index != self.vec.len() {
+            panic!();
+        }
+
+        let Some(stat) = PageStat::compute(values, self.cmp) else {
+            return;
+        };
+
+        self.set_changed_at(page_index);
+
+        self.vec.push(stat);
+    }
+
+    fn set_changed_at(&mut self, page_index: usize) {
+        if self.change_at.is_none_or(|pi| pi > page_index) {
+            self.change_at.replace(page_index);
+        }
+    }
+
+    pub fn truncate(&mut self, page_index: usize) {
+        if page_index >= self.vec.len() {
+            return;
+        }
+        self.vec.truncate(page_index);
+        self.set_changed_at(page_index);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::PageStat;
+
+/// Condition on the values of a vec which can be checked against page statistics to skip pages
+#[derive(Debug, Clone)]
+pub enum Predicate<T> {
+    Lt(T),
+    Le(T),
+    Gt(T),
+    Ge(T),
+    Eq(T),
+    /// Inclusive
+    Between(T, T),
+}
+
+impl<T> Predicate<T>
+where
+    T: PartialOrd,
+{
+    pub fn matches(&self, value: &T) -> bool {
+        match self {
+            Self::Lt(t) => value < t,
+            Self::Le(t) => value <= t,
+            Self::Gt(t) => value > t,
+            Self::Ge(t) => value >= t,
+            Self::Eq(t) => value == t,
+            Self::Between(from, to) => value >= from && value <= to,
+        }
+    }
+
+    /// Returns false only if no value of the page can match
+    pub fn may_match(&self, stat: &PageStat<T>) -> bool {
+        if stat.count == 0 {
+            return false;
+        }
+
+        let PageStat { min, max, .. } = stat;
+
+        match self {
+            Self::Lt(t) => min < t,
+            Self::Le(t) => min <= t,
+            Self::Gt(t) => max > t,
+            Self::Ge(t) => max >= t,
+            Self::Eq(t) => min <= t && max >= t,
+            Self::Between(from, to) => min <= to && max >= from,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+/// Mean and variance of a rolling window, updated with Welford's algorithm
+///
+/// Unlike a running sum of squares, it doesn't lose the variance to cancellation when the values are large compared
+/// to their spread. Non-finite values are ignored, so they need to be given again on removal like the others.
+#[derive(Debug, Default, Clone)]
+pub struct RollingMoments {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl RollingMoments {
+    pub fn insert(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    pub fn remove(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        self.count -= 1;
+        if self.count == 0 {
+            *self = Self::default();
+            return;
+        }
+        let delta = value - self.mean;
+        self.mean -= delta / self.count as f64;
+        self.m2 = (self.m2 - delta * (value - self.mean)).max(0.0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Population variance, NaN if empty
+    pub fn variance(&self) -> f64 {
+        if self.is_empty() {
+            f64::NAN
+        } else {
+            self.m2 / self.count as f64
+        }
+    }
+}
+
+/// Same as `RollingMoments` for pairs of values, pairs with a non-finite value are ignored
+#[derive(Debug, Default, Clone)]
+pub struct RollingCovariance {
+    count: usize,
+    mean_x: f64,
+    mean_y: f64,
+    m2_x: f64,
+    m2_y: f64,
+    co_moment: f64,
+}
+
+impl RollingCovariance {
+    pub fn insert(&mut self, x: f64, y: f64) {
+        if !x.is_finite() || !y.is_finite() {
+            return;
+        }
+        self.count += 1;
+        let delta_x = x - self.mean_x;
+        let delta_y = y - self.mean_y;
+        self.mean_x += delta_x / self.count as f64;
+        self.mean_y += delta_y / self.count as f64;
+        self.m2_x += delta_x * (x - self.mean_x);
+        self.m2_y += delta_y * (y - self.mean_y);
+        self.co_moment += delta_x * (y - self.mean_y);
+    }
+
+    pub fn remove(&mut self, x: f64, y: f64) {
+        if !x.is_finite() || !y.is_finite() {
+            return;
+        }
+        self.count -= 1;
+        if self.count == 0 {
+            *self = Self::default();
+            return;
+        }
+        let delta_x = x - self.mean_x;
+        let delta_y = y - self.mean_y;
+        self.mean_x -= delta_x / self.count as f64;
+        self.mean_y -= delta_y / self.count as f64;
+        self.m2_x = (self.m2_x - delta_x * (x - self.mean_x)).max(0.0);
+        self.m2_y = (self.m2_y - delta_y * (y - self.mean_y)).max(0.0);
+        self.co_moment -= delta_x * (y - self.mean_y);
+    }
+
+    /// Pearson correlation, NaN with less than 2 pairs or if either side is constant
+    pub fn correlation(&self) -> f64 {
+        let denominator = (self.m2_x * self.m2_y).sqrt();
+        if self.count < 2 || denominator <= 0.0 || denominator.is_nan() {
+            f64::NAN
+        } else {
+            (self.co_moment / denominator).clamp(-1.0, 1.0)
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use brk_core::Version;
+use brk_core::{Result, Version};
 
 use super::{BoxedVecIterator, StoredIndex, StoredType};
 
@@ -11,6 +11,16 @@ pub trait AnyVec: Send + Sync {
     }
     fn index_type_to_string(&self) -> &'static str;
     fn value_type_to_size_of(&self) -> usize;
+    /// Changes every time the stored data of the vec (or of its sources) may have changed, see [`crate::Generation`]
+    fn generation(&self) -> u64;
+    /// Picks up the changes made by another process to a read-only vec, returns whether something changed
+    fn refresh(&self) -> Result<bool> {
+        Ok(false)
+    }
+    /// Syncs the stored files of the vec to the disk so that they survive a power loss, lazy vecs have none
+    fn sync_to_disk(&self) -> Result<()> {
+        Ok(())
+    }
     fn etag(&self, to: Option<i64>) -> String {
         let len = self.len();
         format!(
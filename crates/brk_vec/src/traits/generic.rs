@@ -1,15 +1,16 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::{self, Seek, SeekFrom, Write},
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use arc_swap::ArcSwap;
-use brk_core::{Result, Value};
+use brk_core::{Error, Result, Value};
 use memmap2::Mmap;
+use rayon::prelude::*;
 
-use crate::{AnyVec, HEADER_OFFSET, Header};
+use crate::{AnyVec, Generation, Header};
 
 use super::{StoredIndex, StoredType};
 
@@ -47,6 +48,54 @@ where
         }
     }
 
+    /// Reads many, possibly scattered and repeated, indexes at once, values are returned in the same order as requested
+    #[inline]
+    fn get_many(&self, indexes: &[I]) -> Result<Vec<T>> {
+        self.gather(indexes, &self.mmap().load())
+    }
+
+    #[inline]
+    fn gather(&self, indexes: &[I], mmap: &Mmap) -> Result<Vec<T>> {
+        let indexes = indexes
+            .iter()
+            .map(|index| index.to_usize())
+            .collect::<Result<Vec<_>>>()?;
+        self.gather_(&indexes, mmap)
+    }
+
+    fn gather_(&self, indexes: &[usize], mmap: &Mmap) -> Result<Vec<T>> {
+        let mut sorted = indexes.iter().copied().enumerate().collect::<Vec<_>>();
+        sorted.sort_unstable_by_key(|(_, index)| *index);
+
+        let mut unique = Vec::with_capacity(sorted.len());
+        let mut position_to_unique = vec![0; sorted.len()];
+        sorted.into_iter().for_each(|(position, index)| {
+            if unique.last().is_none_or(|last| *last != index) {
+                unique.push(index);
+            }
+            position_to_unique[position] = unique.len() - 1;
+        });
+
+        let values = self.read_sorted_(&unique, mmap)?;
+
+        Ok(position_to_unique
+            .into_iter()
+            .map(|i| values[i].clone())
+            .collect())
+    }
+
+    /// Reads sorted and deduplicated indexes, every index must exist
+    fn read_sorted_(&self, indexes: &[usize], mmap: &Mmap) -> Result<Vec<T>> {
+        indexes
+            .par_iter()
+            .map(|index| {
+                self.get_or_read_(*index, mmap)?
+                    .map(Value::into_inner)
+                    .ok_or(Error::IndexTooHigh)
+            })
+            .collect()
+    }
+
     #[inline]
     fn len_(&self) -> usize {
         self.stored_len() + self.pushed_len()
@@ -57,6 +106,7 @@ where
     }
 
     fn mmap(&self) -> &ArcSwap<Mmap>;
+    fn mmap_generation(&self) -> &Generation;
 
     fn stored_len(&self) -> usize;
     fn stored_len_(&self, mmap: &Mmap) -> usize;
@@ -109,35 +159,16 @@ where
         Ok(file)
     }
 
-    fn file_set_len(&mut self, file: &mut File, len: u64) -> Result<()> {
-        Self::file_set_len_(file, len)?;
-        self.update_mmap(file)
-    }
-    fn file_set_len_(file: &mut File, len: u64) -> Result<()> {
-        file.set_len(len)?;
-        file.seek(SeekFrom::End(0))?;
-        Ok(())
-    }
-
-    fn file_write_all(&mut self, file: &mut File, buf: &[u8]) -> Result<()> {
-        file.write_all(buf)?;
-        self.update_mmap(file)
-    }
-
-    fn file_truncate_and_write_all(&mut self, file: &mut File, len: u64, buf: &[u8]) -> Result<()> {
-        Self::file_set_len_(file, len)?;
+    /// Overwrites the file from `offset` without ever shrinking it, unlike a truncate the mapped pages of readers
+    /// stay valid
+    fn file_write_all_at(&mut self, file: &mut File, offset: u64, buf: &[u8]) -> Result<()> {
+        file.seek(SeekFrom::Start(offset))?;
         file.write_all(buf)?;
         self.update_mmap(file)
     }
 
     fn reset(&mut self) -> Result<()>;
 
-    #[inline]
-    fn reset_(&mut self) -> Result<()> {
-        let mut file = self.open_file()?;
-        self.file_truncate_and_write_all(&mut file, HEADER_OFFSET as u64, &[])
-    }
-
     fn new_mmap(file: &File) -> Result<Arc<Mmap>> {
         Ok(Arc::new(unsafe { Mmap::map(file)? }))
     }
@@ -145,6 +176,7 @@ where
     fn update_mmap(&mut self, file: &File) -> Result<()> {
         let mmap = Self::new_mmap(file)?;
         self.mmap().store(mmap);
+        self.mmap_generation().bump();
         Ok(())
     }
 
@@ -164,5 +196,28 @@ where
 
     fn flush(&mut self) -> Result<()>;
 
+    /// Syncs the files of the vec (and its neighbours in the same folder) to the disk so that they survive a power loss
+    fn sync(&self) -> Result<()> {
+        let folder = self.folder();
+        fs::read_dir(&folder)?.try_for_each(|entry| -> Result<()> {
+            File::open(entry?.path())?.sync_data()?;
+            Ok(())
+        })?;
+        File::open(folder)?.sync_all()?;
+        Ok(())
+    }
+
     fn truncate_if_needed(&mut self, index: I) -> Result<()>;
+
+    /// Verifies the checksums of the stored data, returns the first index of the first corrupted page/chunk if any
+    fn verify(&self) -> Result<Option<usize>>;
+
+    /// Truncates the vec at the first corrupted index if any, so the data can be recomputed
+    fn truncate_if_corrupted(&mut self) -> Result<Option<usize>> {
+        let corrupted = self.verify()?;
+        if let Some(index) = corrupted {
+            self.truncate_if_needed(I::from(index))?;
+        }
+        Ok(corrupted)
+    }
 }
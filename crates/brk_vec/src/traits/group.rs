@@ -0,0 +1,132 @@
+use brk_core::{Result, Version};
+use brk_exit::Exit;
+
+use crate::{AnyIterableVec, AnyVec, EagerVec, StoredIndex, StoredType};
+
+/// Eager vecs of the same index which are computed together, from a single pass over a source
+///
+/// Implemented for mutable references to eager vecs and for tuples, arrays and vecs of groups.
+pub trait EagerVecGroup<I>
+where
+    I: StoredIndex,
+{
+    /// `version` is the one of the sources, the inner version of each vec is added to it
+    fn validate_computed_version_or_reset_files(&mut self, version: Version) -> Result<()>;
+
+    /// Length of the shortest vec, `None` if the group is empty
+    fn min_len(&self) -> Option<usize>;
+
+    fn safe_flush(&mut self, exit: &Exit) -> Result<()>;
+
+    /// Calls `compute` for every index of `source` from `max_from` or the length of the shortest vec, whichever is
+    /// lower, `compute` pushing the values of all the vecs at that index
+    fn compute_from<T>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T>,
+        version: Version,
+        mut compute: impl FnMut(&mut Self, I, T) -> Result<()>,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        Self: Sized,
+        T: StoredType,
+    {
+        self.validate_computed_version_or_reset_files(version + source.version())?;
+
+        let from = self
+            .min_len()
+            .map_or(max_from, |len| max_from.min(I::from(len)));
+
+        source
+            .iter_at(from)
+            .try_for_each(|(i, value)| compute(self, i, value.into_inner()))?;
+
+        self.safe_flush(exit)
+    }
+}
+
+impl<I, T> EagerVecGroup<I> for &mut EagerVec<I, T>
+where
+    I: StoredIndex,
+    T: StoredType,
+{
+    fn validate_computed_version_or_reset_files(&mut self, version: Version) -> Result<()> {
+        let version = Version::ZERO + self.inner_version() + version;
+        self.validate_computed_version_or_reset_file(version)
+    }
+
+    fn min_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn safe_flush(&mut self, exit: &Exit) -> Result<()> {
+        EagerVec::safe_flush(self, exit)
+    }
+}
+
+impl<I, V> EagerVecGroup<I> for Vec<V>
+where
+    I: StoredIndex,
+    V: EagerVecGroup<I>,
+{
+    fn validate_computed_version_or_reset_files(&mut self, version: Version) -> Result<()> {
+        self.iter_mut()
+            .try_for_each(|group| group.validate_computed_version_or_reset_files(version))
+    }
+
+    fn min_len(&self) -> Option<usize> {
+        self.iter().filter_map(|group| group.min_len()).min()
+    }
+
+    fn safe_flush(&mut self, exit: &Exit) -> Result<()> {
+        self.iter_mut().try_for_each(|group| group.safe_flush(exit))
+    }
+}
+
+impl<I, V, const N: usize> EagerVecGroup<I> for [V; N]
+where
+    I: StoredIndex,
+    V: EagerVecGroup<I>,
+{
+    fn validate_computed_version_or_reset_files(&mut self, version: Version) -> Result<()> {
+        self.iter_mut()
+            .try_for_each(|group| group.validate_computed_version_or_reset_files(version))
+    }
+
+    fn min_len(&self) -> Option<usize> {
+        self.iter().filter_map(|group| group.min_len()).min()
+    }
+
+    fn safe_flush(&mut self, exit: &Exit) -> Result<()> {
+        self.iter_mut().try_for_each(|group| group.safe_flush(exit))
+    }
+}
+
+macro_rules! impl_eager_vec_group {
+    ($($V:ident $i:tt),+) => {
+        impl<I, $($V),+> EagerVecGroup<I> for ($($V,)+)
+        where
+            I: StoredIndex,
+            $($V: EagerVecGroup<I>,)+
+        {
+            fn validate_computed_version_or_reset_files(&mut self, version: Version) -> Result<()> {
+                $(self.$i.validate_computed_version_or_reset_files(version)?;)+
+                Ok(())
+            }
+
+            fn min_len(&self) -> Option<usize> {
+                [$(self.$i.min_len()),+].into_iter().flatten().min()
+            }
+
+            fn safe_flush(&mut self, exit: &Exit) -> Result<()> {
+                $(self.$i.safe_flush(exit)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_eager_vec_group!(V0 0, V1 1);
+impl_eager_vec_group!(V0 0, V1 1, V2 2);
+impl_eager_vec_group!(V0 0, V1 1, V2 2, V3 3);
@@ -1,6 +1,7 @@
 mod any;
 mod collectable;
 mod generic;
+mod group;
 mod index;
 mod iterator;
 mod r#type;
@@ -8,6 +9,7 @@ mod r#type;
 pub use any::*;
 pub use collectable::*;
 pub use generic::*;
+pub use group::*;
 pub use index::*;
 pub use iterator::*;
 pub use r#type::*;
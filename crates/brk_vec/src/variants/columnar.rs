@@ -0,0 +1,421 @@
+use std::{fmt::Debug, marker::PhantomData, path::Path};
+
+use brk_core::{Result, Value, Version};
+use brk_exit::Exit;
+
+use crate::{
+    AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedVecIterator, CollectableVec,
+    ImportMode, StoredIndex, StoredType, VecIterator,
+};
+
+use super::{EagerVec, StoredVecIterator};
+
+/// Tuple of the types of the columns of a [`ColumnarVec`], each one stored in its own eager vec
+pub trait Columns<I>: Sized + Clone + Send + Sync
+where
+    I: StoredIndex,
+{
+    type Vecs: Debug + Clone + Send + Sync;
+    type Iters<'a>
+    where
+        Self: 'a,
+        I: 'a;
+
+    const LEN: usize;
+
+    fn forced_import(
+        parent: &Path,
+        names: &[&str],
+        version: Version,
+        mode: ImportMode,
+    ) -> Result<Self::Vecs>;
+    fn len(vecs: &Self::Vecs) -> usize;
+    fn version(vecs: &Self::Vecs) -> Version;
+    fn generation(vecs: &Self::Vecs) -> u64;
+    fn sync_to_disk(vecs: &Self::Vecs) -> Result<()>;
+    fn validate_computed_version_or_reset_file(
+        vecs: &mut Self::Vecs,
+        version: Version,
+    ) -> Result<()>;
+    fn forced_push_at(vecs: &mut Self::Vecs, index: I, columns: Self, exit: &Exit) -> Result<()>;
+    fn safe_flush(vecs: &mut Self::Vecs, exit: &Exit) -> Result<()>;
+    fn refresh(vecs: &Self::Vecs) -> Result<bool>;
+    fn any_vecs(vecs: &Self::Vecs) -> Vec<&dyn AnyCollectableVec>;
+    fn iters<'a>(vecs: &'a Self::Vecs) -> Self::Iters<'a>
+    where
+        Self: 'a,
+        I: 'a;
+    fn get<'a>(iters: &mut Self::Iters<'a>, index: usize) -> Option<Self>
+    where
+        Self: 'a,
+        I: 'a;
+}
+
+macro_rules! impl_columns {
+    ($len:literal; $($T:ident $i:tt),+) => {
+        impl<I, $($T),+> Columns<I> for ($($T,)+)
+        where
+            I: StoredIndex,
+            $($T: StoredType,)+
+        {
+            type Vecs = ($(EagerVec<I, $T>,)+);
+            type Iters<'a>
+                = ($(StoredVecIterator<'a, I, $T>,)+)
+            where
+                Self: 'a,
+                I: 'a;
+
+            const LEN: usize = $len;
+
+            fn forced_import(
+                parent: &Path,
+                names: &[&str],
+                version: Version,
+                mode: ImportMode,
+            ) -> Result<Self::Vecs> {
+                Ok(($(EagerVec::forced_import(parent, names[$i], version, mode)?,)+))
+            }
+
+            fn len(vecs: &Self::Vecs) -> usize {
+                [$(vecs.$i.len()),+].into_iter().min().unwrap()
+            }
+
+            fn version(vecs: &Self::Vecs) -> Version {
+                Version::ZERO $(+ vecs.$i.version())+
+            }
+
+            fn generation(vecs: &Self::Vecs) -> u64 {
+                [$(vecs.$i.generation()),+].into_iter().max().unwrap()
+            }
+
+            fn sync_to_disk(vecs: &Self::Vecs) -> Result<()> {
+                $(vecs.$i.sync_to_disk()?;)+
+                Ok(())
+            }
+
+            fn validate_computed_version_or_reset_file(
+                vecs: &mut Self::Vecs,
+                version: Version,
+            ) -> Result<()> {
+                $(vecs.$i.validate_computed_version_or_reset_file(version)?;)+
+                Ok(())
+            }
+
+            fn forced_push_at(
+                vecs: &mut Self::Vecs,
+                index: I,
+                columns: Self,
+                exit: &Exit,
+            ) -> Result<()> {
+                $(vecs.$i.forced_push_at(index, columns.$i, exit)?;)+
+                Ok(())
+            }
+
+            fn safe_flush(vecs: &mut Self::Vecs, exit: &Exit) -> Result<()> {
+                $(vecs.$i.safe_flush(exit)?;)+
+                Ok(())
+            }
+
+            fn refresh(vecs: &Self::Vecs) -> Result<bool> {
+                let mut refreshed = false;
+                $(refreshed |= vecs.$i.refresh()?;)+
+                Ok(refreshed)
+            }
+
+            fn any_vecs(vecs: &Self::Vecs) -> Vec<&dyn AnyCollectableVec> {
+                vec![$(&vecs.$i as &dyn AnyCollectableVec),+]
+            }
+
+            fn iters<'a>(vecs: &'a Self::Vecs) -> Self::Iters<'a>
+            where
+                Self: 'a,
+                I: 'a,
+            {
+                ($(vecs.$i.into_iter(),)+)
+            }
+
+            fn get<'a>(iters: &mut Self::Iters<'a>, index: usize) -> Option<Self>
+            where
+                Self: 'a,
+                I: 'a,
+            {
+                Some(($(iters.$i.get_(index)?.into_inner(),)+))
+            }
+        }
+    };
+}
+
+impl_columns!(2; A 0, B 1);
+impl_columns!(3; A 0, B 1, C 2);
+impl_columns!(4; A 0, B 1, C 2, D 3);
+
+/// A vec of rows (OHLCs, value triples...) stored column by column
+///
+/// Each column is an eager vec with its own file, available with [`Self::columns`],
+/// while the vec itself is a view of whole rows built from them, nothing is stored twice.
+/// Its length is the one of its shortest column.
+#[derive(Debug, Clone)]
+pub struct ColumnarVec<I, R, C>
+where
+    I: StoredIndex,
+    C: Columns<I>,
+{
+    name: String,
+    columns: C::Vecs,
+    phantom: PhantomData<R>,
+}
+
+impl<I, R, C> ColumnarVec<I, R, C>
+where
+    I: StoredIndex,
+    R: StoredType + From<C>,
+    C: Columns<I> + From<R>,
+{
+    /// `names` are the names of the vecs of the columns, in order, `name` is only the one of the view
+    /// which has no file of its own
+    pub fn forced_import(
+        parent: &Path,
+        name: &str,
+        names: &[&str],
+        version: Version,
+        mode: ImportMode,
+    ) -> Result<Self> {
+        assert_eq!(names.len(), C::LEN, "{name}: expected one name per column");
+
+        Ok(Self {
+            name: name.to_string(),
+            columns: C::forced_import(parent, names, version, mode)?,
+            phantom: PhantomData,
+        })
+    }
+
+    #[inline]
+    pub fn columns(&self) -> &C::Vecs {
+        &self.columns
+    }
+
+    #[inline]
+    pub fn mut_columns(&mut self) -> &mut C::Vecs {
+        &mut self.columns
+    }
+
+    pub fn get(&self, index: I) -> Option<R> {
+        C::get(&mut C::iters(&self.columns), index.unwrap_to_usize()).map(R::from)
+    }
+
+    #[inline]
+    pub fn forced_push_at(&mut self, index: I, row: R, exit: &Exit) -> Result<()> {
+        C::forced_push_at(&mut self.columns, index, C::from(row), exit)
+    }
+
+    pub fn safe_flush(&mut self, exit: &Exit) -> Result<()> {
+        C::safe_flush(&mut self.columns, exit)
+    }
+
+    pub fn validate_computed_version_or_reset_file(&mut self, version: Version) -> Result<()> {
+        C::validate_computed_version_or_reset_file(&mut self.columns, version)
+    }
+
+    pub fn compute_transform<A, B, F>(
+        &mut self,
+        max_from: A,
+        other: &impl AnyIterableVec<A, B>,
+        mut t: F,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        A: StoredIndex,
+        B: StoredType,
+        F: FnMut((A, B, &Self)) -> (I, R),
+    {
+        self.validate_transform_version(other)?;
+
+        let index = max_from.min(A::from(self.len()));
+        other.iter_at(index).try_for_each(|(a, b)| {
+            let (i, v) = t((a, b.into_inner(), self));
+            self.forced_push_at(i, v, exit)
+        })?;
+
+        self.safe_flush(exit)
+    }
+
+    /// Appends the rows of a vec which stored them whole past the end of the columns, as if they were computed by
+    /// [`Self::compute_transform`] from `other`, which then only computes the ones after them
+    pub fn compute_from_rows<A, B>(
+        &mut self,
+        rows: &impl AnyIterableVec<I, R>,
+        other: &impl AnyIterableVec<A, B>,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        A: StoredIndex,
+        B: StoredType,
+    {
+        self.validate_transform_version(other)?;
+
+        rows.iter_at(I::from(self.len()))
+            .try_for_each(|(i, row)| self.forced_push_at(i, row.into_inner(), exit))?;
+
+        self.safe_flush(exit)
+    }
+
+    fn validate_transform_version<A, B>(&mut self, other: &impl AnyIterableVec<A, B>) -> Result<()>
+    where
+        A: StoredIndex,
+        B: StoredType,
+    {
+        self.validate_computed_version_or_reset_file(
+            Version::ZERO + self.version() + other.version(),
+        )
+    }
+
+    /// The view of whole rows followed by every column
+    pub fn vecs(&self) -> Vec<&dyn AnyCollectableVec> {
+        let mut vecs = vec![self as &dyn AnyCollectableVec];
+        vecs.append(&mut C::any_vecs(&self.columns));
+        vecs
+    }
+}
+
+impl<I, R, C> AnyVec for ColumnarVec<I, R, C>
+where
+    I: StoredIndex,
+    R: StoredType,
+    C: Columns<I>,
+{
+    #[inline]
+    fn version(&self) -> Version {
+        C::version(&self.columns)
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        C::len(&self.columns)
+    }
+
+    #[inline]
+    fn index_type_to_string(&self) -> &'static str {
+        I::to_string()
+    }
+
+    #[inline]
+    fn value_type_to_size_of(&self) -> usize {
+        size_of::<R>()
+    }
+
+    #[inline]
+    fn generation(&self) -> u64 {
+        C::generation(&self.columns)
+    }
+
+    fn sync_to_disk(&self) -> Result<()> {
+        C::sync_to_disk(&self.columns)
+    }
+
+    fn refresh(&self) -> Result<bool> {
+        C::refresh(&self.columns)
+    }
+}
+
+pub struct ColumnarVecIterator<'a, I, R, C>
+where
+    I: StoredIndex + 'a,
+    C: Columns<I> + 'a,
+{
+    vec: &'a ColumnarVec<I, R, C>,
+    iters: C::Iters<'a>,
+    index: usize,
+}
+
+impl<'a, I, R, C> BaseVecIterator for ColumnarVecIterator<'a, I, R, C>
+where
+    I: StoredIndex + 'a,
+    R: StoredType + From<C> + 'a,
+    C: Columns<I> + 'a,
+{
+    #[inline]
+    fn mut_index(&mut self) -> &mut usize {
+        &mut self.index
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        self.vec.name()
+    }
+}
+
+impl<'a, I, R, C> Iterator for ColumnarVecIterator<'a, I, R, C>
+where
+    I: StoredIndex + 'a,
+    R: StoredType + From<C> + 'a,
+    C: Columns<I> + 'a,
+{
+    type Item = (I, Value<'a, R>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+        if index >= self.vec.len() {
+            return None;
+        }
+        let row = R::from(C::get(&mut self.iters, index)?);
+        self.index += 1;
+        Some((I::from(index), Value::Owned(row)))
+    }
+}
+
+impl<'a, I, R, C> IntoIterator for &'a ColumnarVec<I, R, C>
+where
+    I: StoredIndex,
+    R: StoredType + From<C> + 'a,
+    C: Columns<I> + 'a,
+{
+    type Item = (I, Value<'a, R>);
+    type IntoIter = ColumnarVecIterator<'a, I, R, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ColumnarVecIterator {
+            vec: self,
+            iters: C::iters(&self.columns),
+            index: 0,
+        }
+    }
+}
+
+impl<I, R, C> AnyIterableVec<I, R> for ColumnarVec<I, R, C>
+where
+    I: StoredIndex,
+    R: StoredType + From<C>,
+    C: Columns<I>,
+{
+    fn boxed_iter<'a>(&'a self) -> BoxedVecIterator<'a, I, R>
+    where
+        R: 'a,
+    {
+        Box::new(self.into_iter())
+    }
+}
+
+impl<I, R, C> AnyCollectableVec for ColumnarVec<I, R, C>
+where
+    I: StoredIndex,
+    R: StoredType + From<C>,
+    C: Columns<I>,
+{
+    fn collect_range_serde_json(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<serde_json::Value>> {
+        CollectableVec::collect_range_serde_json(self, from, to)
+    }
+}
@@ -12,8 +12,8 @@ use zstd::DEFAULT_COMPRESSION_LEVEL;
 
 use crate::{
     AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedVecIterator, CollectableVec,
-    CompressedPageMetadata, CompressedPagesMetadata, GenericStoredVec, HEADER_OFFSET, Header,
-    RawVec, StoredIndex, StoredType, UnsafeSlice,
+    CompressedPageMetadata, CompressedPagesMetadata, Generation, GenericStoredVec, HEADER_OFFSET,
+    Header, RawVec, StoredIndex, StoredType, UnsafeSlice,
 };
 
 const ONE_KIB: usize = 1024;
@@ -21,7 +21,7 @@ const ONE_MIB: usize = ONE_KIB * ONE_KIB;
 pub const MAX_CACHE_SIZE: usize = 100 * ONE_MIB;
 pub const MAX_PAGE_SIZE: usize = 64 * ONE_KIB;
 
-const VERSION: Version = Version::TWO;
+const VERSION: Version = Version::new(4);
 
 #[derive(Debug)]
 pub struct CompressedVec<I, T> {
@@ -39,9 +39,17 @@ where
     pub const CACHE_LENGTH: usize = MAX_CACHE_SIZE / Self::PAGE_SIZE;
 
     /// Same as import but will reset the folder under certain errors, so be careful !
-    pub fn forced_import(parent: &Path, name: &str, mut version: Version) -> Result<Self> {
+    pub fn forced_import(
+        parent: &Path,
+        name: &str,
+        mut version: Version,
+        read_only: bool,
+    ) -> Result<Self> {
         version = version + VERSION;
-        let res = Self::import(parent, name, version);
+        if read_only {
+            return Self::import(parent, name, version, read_only);
+        }
+        let res = Self::import(parent, name, version, read_only);
         match res {
             Err(Error::DifferentCompressionMode)
             | Err(Error::WrongEndian)
@@ -49,20 +57,19 @@ where
             | Err(Error::DifferentVersion { .. }) => {
                 let path = Self::path_(parent, name);
                 fs::remove_file(path)?;
-                Self::import(parent, name, version)
+                Self::import(parent, name, version, read_only)
             }
             _ => res,
         }
     }
 
-    pub fn import(parent: &Path, name: &str, version: Version) -> Result<Self> {
-        let inner = RawVec::import(parent, name, version)?;
+    pub fn import(parent: &Path, name: &str, version: Version, read_only: bool) -> Result<Self> {
+        let inner = RawVec::import_(parent, name, version, read_only)?;
 
         let pages_meta = {
-            let path = inner
-                .folder()
-                .join(format!("{}-pages-meta", I::to_string()));
-            if inner.is_empty() {
+            let path = Self::pages_meta_path(&inner);
+            // The file was just created, the length in its header isn't used by compressed vecs
+            if inner.mmap().load().len() <= HEADER_OFFSET && !inner.read_only() {
                 let _ = fs::remove_file(&path);
             }
             Arc::new(ArcSwap::new(Arc::new(CompressedPagesMetadata::read(
@@ -73,6 +80,21 @@ where
         Ok(Self { inner, pages_meta })
     }
 
+    #[inline]
+    pub fn read_only(&self) -> bool {
+        self.inner.read_only()
+    }
+
+    pub fn pages_meta(&self) -> Guard<Arc<CompressedPagesMetadata>> {
+        self.pages_meta.load()
+    }
+
+    fn pages_meta_path(inner: &RawVec<I, T>) -> PathBuf {
+        inner
+            .folder()
+            .join(format!("{}-pages-meta", I::to_string()))
+    }
+
     fn decode_page(&self, page_index: usize, mmap: &Mmap) -> Result<Vec<T>> {
         Self::decode_page_(self.stored_len(), page_index, mmap, &self.pages_meta.load())
     }
@@ -89,20 +111,50 @@ where
             return Err(Error::ExpectVecToHaveIndex);
         }
 
-        let page = compressed_pages_meta.get(page_index).unwrap();
-        let len = page.bytes_len as usize;
-        let offset = page.start as usize;
+        let bytes = Self::page_bytes(page_index, mmap, compressed_pages_meta)?;
 
-        Ok(zstd::decode_all(&mmap[offset..offset + len])
-            .inspect_err(|_| {
-                dbg!((len, offset, page_index, &mmap[..], &mmap.len()));
-            })?
+        Ok(zstd::decode_all(bytes)?
             .chunks(Self::SIZE_OF_T)
             .map(|slice| T::try_read_from_bytes(slice).unwrap())
             .collect::<Vec<_>>())
     }
 
-    fn compress_page(chunk: &[T]) -> Vec<u8> {
+    /// Returns the compressed bytes of a page after checking them against the page's checksum
+    fn page_bytes<'a>(
+        page_index: usize,
+        mmap: &'a Mmap,
+        compressed_pages_meta: &CompressedPagesMetadata,
+    ) -> Result<&'a [u8]> {
+        let page = compressed_pages_meta
+            .get(page_index)
+            .ok_or(Error::ExpectVecToHaveIndex)?;
+        let len = page.bytes_len as usize;
+        let offset = page.start as usize;
+
+        mmap.get(offset..offset + len)
+            .filter(|bytes| crc32fast::hash(bytes) == page.checksum)
+            .ok_or(Error::ChecksumMismatch {
+                index: Self::page_index_to_index(page_index),
+            })
+    }
+
+    fn verify_page(
+        page_index: usize,
+        mmap: &Mmap,
+        compressed_pages_meta: &CompressedPagesMetadata,
+    ) -> bool {
+        let values_checksum = compressed_pages_meta
+            .get(page_index)
+            .unwrap()
+            .values_checksum;
+        Self::page_bytes(page_index, mmap, compressed_pages_meta)
+            .ok()
+            .and_then(|bytes| zstd::decode_all(bytes).ok())
+            .is_some_and(|decoded| crc32fast::hash(&decoded) == values_checksum)
+    }
+
+    /// Returns the compressed bytes and the checksum of the uncompressed ones
+    fn compress_page(chunk: &[T]) -> (Vec<u8>, u32) {
         if chunk.len() > Self::PER_PAGE {
             panic!();
         }
@@ -116,7 +168,12 @@ where
             .enumerate()
             .for_each(|(i, v)| unsafe_bytes.copy_slice(i * Self::SIZE_OF_T, v.as_bytes()));
 
-        zstd::encode_all(bytes.as_slice(), DEFAULT_COMPRESSION_LEVEL).unwrap()
+        let values_checksum = crc32fast::hash(&bytes);
+
+        (
+            zstd::encode_all(bytes.as_slice(), DEFAULT_COMPRESSION_LEVEL).unwrap(),
+            values_checksum,
+        )
     }
 
     #[inline(always)]
@@ -129,6 +186,31 @@ where
         page_index * Self::PER_PAGE
     }
 
+    fn pages_end(pages_meta: &CompressedPagesMetadata) -> u64 {
+        pages_meta.last().map_or(HEADER_OFFSET as u64, |page| {
+            page.start + page.bytes_len as u64
+        })
+    }
+
+    /// Offset where `len` bytes of pages replacing `replaced` can be written without overwriting any page of the
+    /// metadata on disk (which ends at `used_end`), so that a crash before the new metadata is written tears nothing
+    ///
+    /// That's right after the kept pages if they fit before the replaced one, after all of them otherwise, which makes
+    /// a page rewritten on every flush alternate between two places instead of growing the file each time.
+    fn free_offset(
+        kept: &CompressedPagesMetadata,
+        replaced: Option<&CompressedPageMetadata>,
+        used_end: u64,
+        len: u64,
+    ) -> u64 {
+        let kept_end = Self::pages_end(kept);
+        match replaced {
+            Some(page) if kept_end + len <= page.start => kept_end,
+            Some(_) => used_end,
+            None => kept_end,
+        }
+    }
+
     fn stored_len__(pages_meta: &Guard<Arc<CompressedPagesMetadata>>) -> usize {
         if let Some(last) = pages_meta.last() {
             (pages_meta.len() - 1) * Self::PER_PAGE + last.values_len as usize
@@ -171,6 +253,51 @@ where
             .cloned())
     }
 
+    fn read_sorted_(&self, indexes: &[usize], mmap: &Mmap) -> Result<Vec<T>> {
+        let pages_meta = self.pages_meta.load();
+        let stored_len = Self::stored_len__(&pages_meta);
+        let pages_meta: &CompressedPagesMetadata = &pages_meta;
+
+        let split = indexes.partition_point(|index| *index < stored_len);
+        let (stored, pushed) = indexes.split_at(split);
+
+        // Each page is decoded once for all the indexes it contains
+        let mut values = stored
+            .chunk_by(|a, b| Self::index_to_page_index(*a) == Self::index_to_page_index(*b))
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|indexes| -> Result<Vec<T>> {
+                let page_index = Self::index_to_page_index(indexes[0]);
+                let page = Self::decode_page_(stored_len, page_index, mmap, pages_meta)?;
+                indexes
+                    .iter()
+                    .map(|index| {
+                        page.get(index % Self::PER_PAGE)
+                            .cloned()
+                            .ok_or(Error::IndexTooHigh)
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        values.extend(
+            pushed
+                .iter()
+                .map(|index| {
+                    self.pushed()
+                        .get(index - stored_len)
+                        .cloned()
+                        .ok_or(Error::IndexTooHigh)
+                })
+                .collect::<Result<Vec<_>>>()?,
+        );
+
+        Ok(values)
+    }
+
     fn header(&self) -> &Header {
         self.inner.header()
     }
@@ -184,6 +311,11 @@ where
         self.inner.mmap()
     }
 
+    #[inline]
+    fn mmap_generation(&self) -> &Generation {
+        self.inner.mmap_generation()
+    }
+
     fn parent(&self) -> &Path {
         self.inner.parent()
     }
@@ -212,6 +344,8 @@ where
     }
 
     fn flush(&mut self) -> Result<()> {
+        self.inner.ensure_writable()?;
+
         let file_opt = self.inner.write_header_if_needed()?;
 
         let pushed_len = self.pushed_len();
@@ -224,9 +358,11 @@ where
 
         let mut pages_meta = (**self.pages_meta.load()).clone();
 
+        let used_end = Self::pages_end(&pages_meta);
+
         let mut starting_page_index = pages_meta.len();
         let mut values = vec![];
-        let mut truncate_at = None;
+        let mut replaced = None;
 
         if self.stored_len() % Self::PER_PAGE != 0 {
             if pages_meta.is_empty() {
@@ -246,7 +382,7 @@ where
             })
             .unwrap();
 
-            truncate_at.replace(pages_meta.pop().unwrap().start);
+            replaced = pages_meta.pop();
             starting_page_index = last_page_index;
         }
 
@@ -257,42 +393,48 @@ where
             .map(|chunk| (Self::compress_page(chunk.as_ref()), chunk.len()))
             .collect::<Vec<_>>();
 
-        compressed
-            .iter()
-            .enumerate()
-            .for_each(|(i, (compressed_bytes, values_len))| {
-                let page_index = starting_page_index + i;
-
-                let start = if page_index != 0 {
-                    let prev = pages_meta.get(page_index - 1).unwrap();
-                    prev.start + prev.bytes_len as u64
-                } else {
-                    0
-                };
-                let offsetted_start = start + HEADER_OFFSET as u64;
-
+        let write_at = Self::free_offset(
+            &pages_meta,
+            replaced.as_ref(),
+            used_end,
+            compressed
+                .iter()
+                .map(|((bytes, _), _)| bytes.len() as u64)
+                .sum(),
+        );
+
+        let mut start = write_at;
+        compressed.iter().enumerate().for_each(
+            |(i, ((compressed_bytes, values_checksum), values_len))| {
                 let bytes_len = compressed_bytes.len() as u32;
                 let values_len = *values_len as u32;
+                let checksum = crc32fast::hash(compressed_bytes);
+
+                let page = CompressedPageMetadata::new(
+                    start,
+                    bytes_len,
+                    values_len,
+                    checksum,
+                    *values_checksum,
+                );
 
-                let page = CompressedPageMetadata::new(offsetted_start, bytes_len, values_len);
+                pages_meta.push(starting_page_index + i, page);
 
-                pages_meta.push(page_index, page);
-            });
+                start += bytes_len as u64;
+            },
+        );
 
         let buf = compressed
             .into_iter()
-            .flat_map(|(v, _)| v)
+            .flat_map(|((v, _), _)| v)
             .collect::<Vec<_>>();
 
-        pages_meta.write()?;
-
         let mut file = file_opt.unwrap_or(self.open_file()?);
 
-        if let Some(truncate_at) = truncate_at {
-            self.file_set_len(&mut file, truncate_at)?;
-        }
+        self.file_write_all_at(&mut file, write_at, &buf)?;
 
-        self.file_write_all(&mut file, &buf)?;
+        // Written after the pages so that readers never see the metadata of a page before its bytes
+        pages_meta.write()?;
 
         self.pages_meta.store(Arc::new(pages_meta));
 
@@ -300,14 +442,20 @@ where
     }
 
     fn reset(&mut self) -> Result<()> {
+        self.inner.ensure_writable()?;
+
         let mut pages_meta = (**self.pages_meta.load()).clone();
         pages_meta.truncate(0);
         pages_meta.write()?;
         self.pages_meta.store(Arc::new(pages_meta));
-        self.reset_()
+        self.mmap_generation().bump();
+
+        Ok(())
     }
 
     fn truncate_if_needed(&mut self, index: I) -> Result<()> {
+        self.inner.ensure_writable()?;
+
         let index = index.to_usize()?;
 
         if index >= self.stored_len() {
@@ -321,37 +469,65 @@ where
 
         let mut pages_meta = (**self.pages_meta.load()).clone();
 
-        let page_index = Self::index_to_page_index(index);
-
-        let values = self.decode_page(page_index, &self.mmap().load())?;
-        let mut buf = vec![];
-
-        let mut page = pages_meta.truncate(page_index).unwrap();
-
-        let len = page.start;
+        let used_end = Self::pages_end(&pages_meta);
 
+        let page_index = Self::index_to_page_index(index);
         let decoded_index = index % Self::PER_PAGE;
 
+        // Only decode when part of the page is kept, a corrupted page can still be truncated
+        let values = if decoded_index != 0 {
+            self.decode_page(page_index, &self.mmap().load())?
+        } else {
+            vec![]
+        };
+        let page = pages_meta.truncate(page_index).unwrap();
+
         if decoded_index != 0 {
             let chunk = &values[..decoded_index];
 
-            buf = Self::compress_page(chunk);
+            let (compressed_bytes, values_checksum) = Self::compress_page(chunk);
 
-            page.values_len = chunk.len() as u32;
-            page.bytes_len = buf.len() as u32;
-
-            pages_meta.push(page_index, page);
+            let write_at = Self::free_offset(
+                &pages_meta,
+                Some(&page),
+                used_end,
+                compressed_bytes.len() as u64,
+            );
+
+            let mut file = self.open_file()?;
+            self.file_write_all_at(&mut file, write_at, &compressed_bytes)?;
+
+            pages_meta.push(
+                page_index,
+                CompressedPageMetadata::new(
+                    write_at,
+                    compressed_bytes.len() as u32,
+                    chunk.len() as u32,
+                    crc32fast::hash(&compressed_bytes),
+                    values_checksum,
+                ),
+            );
         }
 
+        // Written after the page, the file is never shrunk so that readers still mapping its end don't crash
         pages_meta.write()?;
 
         self.pages_meta.store(Arc::new(pages_meta));
+        self.mmap_generation().bump();
 
-        let mut file = self.open_file()?;
+        Ok(())
+    }
 
-        self.file_truncate_and_write_all(&mut file, len, &buf)?;
+    fn verify(&self) -> Result<Option<usize>> {
+        let pages_meta = self.pages_meta.load();
+        let pages_meta: &CompressedPagesMetadata = &pages_meta;
+        let mmap = self.mmap().load();
+        let mmap: &Mmap = &mmap;
 
-        Ok(())
+        Ok((0..pages_meta.len())
+            .into_par_iter()
+            .find_first(|page_index| !Self::verify_page(*page_index, mmap, pages_meta))
+            .map(Self::page_index_to_index))
     }
 }
 
@@ -384,6 +560,47 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    #[inline]
+    fn generation(&self) -> u64 {
+        self.inner.generation()
+    }
+
+    /// The pages metadata is in the same folder as the pages
+    fn sync_to_disk(&self) -> Result<()> {
+        self.inner.sync_to_disk()
+    }
+
+    fn refresh(&self) -> Result<bool> {
+        let remapped = self.inner.refresh()?;
+
+        if !self.inner.read_only() {
+            return Ok(false);
+        }
+
+        let mut pages_meta = CompressedPagesMetadata::read(&Self::pages_meta_path(&self.inner))?;
+
+        // Pages written after the file was mapped are ignored until the next refresh
+        let mmap_len = self.mmap().load().len() as u64;
+        while pages_meta
+            .last()
+            .is_some_and(|page| page.start + page.bytes_len as u64 > mmap_len)
+        {
+            pages_meta.pop();
+        }
+
+        // The file doesn't grow when only its last page was rewritten, so the pages are compared too
+        let current = self.pages_meta.load();
+        let changed =
+            remapped || pages_meta.len() != current.len() || pages_meta.last() != current.last();
+
+        if changed {
+            self.pages_meta.store(Arc::new(pages_meta));
+            self.inner.mmap_generation().bump();
+        }
+
+        Ok(changed)
+    }
 }
 
 impl<I, T> Clone for CompressedVec<I, T> {
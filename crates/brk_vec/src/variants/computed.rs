@@ -8,7 +8,7 @@ use brk_core::{Result, StoredPhantom, Value, Version};
 
 use crate::{
     AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedAnyIterableVec,
-    BoxedVecIterator, CollectableVec, Format, StoredIndex, StoredType,
+    BoxedVecIterator, CollectableVec, ImportMode, StoredIndex, StoredType,
 };
 
 use super::{
@@ -82,21 +82,23 @@ where
     S3T: StoredType,
 {
     pub fn forced_import_or_init_from_1(
-        mode: Computation,
+        computation: Computation,
         path: &Path,
         name: &str,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         source: BoxedAnyIterableVec<S1I, S1T>,
         compute: ComputeFrom1<I, T, S1I, S1T>,
     ) -> Result<Self> {
-        Ok(match mode {
+        Ok(match computation {
             Computation::Eager => Self::Eager {
-                vec: EagerVec::forced_import(path, name, version, format)?,
+                vec: EagerVec::forced_import(path, name, version, mode)?,
                 deps: Dependencies::From1(source, compute),
             },
             Computation::Lazy => {
-                let _ = fs::remove_dir_all(path.join(name).join(I::to_string()));
+                if !mode.read_only {
+                    let _ = fs::remove_dir_all(path.join(name).join(I::to_string()));
+                }
                 Self::LazyFrom1(LazyVecFrom1::init(name, version, source, compute))
             }
         })
@@ -104,22 +106,24 @@ where
 
     #[allow(clippy::too_many_arguments)]
     pub fn forced_import_or_init_from_2(
-        mode: Computation,
+        computation: Computation,
         path: &Path,
         name: &str,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         source1: BoxedAnyIterableVec<S1I, S1T>,
         source2: BoxedAnyIterableVec<S2I, S2T>,
         compute: ComputeFrom2<I, T, S1I, S1T, S2I, S2T>,
     ) -> Result<Self> {
-        Ok(match mode {
+        Ok(match computation {
             Computation::Eager => Self::Eager {
-                vec: EagerVec::forced_import(path, name, version, format)?,
+                vec: EagerVec::forced_import(path, name, version, mode)?,
                 deps: Dependencies::From2((source1, source2), compute),
             },
             Computation::Lazy => {
-                let _ = fs::remove_dir_all(path.join(name).join(I::to_string()));
+                if !mode.read_only {
+                    let _ = fs::remove_dir_all(path.join(name).join(I::to_string()));
+                }
                 Self::LazyFrom2(LazyVecFrom2::init(name, version, source1, source2, compute))
             }
         })
@@ -127,23 +131,25 @@ where
 
     #[allow(clippy::too_many_arguments)]
     pub fn forced_import_or_init_from_3(
-        mode: Computation,
+        computation: Computation,
         path: &Path,
         name: &str,
         version: Version,
-        format: Format,
+        mode: ImportMode,
         source1: BoxedAnyIterableVec<S1I, S1T>,
         source2: BoxedAnyIterableVec<S2I, S2T>,
         source3: BoxedAnyIterableVec<S3I, S3T>,
         compute: ComputeFrom3<I, T, S1I, S1T, S2I, S2T, S3I, S3T>,
     ) -> Result<Self> {
-        Ok(match mode {
+        Ok(match computation {
             Computation::Eager => Self::Eager {
-                vec: EagerVec::forced_import(path, name, version, format)?,
+                vec: EagerVec::forced_import(path, name, version, mode)?,
                 deps: Dependencies::From3((source1, source2, source3), compute),
             },
             Computation::Lazy => {
-                let _ = fs::remove_dir_all(path.join(name).join(I::to_string()));
+                if !mode.read_only {
+                    let _ = fs::remove_dir_all(path.join(name).join(I::to_string()));
+                }
                 Self::LazyFrom3(LazyVecFrom3::init(
                     name, version, source1, source2, source3, compute,
                 ))
@@ -249,6 +255,32 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    fn generation(&self) -> u64 {
+        match self {
+            ComputedVec::Eager { vec, .. } => vec.generation(),
+            ComputedVec::LazyFrom1(v) => v.generation(),
+            ComputedVec::LazyFrom2(v) => v.generation(),
+            ComputedVec::LazyFrom3(v) => v.generation(),
+        }
+    }
+
+    fn sync_to_disk(&self) -> Result<()> {
+        match self {
+            ComputedVec::Eager { vec, .. } => vec.sync_to_disk(),
+            ComputedVec::LazyFrom1(_) | ComputedVec::LazyFrom2(_) | ComputedVec::LazyFrom3(_) => {
+                Ok(())
+            }
+        }
+    }
+
+    /// Lazy vecs share their sources and thus don't need to be refreshed
+    fn refresh(&self) -> Result<bool> {
+        match self {
+            ComputedVec::Eager { vec, .. } => vec.refresh(),
+            _ => Ok(false),
+        }
+    }
 }
 
 pub enum ComputedVecIterator<'a, I, T, S1I, S1T, S2I, S2T, S3I, S3T> {
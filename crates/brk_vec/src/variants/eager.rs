@@ -1,10 +1,12 @@
 use core::error;
 use std::{
     cmp::Ordering,
+    collections::VecDeque,
     f32,
     fmt::Debug,
     ops::{Add, Div, Mul},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use arc_swap::ArcSwap;
@@ -17,17 +19,19 @@ use log::info;
 use memmap2::Mmap;
 
 use crate::{
-    AnyCollectableVec, AnyIterableVec, AnyVec, BoxedVecIterator, CollectableVec, Format,
-    GenericStoredVec, StoredIndex, StoredType, StoredVec, StoredVecIterator, VecIterator,
+    AnyCollectableVec, AnyIterableVec, AnyVec, BoxedVecIterator, CollectableVec, GenericStoredVec,
+    ImportMode, IndexedVec, OrderStatisticTree, PageStats, Predicate, RollingCovariance,
+    RollingMoments, StoredIndex, StoredType, StoredVec, StoredVecIterator, VecIterator,
 };
 
 const ONE_KIB: usize = 1024;
 const ONE_MIB: usize = ONE_KIB * ONE_KIB;
 const MAX_CACHE_SIZE: usize = 210 * ONE_MIB;
+const GATHER_BATCH_SIZE: usize = ONE_MIB;
 const DCA_AMOUNT: Dollars = Dollars::mint(100.0);
 
 #[derive(Debug, Clone)]
-pub struct EagerVec<I, T>(StoredVec<I, T>);
+pub struct EagerVec<I, T>(StoredVec<I, T>, Option<Arc<ArcSwap<PageStats<T>>>>);
 // computed_version: Arc<ArcSwap<Option<Version>>>,
 
 impl<I, T> EagerVec<I, T>
@@ -41,11 +45,149 @@ where
         path: &Path,
         value_name: &str,
         version: Version,
-        format: Format,
+        mode: ImportMode,
     ) -> Result<Self> {
-        Ok(Self(StoredVec::forced_import(
-            path, value_name, version, format,
-        )?))
+        Ok(Self(
+            StoredVec::forced_import(path, value_name, version, mode)?,
+            None,
+        ))
+    }
+
+    /// Keeps min/max statistics of every stored page in a side file, used by [`Self::find_first_where`] and [`Self::range_where`] to skip pages
+    pub fn enable_page_stats(&mut self) -> Result<()>
+    where
+        T: PartialOrd,
+    {
+        if self.1.is_some() {
+            return Ok(());
+        }
+
+        let path = self.page_stats_path();
+        self.1
+            .replace(Arc::new(ArcSwap::from_pointee(PageStats::read(
+                &path,
+                T::partial_cmp,
+            )?)));
+
+        if self.0.read_only() {
+            Ok(())
+        } else {
+            self.update_page_stats()
+        }
+    }
+
+    fn page_stats_path(&self) -> PathBuf {
+        self.0
+            .folder()
+            .join(format!("{}-page-stats", I::to_string()))
+    }
+
+    /// Recomputes the stats of the pages which changed since the last call, from the stored values only
+    fn update_page_stats(&self) -> Result<()> {
+        let Some(page_stats) = self.1.as_ref() else {
+            return Ok(());
+        };
+
+        let mut page_stats = (**page_stats.load()).clone();
+
+        let per_page = PageStats::<T>::PER_PAGE;
+        let stored_len = self.0.stored_len();
+
+        let mut page_index = page_stats.values_len().min(stored_len) / per_page;
+        page_stats.truncate(page_index);
+
+        let start = page_index * per_page;
+        let mut values = Vec::with_capacity(per_page);
+
+        self.iter_at_(start)
+            .take(stored_len - start)
+            .for_each(|(_, value)| {
+                values.push(value.into_inner());
+                if values.len() == per_page {
+                    page_stats.push(page_index, &values);
+                    page_index += 1;
+                    values.clear();
+                }
+            });
+
+        page_stats.push(page_index, &values);
+
+        page_stats.write()?;
+
+        self.1.as_ref().unwrap().store(Arc::new(page_stats));
+
+        Ok(())
+    }
+
+    /// First index whose value matches the predicate
+    pub fn find_first_where(&self, predicate: &Predicate<T>) -> Result<Option<I>>
+    where
+        T: PartialOrd,
+    {
+        Ok(self
+            .scan_where(predicate, 0, self.len(), true)?
+            .into_iter()
+            .next()
+            .map(|(i, _)| i))
+    }
+
+    /// Every index and value in `from..to` which matches the predicate
+    pub fn range_where(&self, predicate: &Predicate<T>, from: I, to: I) -> Result<Vec<(I, T)>>
+    where
+        T: PartialOrd,
+    {
+        let from = from.to_usize()?;
+        let to = to.to_usize()?.min(self.len());
+        if from > to {
+            return Err(Error::RangeFromAfterTo(from, to));
+        }
+        self.scan_where(predicate, from, to, false)
+    }
+
+    fn scan_where(
+        &self,
+        predicate: &Predicate<T>,
+        from: usize,
+        to: usize,
+        first_only: bool,
+    ) -> Result<Vec<(I, T)>>
+    where
+        T: PartialOrd,
+    {
+        let page_stats = self.1.as_ref().map(|page_stats| page_stats.load_full());
+
+        let per_page = PageStats::<T>::PER_PAGE;
+
+        let mut found = vec![];
+        let mut index = from;
+
+        while index < to {
+            let page_index = index / per_page;
+            let page_end = ((page_index + 1) * per_page).min(to);
+
+            // Pages without stats (or only partially covered) are scanned
+            let skip = page_stats
+                .as_ref()
+                .filter(|page_stats| page_stats.values_len() >= page_end)
+                .and_then(|page_stats| page_stats.get(page_index))
+                .is_some_and(|stat| !predicate.may_match(stat));
+
+            if !skip {
+                for (i, value) in self.iter_at_(index).take(page_end - index) {
+                    let value = value.into_inner();
+                    if predicate.matches(&value) {
+                        found.push((i, value));
+                        if first_only {
+                            return Ok(found);
+                        }
+                    }
+                }
+            }
+
+            index = page_end;
+        }
+
+        Ok(found)
     }
 
     fn safe_truncate_if_needed(&mut self, index: I, exit: &Exit) -> Result<()> {
@@ -57,6 +199,7 @@ where
             exit.block();
         }
         self.0.truncate_if_needed(index)?;
+        self.update_page_stats()?;
         if !blocked {
             exit.release();
         }
@@ -93,6 +236,7 @@ where
             exit.block();
         }
         self.0.flush()?;
+        self.update_page_stats()?;
         if !blocked {
             exit.release();
         }
@@ -107,6 +251,10 @@ where
         self.0.get_or_read(index, mmap)
     }
 
+    pub fn gather(&self, indexes: &[I], mmap: &Mmap) -> Result<Vec<T>> {
+        self.0.gather(indexes, mmap)
+    }
+
     pub fn mmap(&self) -> &ArcSwap<Mmap> {
         self.0.mmap()
     }
@@ -115,6 +263,18 @@ where
         self.0.version()
     }
 
+    pub fn verify(&self) -> Result<Option<usize>> {
+        self.0.verify()
+    }
+
+    pub fn truncate_if_corrupted(&mut self) -> Result<Option<usize>> {
+        let truncated = self.0.truncate_if_corrupted()?;
+        if truncated.is_some() {
+            self.update_page_stats()?;
+        }
+        Ok(truncated)
+    }
+
     fn update_computed_version(&mut self, computed_version: Version) {
         self.0
             .mut_header()
@@ -126,6 +286,7 @@ where
             self.update_computed_version(version);
             if !self.is_empty() {
                 self.0.reset()?;
+                self.update_page_stats()?;
             }
         }
 
@@ -220,6 +381,62 @@ where
         self.safe_flush(exit)
     }
 
+    /// Looks up the value of each of `indexes` in `values`, unless `resolve` already knows it
+    ///
+    /// Scattered indexes are read by sorted batches, so each page of `values` is decoded only once per batch
+    pub fn compute_gather<A>(
+        &mut self,
+        max_from: I,
+        indexes: &impl AnyIterableVec<I, A>,
+        values: &IndexedVec<A, T>,
+        resolve: impl Fn(A) -> Option<T>,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        A: StoredIndex + StoredType,
+    {
+        self.validate_computed_version_or_reset_file(
+            Version::ZERO + self.0.version() + indexes.version() + values.version(),
+        )?;
+
+        let mmap = values.mmap().load();
+
+        let index = max_from.min(I::from(self.len()));
+        let mut iter = indexes.iter_at(index);
+        let mut batch = Vec::with_capacity(GATHER_BATCH_SIZE);
+
+        loop {
+            batch.clear();
+            batch.extend(
+                iter.by_ref()
+                    .take(GATHER_BATCH_SIZE)
+                    .map(|(i, a)| (i, a.into_inner())),
+            );
+
+            if batch.is_empty() {
+                break;
+            }
+
+            let resolved = batch.iter().map(|(_, a)| resolve(*a)).collect::<Vec<_>>();
+
+            let unresolved = batch
+                .iter()
+                .zip(resolved.iter())
+                .filter(|(_, value)| value.is_none())
+                .map(|((_, a), _)| *a)
+                .collect::<Vec<_>>();
+
+            let mut gathered = values.gather(&unresolved, &mmap)?.into_iter();
+
+            batch.iter().zip(resolved).try_for_each(|((i, _), value)| {
+                let value = value.or_else(|| gathered.next()).unwrap();
+                self.forced_push_at(*i, value, exit)
+            })?;
+        }
+
+        self.safe_flush(exit)
+    }
+
     pub fn compute_add(
         &mut self,
         max_from: I,
@@ -1035,6 +1252,450 @@ where
             exit,
         )
     }
+
+    pub fn compute_ema<T2>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T2>,
+        ema: usize,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T> + From<T2>,
+    {
+        self.validate_computed_version_or_reset_file(
+            Version::ZERO + self.0.version() + source.version(),
+        )?;
+
+        let index = max_from.min(I::from(self.len()));
+        let alpha = 2.0 / (ema as f32 + 1.0);
+        let mut prev = index
+            .decremented()
+            .map(|prev_i| f32::from(self.into_iter().unwrap_get_inner(prev_i)));
+
+        source.iter_at(index).try_for_each(|(i, value)| {
+            let value = f32::from(value.into_inner());
+
+            let ema = prev.map_or(value, |prev| alpha * value + (1.0 - alpha) * prev);
+
+            prev.replace(ema);
+            self.forced_push_at(i, T::from(ema), exit)
+        })?;
+
+        self.safe_flush(exit)
+    }
+
+    /// Population standard deviation of the last `window` values, non-finite values are ignored
+    pub fn compute_rolling_stddev<T2>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T2>,
+        window: usize,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T2>,
+    {
+        self.compute_rolling_stddev_(max_from, source, window, exit, None)
+    }
+
+    /// Same as `compute_rolling_stddev` but the window never starts before `min_i`, values before it are NaN
+    pub fn compute_rolling_stddev_<T2>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T2>,
+        window: usize,
+        exit: &Exit,
+        min_i: Option<I>,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T2>,
+    {
+        if window == 0 {
+            panic!("Window must be at least 1");
+        }
+
+        self.validate_computed_version_or_reset_file(
+            Version::ONE + self.0.version() + source.version(),
+        )?;
+
+        let index = max_from.min(I::from(self.len())).unwrap_to_usize();
+        let min_i = min_i.map_or(0, |min_i| min_i.unwrap_to_usize());
+        let mut source_iter = source.iter();
+
+        let mut moments = RollingMoments::default();
+
+        (index.saturating_sub(window).max(min_i)..index).for_each(|i| {
+            moments.insert(f32::from(source_iter.unwrap_get_inner_(i)) as f64);
+        });
+
+        source.iter_at_(index).try_for_each(|(i, value)| {
+            let i_ = i.unwrap_to_usize();
+
+            if i_ < min_i {
+                return self.forced_push_at(i, T::from(f32::NAN), exit);
+            }
+
+            moments.insert(f32::from(value.into_inner()) as f64);
+
+            if let Some(prev_i) = i_.checked_sub(window).filter(|prev_i| *prev_i >= min_i) {
+                moments.remove(f32::from(source_iter.unwrap_get_inner_(prev_i)) as f64);
+            }
+
+            self.forced_push_at(i, T::from(moments.variance().sqrt() as f32), exit)
+        })?;
+
+        self.safe_flush(exit)
+    }
+
+    pub fn compute_rolling_min(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T>,
+        window: usize,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: PartialOrd,
+    {
+        self.compute_rolling_extremum(max_from, source, window, |a, b| a <= b, exit)
+    }
+
+    pub fn compute_rolling_max(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T>,
+        window: usize,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: PartialOrd,
+    {
+        self.compute_rolling_extremum(max_from, source, window, |a, b| a >= b, exit)
+    }
+
+    /// Monotonic deque, `keep(a, b)` is true if `a` is a better or equal candidate than `b`
+    fn compute_rolling_extremum(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T>,
+        window: usize,
+        keep: fn(&T, &T) -> bool,
+        exit: &Exit,
+    ) -> Result<()> {
+        if window == 0 {
+            panic!("Window must be at least 1");
+        }
+
+        self.validate_computed_version_or_reset_file(
+            Version::ZERO + self.0.version() + source.version(),
+        )?;
+
+        let index = max_from.min(I::from(self.len())).unwrap_to_usize();
+
+        let mut deque: VecDeque<(usize, T)> = VecDeque::new();
+
+        let push = |deque: &mut VecDeque<(usize, T)>, i: usize, value: T| {
+            while deque.back().is_some_and(|(_, back)| !keep(back, &value)) {
+                deque.pop_back();
+            }
+            deque.push_back((i, value));
+            while deque
+                .front()
+                .is_some_and(|(front_i, _)| front_i + window <= i)
+            {
+                deque.pop_front();
+            }
+        };
+
+        source
+            .iter_at_(index.saturating_sub(window - 1))
+            .take(index - index.saturating_sub(window - 1))
+            .for_each(|(i, value)| push(&mut deque, i.unwrap_to_usize(), value.into_inner()));
+
+        source.iter_at_(index).try_for_each(|(i, value)| {
+            push(&mut deque, i.unwrap_to_usize(), value.into_inner());
+            self.forced_push_at(i, deque.front().unwrap().1.clone(), exit)
+        })?;
+
+        self.safe_flush(exit)
+    }
+
+    /// Percentile of the last `window` values, NaNs are ignored
+    pub fn compute_rolling_percentile<T2>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T2>,
+        window: usize,
+        percentile: f64,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T2>,
+    {
+        self.compute_rolling_percentile_(max_from, source, window, percentile, exit, None)
+    }
+
+    /// Same as `compute_rolling_percentile` but the window never starts before `min_i`, values before it are NaN
+    ///
+    /// The source is read in chunks, each with its own tree, so that at most `window` values plus a chunk are loaded
+    /// at once.
+    pub fn compute_rolling_percentile_<T2>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T2>,
+        window: usize,
+        percentile: f64,
+        exit: &Exit,
+        min_i: Option<I>,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T2>,
+    {
+        const CHUNK_LEN: usize = 1 << 16;
+
+        if window == 0 {
+            panic!("Window must be at least 1");
+        }
+
+        self.validate_computed_version_or_reset_file(
+            Version::ONE + self.0.version() + source.version(),
+        )?;
+
+        let index = max_from.min(I::from(self.len())).unwrap_to_usize();
+        let min_i = min_i.map_or(0, |min_i| min_i.unwrap_to_usize());
+        let len = source.len();
+
+        (index.min(min_i)..min_i.min(len))
+            .try_for_each(|i| self.forced_push_at(I::from(i), T::from(f32::NAN), exit))?;
+
+        let mut chunk_start = index.max(min_i);
+
+        while chunk_start < len {
+            let chunk_end = chunk_start.saturating_add(CHUNK_LEN).min(len);
+            let from = chunk_start.saturating_sub(window).max(min_i);
+
+            let values = source
+                .iter_at_(from)
+                .take(chunk_end - from)
+                .map(|(_, value)| f32::from(value.into_inner()))
+                .collect::<Vec<_>>();
+
+            let mut tree = OrderStatisticTree::new(values.clone());
+
+            values[..chunk_start - from]
+                .iter()
+                .for_each(|value| tree.insert(*value));
+
+            (chunk_start..chunk_end).try_for_each(|i| {
+                tree.insert(values[i - from]);
+                if let Some(prev_i) = i.checked_sub(window).filter(|prev_i| *prev_i >= min_i) {
+                    tree.remove(values[prev_i - from]);
+                }
+                self.forced_push_at(I::from(i), T::from(tree.percentile(percentile)), exit)
+            })?;
+
+            chunk_start = chunk_end;
+        }
+
+        self.safe_flush(exit)
+    }
+
+    /// Pearson correlation of the last `window` values of both sources, pairs with a non-finite value are ignored
+    pub fn compute_rolling_correlation<T2, T3>(
+        &mut self,
+        max_from: I,
+        source1: &impl AnyIterableVec<I, T2>,
+        source2: &impl AnyIterableVec<I, T3>,
+        window: usize,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        T3: StoredType,
+        f32: From<T2> + From<T3>,
+    {
+        if window == 0 {
+            panic!("Window must be at least 1");
+        }
+
+        self.validate_computed_version_or_reset_file(
+            Version::ONE + self.0.version() + source1.version() + source2.version(),
+        )?;
+
+        let index = max_from.min(I::from(self.len())).unwrap_to_usize();
+        let mut source1_iter = source1.iter();
+        let mut source2_iter = source2.iter();
+
+        let mut get = |i: usize| {
+            (
+                f32::from(source1_iter.unwrap_get_inner_(i)) as f64,
+                f32::from(source2_iter.unwrap_get_inner_(i)) as f64,
+            )
+        };
+
+        let mut covariance = RollingCovariance::default();
+
+        (index.saturating_sub(window)..index).for_each(|i| {
+            let (x, y) = get(i);
+            covariance.insert(x, y);
+        });
+
+        (index..source1.len().min(source2.len())).try_for_each(|i| {
+            let (x, y) = get(i);
+            covariance.insert(x, y);
+            if let Some(prev_i) = i.checked_sub(window) {
+                let (x, y) = get(prev_i);
+                covariance.remove(x, y);
+            }
+
+            self.forced_push_at(I::from(i), T::from(covariance.correlation() as f32), exit)
+        })?;
+
+        self.safe_flush(exit)
+    }
+
+    /// Wilder's smoothed average of the gains of the source over `len` values, the first `len` ones are a simple
+    /// average
+    ///
+    /// Resumes from its own previous value, non-finite values are skipped.
+    pub fn compute_average_gain<T2>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T2>,
+        len: usize,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T> + From<T2>,
+    {
+        self.compute_average_change(max_from, source, len, 1.0, exit)
+    }
+
+    /// Same as `compute_average_gain` with the losses, as positive values
+    pub fn compute_average_loss<T2>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T2>,
+        len: usize,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T> + From<T2>,
+    {
+        self.compute_average_change(max_from, source, len, -1.0, exit)
+    }
+
+    fn compute_average_change<T2>(
+        &mut self,
+        max_from: I,
+        source: &impl AnyIterableVec<I, T2>,
+        len: usize,
+        sign: f32,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T> + From<T2>,
+    {
+        if len == 0 {
+            panic!("Len must be at least 1");
+        }
+
+        self.validate_computed_version_or_reset_file(
+            Version::ZERO + self.0.version() + source.version(),
+        )?;
+
+        let index = max_from.min(I::from(self.len())).unwrap_to_usize();
+        let len_ = len as f32;
+
+        let mut prev_average = index.checked_sub(1).map_or(0.0, |prev_i| {
+            f32::from(self.into_iter().unwrap_get_inner_(prev_i))
+        });
+
+        // Last finite value before the index
+        let mut source_iter = source.iter();
+        let mut prev_value = (0..index)
+            .rev()
+            .map(|i| f32::from(source_iter.unwrap_get_inner_(i)))
+            .find(|value| value.is_finite());
+
+        source.iter_at_(index).try_for_each(|(i, value)| {
+            let value = f32::from(value.into_inner());
+
+            let change = if value.is_finite() {
+                prev_value
+                    .replace(value)
+                    .map_or(0.0, |prev| ((value - prev) * sign).max(0.0))
+            } else {
+                0.0
+            };
+
+            let average = if i.unwrap_to_usize() <= len {
+                prev_average + change / len_
+            } else {
+                (prev_average * (len_ - 1.0) + change) / len_
+            };
+
+            prev_average = average;
+            self.forced_push_at(i, T::from(average), exit)
+        })?;
+
+        self.safe_flush(exit)
+    }
+
+    /// Relative Strength Index from the average gains and losses over `len` values (see `compute_average_gain`)
+    pub fn compute_rsi<T2>(
+        &mut self,
+        max_from: I,
+        average_gain: &impl AnyIterableVec<I, T2>,
+        average_loss: &impl AnyIterableVec<I, T2>,
+        len: usize,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<f32>,
+        T2: StoredType,
+        f32: From<T2>,
+    {
+        let mut average_loss_iter = average_loss.iter();
+
+        self.compute_transform(
+            max_from,
+            average_gain,
+            |(i, average_gain, ..)| {
+                let average_gain = f32::from(average_gain);
+                let average_loss = f32::from(average_loss_iter.unwrap_get_inner(i));
+
+                let rsi = if i.unwrap_to_usize() < len {
+                    f32::NAN
+                } else if average_loss == 0.0 {
+                    if average_gain == 0.0 { 50.0 } else { 100.0 }
+                } else {
+                    100.0 - 100.0 / (1.0 + average_gain / average_loss)
+                };
+
+                (i, T::from(rsi))
+            },
+            exit,
+        )
+    }
 }
 
 impl EagerVec<DateIndex, Sats> {
@@ -1312,6 +1973,25 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    #[inline]
+    fn generation(&self) -> u64 {
+        self.0.generation()
+    }
+
+    /// The page stats are in the same folder as the vec
+    fn sync_to_disk(&self) -> Result<()> {
+        self.0.sync_to_disk()
+    }
+
+    fn refresh(&self) -> Result<bool> {
+        let refreshed = self.0.refresh()?;
+        if let Some(page_stats) = self.1.as_ref().filter(|_| refreshed) {
+            let current = page_stats.load();
+            page_stats.store(Arc::new(PageStats::read(current.path(), current.cmp())?));
+        }
+        Ok(refreshed)
+    }
 }
 
 impl<I, T> AnyIterableVec<I, T> for EagerVec<I, T>
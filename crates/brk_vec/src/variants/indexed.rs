@@ -4,8 +4,8 @@ use arc_swap::ArcSwap;
 use brk_core::{Error, Height, Result, Value, Version};
 
 use crate::{
-    AnyCollectableVec, AnyIterableVec, AnyVec, BoxedVecIterator, CollectableVec, Format,
-    GenericStoredVec, Header, Mmap, StoredIndex, StoredType, StoredVec,
+    AnyCollectableVec, AnyIterableVec, AnyVec, BoxedVecIterator, CollectableVec, GenericStoredVec,
+    Header, ImportMode, Mmap, StoredIndex, StoredType, StoredVec,
 };
 
 use super::StoredVecIterator;
@@ -22,10 +22,10 @@ where
         path: &Path,
         name: &str,
         version: Version,
-        format: Format,
+        mode: ImportMode,
     ) -> Result<Self> {
         Ok(Self(
-            StoredVec::forced_import(path, name, version, format).unwrap(),
+            StoredVec::forced_import(path, name, version, mode).unwrap(),
         ))
     }
 
@@ -34,6 +34,11 @@ where
         self.0.get_or_read(index, mmap)
     }
 
+    #[inline]
+    pub fn gather(&self, indexes: &[I], mmap: &Mmap) -> Result<Vec<T>> {
+        self.0.gather(indexes, mmap)
+    }
+
     #[inline]
     pub fn push_if_needed(&mut self, index: I, value: T) -> Result<()> {
         let len = self.0.len();
@@ -77,6 +82,10 @@ where
         self.0.mmap()
     }
 
+    pub fn verify(&self) -> Result<Option<usize>> {
+        self.0.verify()
+    }
+
     #[inline]
     pub fn hasnt(&self, index: I) -> Result<bool> {
         self.0.has(index).map(|b| !b)
@@ -112,11 +121,26 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    #[inline]
+    fn generation(&self) -> u64 {
+        self.0.generation()
+    }
+
+    fn sync_to_disk(&self) -> Result<()> {
+        self.0.sync_to_disk()
+    }
+
+    fn refresh(&self) -> Result<bool> {
+        self.0.refresh()
+    }
 }
 
 pub trait AnyIndexedVec: AnyVec {
     fn height(&self) -> Height;
     fn flush(&mut self, height: Height) -> Result<()>;
+    fn sync(&self) -> Result<()>;
+    fn truncate_if_needed_(&mut self, index: usize, height: Height) -> Result<()>;
 }
 
 impl<I, T> AnyIndexedVec for IndexedVec<I, T>
@@ -131,6 +155,14 @@ where
     fn flush(&mut self, height: Height) -> Result<()> {
         self.flush(height)
     }
+
+    fn sync(&self) -> Result<()> {
+        self.0.sync()
+    }
+
+    fn truncate_if_needed_(&mut self, index: usize, height: Height) -> Result<()> {
+        self.truncate_if_needed(I::from(index), height)
+    }
 }
 
 impl<'a, I, T> IntoIterator for &'a IndexedVec<I, T>
@@ -4,7 +4,7 @@ use brk_core::{Result, Value, Version};
 
 use crate::{
     AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedAnyIterableVec,
-    BoxedVecIterator, CollectableVec, StoredIndex, StoredType,
+    BoxedVecIterator, CollectableVec, PageCache, PageCacheReader, StoredIndex, StoredType,
 };
 
 pub type ComputeFrom1<I, T, S1I, S1T> =
@@ -16,6 +16,7 @@ pub struct LazyVecFrom1<I, T, S1I, S1T> {
     version: Version,
     source: BoxedAnyIterableVec<S1I, S1T>,
     compute: ComputeFrom1<I, T, S1I, S1T>,
+    id: usize,
     phantom: PhantomData<I>,
 }
 
@@ -41,6 +42,7 @@ where
             version,
             source,
             compute,
+            id: PageCache::next_id(),
             phantom: PhantomData,
         }
     }
@@ -53,6 +55,7 @@ where
 pub struct LazyVecFrom1Iterator<'a, I, T, S1I, S1T> {
     lazy: &'a LazyVecFrom1<I, T, S1I, S1T>,
     source: BoxedVecIterator<'a, S1I, S1T>,
+    cache: PageCacheReader<T>,
     index: usize,
 }
 
@@ -66,11 +69,17 @@ where
     type Item = (I, Value<'a, T>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.len() {
+        let index = self.index;
+        let len = self.len();
+        if index >= len {
             return None;
         }
-        let index = I::from(self.index);
-        let opt = (self.lazy.compute)(index, &mut *self.source).map(|v| (index, Value::Owned(v)));
+        let opt = self
+            .cache
+            .get(index, len, |i| {
+                (self.lazy.compute)(I::from(i), &mut *self.source)
+            })
+            .map(|v| (I::from(index), Value::Owned(v)));
         if opt.is_some() {
             self.index += 1;
         }
@@ -115,6 +124,14 @@ where
         LazyVecFrom1Iterator {
             lazy: self,
             source: self.source.iter(),
+            cache: PageCacheReader::new(
+                self.id,
+                vec![(
+                    self.source.len(),
+                    self.source.version(),
+                    self.source.generation(),
+                )],
+            ),
             index: 0,
         }
     }
@@ -147,6 +164,10 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    fn generation(&self) -> u64 {
+        self.source.generation()
+    }
 }
 
 impl<I, T, S1I, S1T> AnyIterableVec<I, T> for LazyVecFrom1<I, T, S1I, S1T>
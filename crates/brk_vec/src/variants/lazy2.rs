@@ -4,7 +4,7 @@ use brk_core::{Result, Value, Version};
 
 use crate::{
     AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedAnyIterableVec,
-    BoxedVecIterator, CollectableVec, StoredIndex, StoredType,
+    BoxedVecIterator, CollectableVec, PageCache, PageCacheReader, StoredIndex, StoredType,
 };
 
 pub type ComputeFrom2<I, T, S1I, S1T, S2I, S2T> = for<'a> fn(
@@ -20,6 +20,7 @@ pub struct LazyVecFrom2<I, T, S1I, S1T, S2I, S2T> {
     source1: BoxedAnyIterableVec<S1I, S1T>,
     source2: BoxedAnyIterableVec<S2I, S2T>,
     compute: ComputeFrom2<I, T, S1I, S1T, S2I, S2T>,
+    id: usize,
     phantom: PhantomData<I>,
 }
 
@@ -57,6 +58,7 @@ where
             source1,
             source2,
             compute,
+            id: PageCache::next_id(),
             phantom: PhantomData,
         }
     }
@@ -70,6 +72,7 @@ pub struct LazyVecFrom2Iterator<'a, I, T, S1I, S1T, S2I, S2T> {
     lazy: &'a LazyVecFrom2<I, T, S1I, S1T, S2I, S2T>,
     source1: BoxedVecIterator<'a, S1I, S1T>,
     source2: BoxedVecIterator<'a, S2I, S2T>,
+    cache: PageCacheReader<T>,
     index: usize,
 }
 
@@ -85,9 +88,17 @@ where
     type Item = (I, Value<'a, T>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let index = I::from(self.index);
-        let opt = (self.lazy.compute)(index, &mut *self.source1, &mut *self.source2)
-            .map(|v| (index, Value::Owned(v)));
+        let index = self.index;
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        let opt = self
+            .cache
+            .get(index, len, |i| {
+                (self.lazy.compute)(I::from(i), &mut *self.source1, &mut *self.source2)
+            })
+            .map(|v| (I::from(index), Value::Owned(v)));
         if opt.is_some() {
             self.index += 1;
         }
@@ -148,6 +159,21 @@ where
             lazy: self,
             source1: self.source1.iter(),
             source2: self.source2.iter(),
+            cache: PageCacheReader::new(
+                self.id,
+                vec![
+                    (
+                        self.source1.len(),
+                        self.source1.version(),
+                        self.source1.generation(),
+                    ),
+                    (
+                        self.source2.len(),
+                        self.source2.version(),
+                        self.source2.generation(),
+                    ),
+                ],
+            ),
             index: 0,
         }
     }
@@ -192,6 +218,13 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    fn generation(&self) -> u64 {
+        [self.source1.generation(), self.source2.generation()]
+            .into_iter()
+            .max()
+            .unwrap()
+    }
 }
 
 impl<I, T, S1I, S1T, S2I, S2T> AnyIterableVec<I, T> for LazyVecFrom2<I, T, S1I, S1T, S2I, S2T>
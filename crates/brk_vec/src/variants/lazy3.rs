@@ -4,7 +4,7 @@ use brk_core::{Result, Value, Version};
 
 use crate::{
     AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedAnyIterableVec,
-    BoxedVecIterator, CollectableVec, StoredIndex, StoredType,
+    BoxedVecIterator, CollectableVec, PageCache, PageCacheReader, StoredIndex, StoredType,
 };
 
 pub type ComputeFrom3<I, T, S1I, S1T, S2I, S2T, S3I, S3T> = for<'a> fn(
@@ -22,6 +22,7 @@ pub struct LazyVecFrom3<I, T, S1I, S1T, S2I, S2T, S3I, S3T> {
     source2: BoxedAnyIterableVec<S2I, S2T>,
     source3: BoxedAnyIterableVec<S3I, S3T>,
     compute: ComputeFrom3<I, T, S1I, S1T, S2I, S2T, S3I, S3T>,
+    id: usize,
     phantom: PhantomData<I>,
 }
 
@@ -64,6 +65,7 @@ where
             source2,
             source3,
             compute,
+            id: PageCache::next_id(),
             phantom: PhantomData,
         }
     }
@@ -78,6 +80,7 @@ pub struct LazyVecFrom3Iterator<'a, I, T, S1I, S1T, S2I, S2T, S3I, S3T> {
     source1: BoxedVecIterator<'a, S1I, S1T>,
     source2: BoxedVecIterator<'a, S2I, S2T>,
     source3: BoxedVecIterator<'a, S3I, S3T>,
+    cache: PageCacheReader<T>,
     index: usize,
 }
 
@@ -96,14 +99,22 @@ where
     type Item = (I, Value<'a, T>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let index = I::from(self.index);
-        let opt = (self.lazy.compute)(
-            index,
-            &mut *self.source1,
-            &mut *self.source2,
-            &mut *self.source3,
-        )
-        .map(|v| (index, Value::Owned(v)));
+        let index = self.index;
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        let opt = self
+            .cache
+            .get(index, len, |i| {
+                (self.lazy.compute)(
+                    I::from(i),
+                    &mut *self.source1,
+                    &mut *self.source2,
+                    &mut *self.source3,
+                )
+            })
+            .map(|v| (I::from(index), Value::Owned(v)));
         if opt.is_some() {
             self.index += 1;
         }
@@ -175,6 +186,26 @@ where
             source1: self.source1.iter(),
             source2: self.source2.iter(),
             source3: self.source3.iter(),
+            cache: PageCacheReader::new(
+                self.id,
+                vec![
+                    (
+                        self.source1.len(),
+                        self.source1.version(),
+                        self.source1.generation(),
+                    ),
+                    (
+                        self.source2.len(),
+                        self.source2.version(),
+                        self.source2.generation(),
+                    ),
+                    (
+                        self.source3.len(),
+                        self.source3.version(),
+                        self.source3.generation(),
+                    ),
+                ],
+            ),
             index: 0,
         }
     }
@@ -226,6 +257,17 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    fn generation(&self) -> u64 {
+        [
+            self.source1.generation(),
+            self.source2.generation(),
+            self.source3.generation(),
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
 }
 
 impl<I, T, S1I, S1T, S2I, S2T, S3I, S3T> AnyIterableVec<I, T>
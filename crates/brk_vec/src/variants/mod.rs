@@ -1,3 +1,4 @@
+mod columnar;
 mod compressed;
 mod computed;
 mod eager;
@@ -6,8 +7,11 @@ mod lazy1;
 mod lazy2;
 mod lazy3;
 mod raw;
+mod sparse;
 mod stored;
+mod var;
 
+pub use columnar::*;
 pub use compressed::*;
 pub use computed::*;
 pub use eager::*;
@@ -16,4 +20,6 @@ pub use lazy1::*;
 pub use lazy2::*;
 pub use lazy3::*;
 pub use raw::*;
+pub use sparse::*;
 pub use stored::*;
+pub use var::*;
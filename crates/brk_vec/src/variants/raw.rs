@@ -13,8 +13,9 @@ use memmap2::Mmap;
 use rayon::prelude::*;
 
 use crate::{
-    AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedVecIterator, CollectableVec,
-    Format, GenericStoredVec, HEADER_OFFSET, Header, StoredIndex, StoredType, UnsafeSlice,
+    AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedVecIterator, Checksums,
+    CollectableVec, Format, Generation, GenericStoredVec, HEADER_OFFSET, Header, MAX_PAGE_SIZE,
+    StoredIndex, StoredType, UnsafeSlice,
 };
 
 const VERSION: Version = Version::ONE;
@@ -26,7 +27,10 @@ pub struct RawVec<I, T> {
     name: String,
     // Consider  Arc<ArcSwap<Option<Mmap>>> for dataraces when reorg ?
     mmap: Arc<ArcSwap<Mmap>>,
+    generation: Generation,
+    checksums: Option<Arc<ArcSwap<Checksums>>>,
     pushed: Vec<T>,
+    read_only: bool,
     phantom: PhantomData<I>,
 }
 
@@ -35,10 +39,21 @@ where
     I: StoredIndex,
     T: StoredType,
 {
+    pub const PER_CHUNK: usize = MAX_PAGE_SIZE / Self::SIZE_OF_T;
+    pub const CHUNK_SIZE: usize = Self::PER_CHUNK * Self::SIZE_OF_T;
+
     /// Same as import but will reset the folder under certain errors, so be careful !
-    pub fn forced_import(parent: &Path, name: &str, mut version: Version) -> Result<Self> {
+    pub fn forced_import(
+        parent: &Path,
+        name: &str,
+        mut version: Version,
+        read_only: bool,
+    ) -> Result<Self> {
         version = version + VERSION;
-        let res = Self::import(parent, name, version);
+        if read_only {
+            return Self::import(parent, name, version, read_only);
+        }
+        let res = Self::import(parent, name, version, read_only);
         match res {
             Err(Error::DifferentCompressionMode)
             | Err(Error::WrongEndian)
@@ -46,15 +61,70 @@ where
             | Err(Error::DifferentVersion { .. }) => {
                 let path = Self::path_(parent, name);
                 fs::remove_file(path)?;
-                Self::import(parent, name, version)
+                Self::import(parent, name, version, read_only)
             }
             _ => res,
         }
     }
 
-    pub fn import(parent: &Path, name: &str, version: Version) -> Result<Self> {
+    pub fn import(parent: &Path, name: &str, version: Version, read_only: bool) -> Result<Self> {
+        let mut this = Self::import_(parent, name, version, read_only)?;
+
+        let path = this.checksums_path();
+        let missing = !path.exists();
+
+        this.checksums = Some(Arc::new(ArcSwap::new(Arc::new(Checksums::read(&path)?))));
+
+        // Vecs created before checksums existed
+        if missing && !this.is_empty() && !this.read_only {
+            this.update_checksums(0)?;
+        }
+
+        Ok(this)
+    }
+
+    /// Import without checksums, used by vecs which handle the integrity of their data themselves
+    pub(crate) fn import_(
+        parent: &Path,
+        name: &str,
+        version: Version,
+        read_only: bool,
+    ) -> Result<Self> {
         let path = Self::path_(parent, name);
-        let (mmap, header) = match Self::open_file_(&path) {
+
+        let (mmap, header) = if read_only {
+            let file = File::open(&path)?;
+            let mmap = Self::new_mmap(&file)?;
+            let header = Header::import_and_verify(&mmap, version, Format::Raw)?;
+            (mmap, header)
+        } else {
+            Self::import_or_create(parent, name, &path, version)?
+        };
+
+        let mmap = Arc::new(ArcSwap::new(mmap));
+
+        Ok(Self {
+            mmap,
+            generation: Generation::default(),
+            checksums: None,
+            header,
+            name: name.to_string(),
+            parent: parent.to_owned(),
+            pushed: vec![],
+            read_only,
+            phantom: PhantomData,
+        })
+    }
+
+    fn import_or_create(
+        parent: &Path,
+        name: &str,
+        path: &Path,
+        version: Version,
+    ) -> Result<(Arc<Mmap>, Header)> {
+        Header::upgrade_raw_if_needed(path, Self::SIZE_OF_T)?;
+
+        Ok(match Self::open_file_(path) {
             Ok(mut file) => {
                 if file.metadata()?.len() == 0 {
                     let header = Header::create_and_write(&mut file, version, Format::Raw)?;
@@ -69,25 +139,32 @@ where
             Err(e) => match e.kind() {
                 io::ErrorKind::NotFound => {
                     fs::create_dir_all(Self::folder_(parent, name))?;
-                    let mut file = Self::open_file_(&path)?;
+                    let mut file = Self::open_file_(path)?;
                     let header = Header::create_and_write(&mut file, version, Format::Raw)?;
                     let mmap = Self::new_mmap(&file)?;
                     (mmap, header)
                 }
                 _ => return Err(e.into()),
             },
-        };
+        })
+    }
 
-        let mmap = Arc::new(ArcSwap::new(mmap));
+    fn checksums_path(&self) -> PathBuf {
+        self.folder().join(format!("{}-checksums", I::to_string()))
+    }
 
-        Ok(Self {
-            mmap,
-            header,
-            name: name.to_string(),
-            parent: parent.to_owned(),
-            pushed: vec![],
-            phantom: PhantomData,
-        })
+    #[inline]
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    #[inline]
+    pub(crate) fn ensure_writable(&self) -> Result<()> {
+        if self.read_only {
+            Err(Error::ReadOnly)
+        } else {
+            Ok(())
+        }
     }
 
     #[inline]
@@ -107,6 +184,36 @@ where
         iter
     }
 
+    /// Overwrites the last stored value in place, unlike a truncate followed by a push the file never shrinks so the
+    /// mapped pages of readers stay valid
+    pub(crate) fn overwrite_last(&mut self, value: T) -> Result<()> {
+        self.ensure_writable()?;
+
+        let index = self
+            .stored_len()
+            .checked_sub(1)
+            .ok_or(Error::ExpectVecToHaveIndex)?;
+
+        let mut file = self.open_file()?;
+        self.file_write_all_at(
+            &mut file,
+            (HEADER_OFFSET + index * Self::SIZE_OF_T) as u64,
+            value.as_bytes(),
+        )?;
+
+        self.update_checksums(Self::index_to_chunk_index(index))
+    }
+
+    /// Only the length in the header changes, shrinking the file would make any reader still mapping its end crash
+    fn truncate_len(&mut self, len: usize) -> Result<()> {
+        let mut file = self.open_file()?;
+        self.header.update_stored_len(len);
+        self.header.write(&mut file)?;
+        self.generation.bump();
+
+        self.update_checksums(Self::index_to_chunk_index(len))
+    }
+
     pub fn write_header_if_needed(&mut self) -> io::Result<Option<File>> {
         if self.header.modified() {
             let mut file = self.open_file()?;
@@ -116,6 +223,52 @@ where
             Ok(None)
         }
     }
+
+    #[inline(always)]
+    fn index_to_chunk_index(index: usize) -> usize {
+        index / Self::PER_CHUNK
+    }
+
+    #[inline(always)]
+    fn chunk_index_to_index(chunk_index: usize) -> usize {
+        chunk_index * Self::PER_CHUNK
+    }
+
+    fn chunk_checksum(mmap: &Mmap, stored_len: usize, chunk_index: usize) -> u32 {
+        let start = HEADER_OFFSET + chunk_index * Self::CHUNK_SIZE;
+        let end = HEADER_OFFSET
+            + ((chunk_index + 1) * Self::CHUNK_SIZE).min(stored_len * Self::SIZE_OF_T);
+        crc32fast::hash(&mmap[start..end])
+    }
+
+    fn update_checksums(&self, from_chunk_index: usize) -> Result<()> {
+        let Some(checksums) = self.checksums.as_ref() else {
+            return Ok(());
+        };
+
+        let mmap = self.mmap.load();
+        let mmap: &Mmap = &mmap;
+        let stored_len = self.stored_len_(mmap);
+
+        let mut checksums_ = (**checksums.load()).clone();
+
+        let from_chunk_index = from_chunk_index.min(checksums_.len());
+        checksums_.truncate(from_chunk_index);
+
+        (from_chunk_index..stored_len.div_ceil(Self::PER_CHUNK))
+            .into_par_iter()
+            .map(|chunk_index| Self::chunk_checksum(mmap, stored_len, chunk_index))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, checksum)| checksums_.push(from_chunk_index + i, checksum));
+
+        checksums_.write()?;
+
+        checksums.store(Arc::new(checksums_));
+
+        Ok(())
+    }
 }
 
 impl<I, T> GenericStoredVec<I, T> for RawVec<I, T>
@@ -145,13 +298,20 @@ where
         &self.mmap
     }
 
+    #[inline]
+    fn mmap_generation(&self) -> &Generation {
+        &self.generation
+    }
+
     #[inline]
     fn stored_len(&self) -> usize {
         self.stored_len_(&self.mmap.load())
     }
     #[inline]
     fn stored_len_(&self, mmap: &Mmap) -> usize {
-        (mmap.len() - HEADER_OFFSET) / Self::SIZE_OF_T
+        self.header
+            .stored_len()
+            .min((mmap.len() - HEADER_OFFSET) / Self::SIZE_OF_T)
     }
 
     #[inline]
@@ -169,6 +329,8 @@ where
     }
 
     fn flush(&mut self) -> Result<()> {
+        self.ensure_writable()?;
+
         let file_opt = self.write_header_if_needed()?;
 
         let pushed_len = self.pushed_len();
@@ -192,35 +354,62 @@ where
             bytes
         };
 
+        let stored_len = self.stored_len();
+
+        let from_chunk_index = Self::index_to_chunk_index(stored_len);
+
         let mut file = file_opt.unwrap_or(self.open_file()?);
 
-        self.file_write_all(&mut file, &bytes)?;
+        // The file can be longer than the stored values after a truncate
+        self.file_write_all_at(
+            &mut file,
+            (HEADER_OFFSET + stored_len * Self::SIZE_OF_T) as u64,
+            &bytes,
+        )?;
 
-        Ok(())
+        // Written after the values so that readers never see a length covering values which aren't there yet
+        self.header.update_stored_len(stored_len + pushed_len);
+        self.header.write(&mut file)?;
+
+        self.update_checksums(from_chunk_index)
     }
 
     fn truncate_if_needed(&mut self, index: I) -> Result<()> {
+        self.ensure_writable()?;
+
         let index = index.to_usize()?;
 
         if index >= self.stored_len() {
             return Ok(());
         }
 
-        if index == 0 {
-            self.reset()?;
-            return Ok(());
-        }
-
-        let len = index * Self::SIZE_OF_T + HEADER_OFFSET;
-
-        let mut file = self.open_file()?;
-        self.file_set_len(&mut file, len as u64)?;
-
-        Ok(())
+        self.truncate_len(index)
     }
 
     fn reset(&mut self) -> Result<()> {
-        self.reset_()
+        self.ensure_writable()?;
+        self.truncate_len(0)
+    }
+
+    fn verify(&self) -> Result<Option<usize>> {
+        let Some(checksums) = self.checksums.as_ref() else {
+            return Ok(None);
+        };
+
+        let checksums = checksums.load();
+        let checksums: &Checksums = &checksums;
+        let mmap = self.mmap.load();
+        let mmap: &Mmap = &mmap;
+        let stored_len = self.stored_len_(mmap);
+
+        Ok((0..stored_len.div_ceil(Self::PER_CHUNK))
+            .into_par_iter()
+            .find_first(|chunk_index| {
+                checksums.get(*chunk_index).is_none_or(|checksum| {
+                    *checksum != Self::chunk_checksum(mmap, stored_len, *chunk_index)
+                })
+            })
+            .map(Self::chunk_index_to_index))
     }
 }
 
@@ -253,6 +442,41 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    #[inline]
+    fn generation(&self) -> u64 {
+        self.generation.get()
+    }
+
+    fn sync_to_disk(&self) -> Result<()> {
+        GenericStoredVec::sync(self)
+    }
+
+    /// Remaps the file if another process changed its length or its header
+    fn refresh(&self) -> Result<bool> {
+        if !self.read_only {
+            return Ok(false);
+        }
+
+        let file = File::open(self.path())?;
+
+        let remapped = file.metadata()?.len() as usize != self.mmap.load().len();
+        if remapped {
+            self.mmap.store(Self::new_mmap(&file)?);
+        }
+
+        let changed = self.header.reload(&self.mmap.load())? || remapped;
+
+        if changed {
+            self.generation.bump();
+        }
+
+        if let Some(checksums) = self.checksums.as_ref().filter(|_| changed) {
+            checksums.store(Arc::new(Checksums::read(&self.checksums_path())?));
+        }
+
+        Ok(changed)
+    }
 }
 
 impl<I, T> Clone for RawVec<I, T> {
@@ -262,7 +486,10 @@ impl<I, T> Clone for RawVec<I, T> {
             parent: self.parent.clone(),
             name: self.name.clone(),
             mmap: self.mmap.clone(),
+            generation: self.generation.clone(),
+            checksums: self.checksums.clone(),
             pushed: vec![],
+            read_only: self.read_only,
             phantom: PhantomData,
         }
     }
@@ -0,0 +1,734 @@
+use std::{cmp::Ordering, mem, ops::Add, path::Path, sync::Arc};
+
+use arc_swap::Guard;
+use brk_core::{Error, Result, Value, Version};
+use brk_exit::Exit;
+use log::info;
+use memmap2::Mmap;
+use serde::Serialize;
+use zerocopy_derive::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+use crate::{
+    AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedVecIterator, CollectableVec,
+    GenericStoredVec, Header, ImportMode, StoredIndex, StoredType,
+};
+
+use super::RawVec;
+
+const VERSION: Version = Version::ONE;
+
+const ONE_KIB: usize = 1024;
+const ONE_MIB: usize = ONE_KIB * ONE_KIB;
+const MAX_CACHE_SIZE: usize = 210 * ONE_MIB;
+
+/// Ends (exclusive) of a run of values and of its stored values, a run without stored values is filled
+#[repr(C)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    FromBytes,
+    IntoBytes,
+    Immutable,
+    KnownLayout,
+    Serialize,
+)]
+pub struct SparseRun {
+    end: u64,
+    values_end: u64,
+}
+
+impl SparseRun {
+    #[inline]
+    fn is_fill(&self, prev: &Self) -> bool {
+        self.values_end == prev.values_end
+    }
+}
+
+/// A vec of mostly identical values (zeros, NaNs, defaults...)
+///
+/// Stored as two raw vecs: the runs of values in a `RawVec<I, SparseRun>`, which also holds the header,
+/// and only the values which aren't the fill value, in a `values` folder next to it, the format of the import mode is thus ignored.
+/// The values are always written before the runs, which are the source of truth.
+#[derive(Debug)]
+pub struct SparseVec<I, T> {
+    runs: RawVec<I, SparseRun>,
+    values: RawVec<I, T>,
+    fill: T,
+    pushed: Vec<T>,
+}
+
+impl<I, T> SparseVec<I, T>
+where
+    I: StoredIndex,
+    T: StoredType,
+{
+    const SIZE_OF: usize = size_of::<T>();
+
+    /// Same as import but will reset the folder under certain errors, so be careful !
+    ///
+    /// Values are compared to `fill` byte by byte, so NaNs can be used
+    pub fn forced_import(
+        parent: &Path,
+        name: &str,
+        version: Version,
+        fill: T,
+        mode: ImportMode,
+    ) -> Result<Self> {
+        if version == Version::ZERO {
+            dbg!(parent, name);
+            panic!("Version must be at least 1, can't verify endianess otherwise");
+        }
+
+        let runs = RawVec::forced_import(parent, name, version + VERSION, mode.read_only)?;
+        let values =
+            RawVec::forced_import(&runs.folder(), "values", version + VERSION, mode.read_only)?;
+
+        let mut this = Self {
+            runs,
+            values,
+            fill,
+            pushed: vec![],
+        };
+
+        if !this.runs.read_only() {
+            this.reconcile()?;
+        }
+
+        Ok(this)
+    }
+
+    /// Brings both vecs back in sync after an interrupted flush or truncate
+    fn reconcile(&mut self) -> Result<()> {
+        let values_len = self.values.stored_len() as u64;
+
+        let runs_mmap = self.runs.mmap().load();
+        let runs_len = self.runs.stored_len_(&runs_mmap);
+
+        // Runs were written but not (all) their values
+        let valid_len = (0..runs_len)
+            .rev()
+            .find(|i| {
+                self.runs
+                    .read_(*i, &runs_mmap)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|run| run.values_end <= values_len)
+            })
+            .map_or(0, |i| i + 1);
+
+        drop(runs_mmap);
+
+        if valid_len < runs_len {
+            self.runs.truncate_if_needed(I::from(valid_len))?;
+        }
+
+        // Values were written but not their runs
+        let values_end = self.last_run_(&self.runs.mmap().load())?.values_end;
+        if values_end < values_len {
+            self.values
+                .truncate_if_needed(I::from(values_end as usize))?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn is_fill(&self, value: &T) -> bool {
+        value.as_bytes() == self.fill.as_bytes()
+    }
+
+    fn read_run(&self, run_index: usize, runs_mmap: &Mmap) -> Result<SparseRun> {
+        self.runs
+            .read_(run_index, runs_mmap)?
+            .ok_or(Error::ExpectVecToHaveIndex)
+    }
+
+    fn last_run_(&self, runs_mmap: &Mmap) -> Result<SparseRun> {
+        match self.runs.stored_len_(runs_mmap) {
+            0 => Ok(SparseRun::default()),
+            len => self.read_run(len - 1, runs_mmap),
+        }
+    }
+
+    /// Index of the run which contains `index`, with the run before it and itself
+    fn find_run(
+        &self,
+        index: usize,
+        runs_mmap: &Mmap,
+    ) -> Result<Option<(usize, SparseRun, SparseRun)>> {
+        let len = self.runs.stored_len_(runs_mmap);
+
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.read_run(mid, runs_mmap)?.end as usize <= index {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        if low == len {
+            return Ok(None);
+        }
+
+        let prev = if low == 0 {
+            SparseRun::default()
+        } else {
+            self.read_run(low - 1, runs_mmap)?
+        };
+
+        Ok(Some((low, prev, self.read_run(low, runs_mmap)?)))
+    }
+
+    fn read_in_run(
+        &self,
+        index: usize,
+        prev: &SparseRun,
+        run: &SparseRun,
+        values_mmap: &Mmap,
+    ) -> Result<T> {
+        if run.is_fill(prev) {
+            return Ok(self.fill.clone());
+        }
+        self.values
+            .read_(
+                (prev.values_end + (index as u64 - prev.end)) as usize,
+                values_mmap,
+            )?
+            .ok_or(Error::ExpectVecToHaveIndex)
+    }
+
+    fn get_or_read_(
+        &self,
+        index: usize,
+        runs_mmap: &Mmap,
+        values_mmap: &Mmap,
+    ) -> Result<Option<T>> {
+        let stored_len = self.last_run_(runs_mmap)?.end as usize;
+
+        if index >= stored_len {
+            return Ok(self.pushed.get(index - stored_len).cloned());
+        }
+
+        let Some((_, prev, run)) = self.find_run(index, runs_mmap)? else {
+            return Ok(None);
+        };
+
+        self.read_in_run(index, &prev, &run, values_mmap).map(Some)
+    }
+
+    #[inline]
+    pub fn get(&self, index: I) -> Result<Option<T>> {
+        self.get_(index.to_usize()?)
+    }
+
+    pub fn get_(&self, index: usize) -> Result<Option<T>> {
+        self.get_or_read_(index, &self.runs.mmap().load(), &self.values.mmap().load())
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.pushed.push(value);
+    }
+
+    #[inline]
+    pub fn push_if_needed(&mut self, index: I, value: T) -> Result<()> {
+        let len = self.len();
+        match len.cmp(&index.to_usize()?) {
+            Ordering::Greater => Ok(()),
+            Ordering::Equal => {
+                self.push(value);
+                Ok(())
+            }
+            Ordering::Less => Err(Error::IndexTooHigh),
+        }
+    }
+
+    #[inline]
+    pub fn forced_push_at(&mut self, index: I, value: T, exit: &Exit) -> Result<()> {
+        match self.len().cmp(&index.to_usize()?) {
+            Ordering::Less => {
+                return Err(Error::IndexTooHigh);
+            }
+            ord => {
+                if ord == Ordering::Greater {
+                    self.safe_truncate_if_needed(index, exit)?;
+                }
+                self.push(value);
+            }
+        }
+
+        if self.pushed_len() * Self::SIZE_OF >= MAX_CACHE_SIZE {
+            self.safe_flush(exit)
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline]
+    pub fn stored_len(&self) -> usize {
+        self.last_run_(&self.runs.mmap().load())
+            .map_or(0, |run| run.end as usize)
+    }
+
+    #[inline]
+    pub fn pushed_len(&self) -> usize {
+        self.pushed.len()
+    }
+
+    /// Number of runs and of values which aren't the fill value, on disk
+    pub fn stored_counts(&self) -> (usize, usize) {
+        (self.runs.stored_len(), self.values.stored_len())
+    }
+
+    pub fn inner_version(&self) -> Version {
+        self.runs.version()
+    }
+
+    pub fn fill(&self) -> &T {
+        &self.fill
+    }
+
+    pub fn header(&self) -> &Header {
+        self.runs.header()
+    }
+
+    pub fn mut_header(&mut self) -> &mut Header {
+        self.runs.mut_header()
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.runs.ensure_writable()?;
+
+        let runs_mmap = self.runs.mmap().load();
+        let mut prev = self.last_run_(&runs_mmap)?;
+        let mut run: Option<(SparseRun, bool)> = None;
+
+        // The last stored run is extended instead of starting a new one when the first value has the same fill
+        let mut extends_last = false;
+        if let Some(first) = self.pushed.first() {
+            let runs_len = self.runs.stored_len_(&runs_mmap);
+            if runs_len > 0 {
+                let before = match runs_len {
+                    1 => SparseRun::default(),
+                    _ => self.read_run(runs_len - 2, &runs_mmap)?,
+                };
+                let fill = self.is_fill(first);
+                if prev.is_fill(&before) == fill {
+                    run.replace((prev, fill));
+                    prev = before;
+                    extends_last = true;
+                }
+            }
+        }
+        drop(runs_mmap);
+
+        let mut runs = vec![];
+
+        for value in mem::take(&mut self.pushed) {
+            let fill = self.is_fill(&value);
+
+            if run.is_none_or(|(_, run_fill)| run_fill != fill) {
+                if let Some((ended, _)) = run.take() {
+                    runs.push(ended);
+                    prev = ended;
+                }
+                run.replace((prev, fill));
+            }
+
+            let (current, _) = run.as_mut().unwrap();
+            current.end += 1;
+            if !fill {
+                current.values_end += 1;
+                self.values.push(value);
+            }
+        }
+
+        if let Some((run, _)) = run {
+            runs.push(run);
+        }
+
+        self.values.flush()?;
+
+        let mut runs = runs.into_iter();
+        if extends_last {
+            self.runs.overwrite_last(runs.next().unwrap())?;
+        }
+        runs.for_each(|run| self.runs.push(run));
+
+        self.runs.flush()
+    }
+
+    pub fn safe_flush(&mut self, exit: &Exit) -> Result<()> {
+        if exit.triggered() {
+            return Ok(());
+        }
+        let blocked = exit.blocked();
+        if !blocked {
+            exit.block();
+        }
+        self.flush()?;
+        if !blocked {
+            exit.release();
+        }
+        Ok(())
+    }
+
+    pub fn truncate_if_needed(&mut self, index: I) -> Result<()> {
+        self.runs.ensure_writable()?;
+
+        let index = index.to_usize()?;
+        let stored_len = self.stored_len();
+
+        if index >= stored_len {
+            self.pushed.truncate(index - stored_len);
+            return Ok(());
+        }
+
+        self.pushed.clear();
+
+        let (run_index, prev, run) = self
+            .find_run(index, &self.runs.mmap().load())?
+            .ok_or(Error::ExpectVecToHaveIndex)?;
+
+        self.runs.truncate_if_needed(I::from(run_index))?;
+
+        // The run which contains the index is cut instead of removed
+        let values_end = if run.is_fill(&prev) {
+            prev.values_end
+        } else {
+            prev.values_end + (index as u64 - prev.end)
+        };
+        if index as u64 > prev.end {
+            self.runs.push(SparseRun {
+                end: index as u64,
+                values_end,
+            });
+            self.runs.flush()?;
+        }
+
+        self.values.truncate_if_needed(I::from(values_end as usize))
+    }
+
+    fn safe_truncate_if_needed(&mut self, index: I, exit: &Exit) -> Result<()> {
+        if exit.triggered() {
+            return Ok(());
+        }
+        let blocked = exit.blocked();
+        if !blocked {
+            exit.block();
+        }
+        self.truncate_if_needed(index)?;
+        if !blocked {
+            exit.release();
+        }
+        Ok(())
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        self.runs.ensure_writable()?;
+        self.pushed.clear();
+        self.runs.reset()?;
+        self.values.reset()
+    }
+
+    /// Returns the first corrupted index of the vec
+    pub fn verify(&self) -> Result<Option<usize>> {
+        if let Some(run_index) = self.runs.verify()? {
+            return Ok(Some(if run_index == 0 {
+                0
+            } else {
+                self.read_run(run_index - 1, &self.runs.mmap().load())?.end as usize
+            }));
+        }
+
+        let Some(value_index) = self.values.verify()? else {
+            return Ok(None);
+        };
+
+        let runs_mmap = self.runs.mmap().load();
+        let runs_len = self.runs.stored_len_(&runs_mmap);
+
+        let mut prev = SparseRun::default();
+        for run_index in 0..runs_len {
+            let run = self.read_run(run_index, &runs_mmap)?;
+            if run.values_end > value_index as u64 {
+                return Ok(Some(
+                    (prev.end + (value_index as u64 - prev.values_end)) as usize,
+                ));
+            }
+            prev = run;
+        }
+
+        Ok(Some(prev.end as usize))
+    }
+
+    fn update_computed_version(&mut self, computed_version: Version) {
+        self.runs
+            .mut_header()
+            .update_computed_version(computed_version);
+    }
+
+    pub fn validate_computed_version_or_reset_file(&mut self, version: Version) -> Result<()> {
+        if version != self.runs.header().computed_version() {
+            self.update_computed_version(version);
+            if !self.is_empty() {
+                self.reset()?;
+            }
+        }
+
+        if self.is_empty() {
+            info!(
+                "Computing {}_to_{}...",
+                self.index_type_to_string(),
+                self.name()
+            )
+        }
+
+        Ok(())
+    }
+
+    pub fn compute_transform<A, B, F>(
+        &mut self,
+        max_from: A,
+        other: &impl AnyIterableVec<A, B>,
+        mut t: F,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        A: StoredIndex,
+        B: StoredType,
+        F: FnMut((A, B, &Self)) -> (I, T),
+    {
+        self.validate_computed_version_or_reset_file(
+            Version::ZERO + self.runs.version() + other.version(),
+        )?;
+
+        let index = max_from.min(A::from(self.len()));
+        other.iter_at(index).try_for_each(|(a, b)| {
+            let (i, v) = t((a, b.into_inner(), self));
+            self.forced_push_at(i, v, exit)
+        })?;
+
+        self.safe_flush(exit)
+    }
+
+    pub fn compute_count_from_indexes<T2, T3>(
+        &mut self,
+        max_from: I,
+        first_indexes: &impl AnyIterableVec<I, T2>,
+        other_to_else: &impl AnyIterableVec<T2, T3>,
+        exit: &Exit,
+    ) -> Result<()>
+    where
+        T: From<T2>,
+        T2: StoredType + StoredIndex + Copy + Add<usize, Output = T2>,
+        T3: StoredType,
+    {
+        self.validate_computed_version_or_reset_file(
+            Version::ZERO + self.runs.version() + first_indexes.version() + other_to_else.version(),
+        )?;
+
+        let mut other_iter = first_indexes.iter();
+        let index = max_from.min(I::from(self.len()));
+        first_indexes
+            .iter_at(index)
+            .try_for_each(|(i, first_index)| {
+                let end = other_iter
+                    .get_inner(i + 1)
+                    .map(|v| v.unwrap_to_usize())
+                    .unwrap_or_else(|| other_to_else.len());
+                let count = end - first_index.unwrap_to_usize();
+                self.forced_push_at(i, T::from(T2::from(count)), exit)
+            })?;
+
+        self.safe_flush(exit)
+    }
+}
+
+impl<I, T> AnyVec for SparseVec<I, T>
+where
+    I: StoredIndex,
+    T: StoredType,
+{
+    #[inline]
+    fn version(&self) -> Version {
+        self.runs.header().computed_version()
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        self.runs.name()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.stored_len() + self.pushed_len()
+    }
+
+    #[inline]
+    fn index_type_to_string(&self) -> &'static str {
+        I::to_string()
+    }
+
+    #[inline]
+    fn value_type_to_size_of(&self) -> usize {
+        size_of::<T>()
+    }
+
+    #[inline]
+    fn generation(&self) -> u64 {
+        self.runs.generation().max(self.values.generation())
+    }
+
+    fn sync_to_disk(&self) -> Result<()> {
+        self.values.sync_to_disk()?;
+        self.runs.sync_to_disk()
+    }
+
+    /// The runs are refreshed first so that the values are always at least as recent as them
+    fn refresh(&self) -> Result<bool> {
+        if !self.runs.refresh()? {
+            return Ok(false);
+        }
+        self.values.refresh()?;
+        Ok(true)
+    }
+}
+
+impl<I, T> Clone for SparseVec<I, T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            runs: self.runs.clone(),
+            values: self.values.clone(),
+            fill: self.fill.clone(),
+            pushed: vec![],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SparseVecIterator<'a, I, T> {
+    vec: &'a SparseVec<I, T>,
+    runs_guard: Guard<Arc<Mmap>>,
+    values_guard: Guard<Arc<Mmap>>,
+    stored_len: usize,
+    /// Last run read and the one before it, consecutive reads are mostly in the same run
+    run: Option<(SparseRun, SparseRun)>,
+    index: usize,
+}
+
+impl<I, T> BaseVecIterator for SparseVecIterator<'_, I, T>
+where
+    I: StoredIndex,
+    T: StoredType,
+{
+    #[inline]
+    fn mut_index(&mut self) -> &mut usize {
+        &mut self.index
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        self.vec.name()
+    }
+}
+
+impl<'a, I, T> Iterator for SparseVecIterator<'a, I, T>
+where
+    I: StoredIndex,
+    T: StoredType + 'a,
+{
+    type Item = (I, Value<'a, T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+
+        let value = if index >= self.stored_len {
+            self.vec.pushed.get(index - self.stored_len)?.clone()
+        } else {
+            if !self
+                .run
+                .is_some_and(|(prev, run)| prev.end as usize <= index && index < run.end as usize)
+            {
+                self.run = self
+                    .vec
+                    .find_run(index, &self.runs_guard)
+                    .unwrap()
+                    .map(|(_, prev, run)| (prev, run));
+            }
+            let (prev, run) = self.run.as_ref()?;
+            self.vec
+                .read_in_run(index, prev, run, &self.values_guard)
+                .unwrap()
+        };
+
+        self.index += 1;
+
+        Some((I::from(index), Value::Owned(value)))
+    }
+}
+
+impl<'a, I, T> IntoIterator for &'a SparseVec<I, T>
+where
+    I: StoredIndex,
+    T: StoredType,
+{
+    type Item = (I, Value<'a, T>);
+    type IntoIter = SparseVecIterator<'a, I, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let runs_guard = self.runs.mmap().load();
+        let stored_len = self
+            .last_run_(&runs_guard)
+            .map_or(0, |run| run.end as usize);
+        SparseVecIterator {
+            vec: self,
+            runs_guard,
+            values_guard: self.values.mmap().load(),
+            stored_len,
+            run: None,
+            index: 0,
+        }
+    }
+}
+
+impl<I, T> AnyIterableVec<I, T> for SparseVec<I, T>
+where
+    I: StoredIndex,
+    T: StoredType,
+{
+    fn boxed_iter<'a>(&'a self) -> BoxedVecIterator<'a, I, T>
+    where
+        T: 'a,
+    {
+        Box::new(self.into_iter())
+    }
+}
+
+impl<I, T> AnyCollectableVec for SparseVec<I, T>
+where
+    I: StoredIndex,
+    T: StoredType,
+{
+    fn collect_range_serde_json(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<serde_json::Value>> {
+        CollectableVec::collect_range_serde_json(self, from, to)
+    }
+}
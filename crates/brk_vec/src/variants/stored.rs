@@ -6,7 +6,7 @@ use memmap2::Mmap;
 
 use crate::{
     AnyCollectableVec, AnyIterableVec, AnyVec, BaseVecIterator, BoxedVecIterator, CollectableVec,
-    Format, GenericStoredVec, Header, StoredIndex, StoredType,
+    Generation, GenericStoredVec, Header, ImportMode, StoredIndex, StoredType,
 };
 
 use super::{CompressedVec, CompressedVecIterator, RawVec, RawVecIterator};
@@ -26,19 +26,35 @@ where
         path: &Path,
         name: &str,
         version: Version,
-        format: Format,
+        mode: ImportMode,
     ) -> Result<Self> {
         if version == Version::ZERO {
             dbg!(path, name);
             panic!("Version must be at least 1, can't verify endianess otherwise");
         }
 
-        if format.is_compressed() {
+        if mode.format.is_compressed() {
             Ok(Self::Compressed(CompressedVec::forced_import(
-                path, name, version,
+                path,
+                name,
+                version,
+                mode.read_only,
             )?))
         } else {
-            Ok(Self::Raw(RawVec::forced_import(path, name, version)?))
+            Ok(Self::Raw(RawVec::forced_import(
+                path,
+                name,
+                version,
+                mode.read_only,
+            )?))
+        }
+    }
+
+    #[inline]
+    pub fn read_only(&self) -> bool {
+        match self {
+            StoredVec::Raw(v) => v.read_only(),
+            StoredVec::Compressed(v) => v.read_only(),
         }
     }
 }
@@ -56,6 +72,13 @@ where
         }
     }
 
+    fn read_sorted_(&self, indexes: &[usize], mmap: &Mmap) -> Result<Vec<T>> {
+        match self {
+            StoredVec::Raw(v) => v.read_sorted_(indexes, mmap),
+            StoredVec::Compressed(v) => v.read_sorted_(indexes, mmap),
+        }
+    }
+
     #[inline]
     fn header(&self) -> &Header {
         match self {
@@ -80,6 +103,14 @@ where
         }
     }
 
+    #[inline]
+    fn mmap_generation(&self) -> &Generation {
+        match self {
+            StoredVec::Raw(v) => v.mmap_generation(),
+            StoredVec::Compressed(v) => v.mmap_generation(),
+        }
+    }
+
     #[inline]
     fn parent(&self) -> &Path {
         match self {
@@ -146,6 +177,13 @@ where
             StoredVec::Compressed(v) => v.reset(),
         }
     }
+
+    fn verify(&self) -> Result<Option<usize>> {
+        match self {
+            StoredVec::Raw(v) => v.verify(),
+            StoredVec::Compressed(v) => v.verify(),
+        }
+    }
 }
 
 impl<I, T> AnyVec for StoredVec<I, T>
@@ -182,6 +220,28 @@ where
     fn value_type_to_size_of(&self) -> usize {
         size_of::<T>()
     }
+
+    #[inline]
+    fn generation(&self) -> u64 {
+        match self {
+            StoredVec::Raw(v) => v.generation(),
+            StoredVec::Compressed(v) => v.generation(),
+        }
+    }
+
+    fn sync_to_disk(&self) -> Result<()> {
+        match self {
+            StoredVec::Raw(v) => v.sync_to_disk(),
+            StoredVec::Compressed(v) => v.sync_to_disk(),
+        }
+    }
+
+    fn refresh(&self) -> Result<bool> {
+        match self {
+            StoredVec::Raw(v) => v.refresh(),
+            StoredVec::Compressed(v) => v.refresh(),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,609 @@
+use std::{
+    cmp::Ordering,
+    fs::File,
+    io::{self, Write},
+    mem,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use arc_swap::{ArcSwap, Guard};
+use brk_core::{Error, Height, Result, Version};
+use memmap2::Mmap;
+use rayon::prelude::*;
+
+use crate::{
+    AnyCollectableVec, AnyVec, BaseVecIterator, Checksums, CollectableVec, Encoding, Generation,
+    GenericStoredVec, Header, ImportMode, MAX_PAGE_SIZE, StoredIndex,
+};
+
+use super::{AnyIndexedVec, RawVec};
+
+const VERSION: Version = Version::ONE;
+
+/// A vec of variable length values (scripts, payloads, tags...)
+///
+/// Stored as two files: the end offset of each value in a `RawVec<I, u64>`, which also holds the header,
+/// and the concatenated bytes of all values in a data file, the format of the import mode is thus ignored.
+/// The data is always written before the offsets, which are the source of truth.
+/// Both are checksummed, the offsets by chunk like any raw vec and the data by chunk of 64 KB.
+#[derive(Debug)]
+pub struct VarVec<I> {
+    offsets: RawVec<I, u64>,
+    mmap: Arc<ArcSwap<Mmap>>,
+    checksums: Arc<ArcSwap<Checksums>>,
+    generation: Generation,
+    pushed: Vec<u8>,
+    encoding: Encoding,
+}
+
+impl<I> VarVec<I>
+where
+    I: StoredIndex,
+{
+    /// Same as import but will reset the folder under certain errors, so be careful !
+    pub fn forced_import(
+        parent: &Path,
+        name: &str,
+        version: Version,
+        encoding: Encoding,
+        mode: ImportMode,
+    ) -> Result<Self> {
+        if version == Version::ZERO {
+            dbg!(parent, name);
+            panic!("Version must be at least 1, can't verify endianess otherwise");
+        }
+
+        let offsets = RawVec::forced_import(parent, name, version + VERSION, mode.read_only)?;
+        Self::import_(offsets, encoding)
+    }
+
+    pub fn import(
+        parent: &Path,
+        name: &str,
+        version: Version,
+        encoding: Encoding,
+        mode: ImportMode,
+    ) -> Result<Self> {
+        let offsets = RawVec::import(parent, name, version + VERSION, mode.read_only)?;
+        Self::import_(offsets, encoding)
+    }
+
+    fn import_(offsets: RawVec<I, u64>, encoding: Encoding) -> Result<Self> {
+        let path = Self::data_path_(&offsets);
+        let file = if offsets.read_only() {
+            File::open(&path)?
+        } else {
+            Self::open_data_file_(&path)?
+        };
+        let mmap = Arc::new(ArcSwap::new(RawVec::<I, u64>::new_mmap(&file)?));
+
+        let checksums_path = Self::checksums_path_(&offsets);
+        let missing = !checksums_path.exists();
+        let checksums = Arc::new(ArcSwap::new(Arc::new(Checksums::read(&checksums_path)?)));
+
+        let mut this = Self {
+            offsets,
+            mmap,
+            checksums,
+            generation: Generation::default(),
+            pushed: vec![],
+            encoding,
+        };
+
+        if !this.offsets.read_only() {
+            this.reconcile()?;
+
+            // Vecs created before their data was checksummed
+            if missing {
+                this.update_checksums(0)?;
+            }
+        }
+
+        Ok(this)
+    }
+
+    /// Brings both files back in sync after an interrupted flush or truncate
+    fn reconcile(&mut self) -> Result<()> {
+        let data_len = self.stored_data_len() as u64;
+
+        let offsets_mmap = self.offsets.mmap().load();
+        let stored_len = self.offsets.stored_len_(&offsets_mmap);
+
+        // Offsets were written but not (all) their data
+        let valid_len = (0..stored_len)
+            .rev()
+            .find(|i| {
+                self.offsets
+                    .read_(*i, &offsets_mmap)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|end| end <= data_len)
+            })
+            .map_or(0, |i| i + 1);
+
+        drop(offsets_mmap);
+
+        if valid_len < stored_len {
+            self.offsets.truncate_if_needed(I::from(valid_len))?;
+        }
+
+        // Data was written but not its offsets
+        let end = self.end_(valid_len)?;
+        if end < data_len {
+            let file = self.open_data_file()?;
+            file.set_len(end)?;
+            self.update_mmap(&file)?;
+            self.update_checksums(Self::data_chunk_index(end as usize))?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn data_chunk_index(data_index: usize) -> usize {
+        data_index / MAX_PAGE_SIZE
+    }
+
+    fn data_chunk_checksum(mmap: &Mmap, chunk_index: usize) -> u32 {
+        let start = chunk_index * MAX_PAGE_SIZE;
+        let end = (start + MAX_PAGE_SIZE).min(mmap.len());
+        crc32fast::hash(&mmap[start..end])
+    }
+
+    /// Recomputes the checksums of the data from the chunk `from_chunk_index`, the previous ones are unchanged
+    fn update_checksums(&self, from_chunk_index: usize) -> Result<()> {
+        let mmap = self.mmap.load();
+        let mmap: &Mmap = &mmap;
+
+        let mut checksums = (**self.checksums.load()).clone();
+
+        let from_chunk_index = from_chunk_index.min(checksums.len());
+        checksums.truncate(from_chunk_index);
+
+        (from_chunk_index..mmap.len().div_ceil(MAX_PAGE_SIZE))
+            .into_par_iter()
+            .map(|chunk_index| Self::data_chunk_checksum(mmap, chunk_index))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, checksum)| checksums.push(from_chunk_index + i, checksum));
+
+        checksums.write()?;
+
+        self.checksums.store(Arc::new(checksums));
+
+        Ok(())
+    }
+
+    /// Index of the first value whose bytes end after `data_index`
+    fn data_index_to_index(&self, data_index: usize) -> Result<usize> {
+        let offsets_mmap = self.offsets.mmap().load();
+
+        let (mut low, mut high) = (0, self.offsets.stored_len_(&offsets_mmap));
+        while low < high {
+            let mid = (low + high) / 2;
+            let end = self.offsets.read_(mid, &offsets_mmap)?.unwrap_or_default();
+            if end as usize <= data_index {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(low)
+    }
+
+    fn data_path_(offsets: &RawVec<I, u64>) -> PathBuf {
+        offsets.folder().join(format!("{}-data", I::to_string()))
+    }
+
+    pub fn data_path(&self) -> PathBuf {
+        Self::data_path_(&self.offsets)
+    }
+
+    fn checksums_path_(offsets: &RawVec<I, u64>) -> PathBuf {
+        offsets
+            .folder()
+            .join(format!("{}-data-checksums", I::to_string()))
+    }
+
+    fn open_data_file(&self) -> io::Result<File> {
+        Self::open_data_file_(&self.data_path())
+    }
+
+    fn open_data_file_(path: &Path) -> io::Result<File> {
+        RawVec::<I, u64>::open_file_(path)
+    }
+
+    fn update_mmap(&self, file: &File) -> Result<()> {
+        self.mmap.store(RawVec::<I, u64>::new_mmap(file)?);
+        self.generation.bump();
+        Ok(())
+    }
+
+    #[inline]
+    fn stored_data_len(&self) -> usize {
+        self.mmap.load().len()
+    }
+
+    /// End offset of the values before `index` from the stored offsets
+    fn end_(&self, index: usize) -> Result<u64> {
+        if index == 0 {
+            return Ok(0);
+        }
+        Ok(self
+            .offsets
+            .read_(index - 1, &self.offsets.mmap().load())?
+            .unwrap_or_default())
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: &[u8]) {
+        self.pushed.extend_from_slice(value);
+        let end = self.stored_data_len() + self.pushed.len();
+        self.offsets.push(end as u64);
+    }
+
+    #[inline]
+    pub fn push_if_needed(&mut self, index: I, value: &[u8]) -> Result<()> {
+        let len = self.len();
+        match len.cmp(&index.to_usize()?) {
+            Ordering::Greater => Ok(()),
+            Ordering::Equal => {
+                self.push(value);
+                Ok(())
+            }
+            Ordering::Less => Err(Error::IndexTooHigh),
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, index: I) -> Result<Option<Vec<u8>>> {
+        self.get_(index.to_usize()?)
+    }
+
+    pub fn get_(&self, index: usize) -> Result<Option<Vec<u8>>> {
+        self.get_or_read_(index, &self.offsets.mmap().load(), &self.mmap.load())
+    }
+
+    fn get_or_read_(
+        &self,
+        index: usize,
+        offsets_mmap: &Mmap,
+        mmap: &Mmap,
+    ) -> Result<Option<Vec<u8>>> {
+        let Some(end) = self.offsets.get_or_read_(index, offsets_mmap)? else {
+            return Ok(None);
+        };
+        let end = *end as usize;
+
+        let start = if index == 0 {
+            0
+        } else {
+            self.offsets
+                .get_or_read_(index - 1, offsets_mmap)?
+                .map_or(0, |v| *v as usize)
+        };
+
+        let stored_data_len = mmap.len();
+
+        Ok(Some(if end <= stored_data_len {
+            mmap[start..end].to_vec()
+        } else {
+            self.pushed[start - stored_data_len..end - stored_data_len].to_vec()
+        }))
+    }
+
+    #[inline]
+    pub fn stored_len(&self) -> usize {
+        self.offsets.stored_len()
+    }
+
+    #[inline]
+    pub fn pushed_len(&self) -> usize {
+        self.offsets.pushed_len()
+    }
+
+    pub fn header(&self) -> &Header {
+        self.offsets.header()
+    }
+
+    pub fn mut_header(&mut self) -> &mut Header {
+        self.offsets.mut_header()
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.offsets.ensure_writable()?;
+
+        if !self.pushed.is_empty() {
+            let from_chunk_index = Self::data_chunk_index(self.stored_data_len());
+            let mut file = self.open_data_file()?;
+            file.write_all(&mem::take(&mut self.pushed))?;
+            self.update_mmap(&file)?;
+            self.update_checksums(from_chunk_index)?;
+        }
+
+        self.offsets.flush()
+    }
+
+    pub fn truncate_if_needed(&mut self, index: I) -> Result<()> {
+        self.offsets.ensure_writable()?;
+
+        let index_ = index.to_usize()?;
+
+        if index_ >= self.stored_len() {
+            return Ok(());
+        }
+
+        let end = self.end_(index_)?;
+
+        self.offsets.truncate_if_needed(index)?;
+
+        let file = self.open_data_file()?;
+        file.set_len(end)?;
+        self.update_mmap(&file)?;
+        self.update_checksums(Self::data_chunk_index(end as usize))
+    }
+
+    /// Checks the offsets and then the data, returns the first index whose offset or bytes are corrupted
+    pub fn verify(&self) -> Result<Option<usize>> {
+        let corrupted_offset = self.offsets.verify()?;
+
+        let checksums = self.checksums.load();
+        let checksums: &Checksums = &checksums;
+        let mmap = self.mmap.load();
+        let mmap: &Mmap = &mmap;
+
+        let corrupted_chunk_index = (0..mmap.len().div_ceil(MAX_PAGE_SIZE))
+            .into_par_iter()
+            .find_first(|chunk_index| {
+                checksums.get(*chunk_index).is_none_or(|checksum| {
+                    *checksum != Self::data_chunk_checksum(mmap, *chunk_index)
+                })
+            });
+
+        let corrupted_value = corrupted_chunk_index
+            .map(|chunk_index| self.data_index_to_index(chunk_index * MAX_PAGE_SIZE))
+            .transpose()?;
+
+        Ok(corrupted_offset.into_iter().chain(corrupted_value).min())
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        self.offsets.ensure_writable()?;
+        self.offsets.reset()?;
+        self.pushed.clear();
+
+        let file = self.open_data_file()?;
+        file.set_len(0)?;
+        self.update_mmap(&file)?;
+        self.update_checksums(0)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> VarVecIterator<'_, I> {
+        self.into_iter()
+    }
+
+    #[inline]
+    pub fn iter_at(&self, i: I) -> VarVecIterator<'_, I> {
+        self.iter_at_(i.unwrap_to_usize())
+    }
+
+    #[inline]
+    pub fn iter_at_(&self, i: usize) -> VarVecIterator<'_, I> {
+        let mut iter = self.into_iter();
+        iter.set_(i);
+        iter
+    }
+
+    pub fn collect_range(&self, from: Option<usize>, to: Option<usize>) -> Result<Vec<Vec<u8>>> {
+        let len = self.len();
+        let from = from.unwrap_or_default();
+        let to = to.map_or(len, |to| to.min(len));
+
+        if from >= len || from >= to {
+            return Ok(vec![]);
+        }
+
+        Ok(self
+            .iter_at_(from)
+            .take(to - from)
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>())
+    }
+}
+
+impl<I> AnyVec for VarVec<I>
+where
+    I: StoredIndex,
+{
+    #[inline]
+    fn version(&self) -> Version {
+        self.offsets.version()
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        self.offsets.name()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    #[inline]
+    fn index_type_to_string(&self) -> &'static str {
+        I::to_string()
+    }
+
+    /// Average size of a stored value
+    #[inline]
+    fn value_type_to_size_of(&self) -> usize {
+        self.stored_data_len()
+            .checked_div(self.stored_len())
+            .unwrap_or_default()
+            .max(1)
+    }
+
+    #[inline]
+    fn generation(&self) -> u64 {
+        self.offsets.generation().max(self.generation.get())
+    }
+
+    /// The data file is in the same folder as the offsets
+    fn sync_to_disk(&self) -> Result<()> {
+        self.offsets.sync_to_disk()
+    }
+
+    /// The offsets are refreshed first so that the data is always at least as recent as them
+    fn refresh(&self) -> Result<bool> {
+        if !self.offsets.refresh()? {
+            return Ok(false);
+        }
+
+        let file = File::open(self.data_path())?;
+        if file.metadata()?.len() as usize != self.stored_data_len() {
+            self.update_mmap(&file)?;
+        }
+
+        self.checksums
+            .store(Arc::new(Checksums::read(&Self::checksums_path_(
+                &self.offsets,
+            ))?));
+
+        Ok(true)
+    }
+}
+
+/// Lets a var vec be flushed and rolled back along with the indexed vecs of the indexer
+impl<I> AnyIndexedVec for VarVec<I>
+where
+    I: StoredIndex,
+{
+    fn height(&self) -> Height {
+        self.header().height()
+    }
+
+    fn flush(&mut self, height: Height) -> Result<()> {
+        self.mut_header().update_height(height);
+        self.flush()
+    }
+
+    /// The data file is in the same folder as the offsets
+    fn sync(&self) -> Result<()> {
+        self.offsets.sync()
+    }
+
+    fn truncate_if_needed_(&mut self, index: usize, height: Height) -> Result<()> {
+        self.mut_header().update_height(height);
+        self.truncate_if_needed(I::from(index))
+    }
+}
+
+impl<I> Clone for VarVec<I> {
+    fn clone(&self) -> Self {
+        Self {
+            offsets: self.offsets.clone(),
+            mmap: self.mmap.clone(),
+            checksums: self.checksums.clone(),
+            generation: self.generation.clone(),
+            pushed: vec![],
+            encoding: self.encoding,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct VarVecIterator<'a, I> {
+    vec: &'a VarVec<I>,
+    offsets_guard: Guard<Arc<Mmap>>,
+    guard: Guard<Arc<Mmap>>,
+    index: usize,
+}
+
+impl<I> BaseVecIterator for VarVecIterator<'_, I>
+where
+    I: StoredIndex,
+{
+    #[inline]
+    fn mut_index(&mut self) -> &mut usize {
+        &mut self.index
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        self.vec.name()
+    }
+}
+
+impl<I> Iterator for VarVecIterator<'_, I>
+where
+    I: StoredIndex,
+{
+    type Item = (I, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+
+        let opt = self
+            .vec
+            .get_or_read_(index, &self.offsets_guard, &self.guard)
+            .unwrap()
+            .map(|v| (I::from(index), v));
+
+        if opt.is_some() {
+            self.index += 1;
+        }
+
+        opt
+    }
+}
+
+impl<'a, I> IntoIterator for &'a VarVec<I>
+where
+    I: StoredIndex,
+{
+    type Item = (I, Vec<u8>);
+    type IntoIter = VarVecIterator<'a, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        VarVecIterator {
+            vec: self,
+            offsets_guard: self.offsets.mmap().load(),
+            guard: self.mmap.load(),
+            index: 0,
+        }
+    }
+}
+
+impl<I> AnyCollectableVec for VarVec<I>
+where
+    I: StoredIndex,
+{
+    fn collect_range_serde_json(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<serde_json::Value>> {
+        let len = self.len();
+        let from = from.map(|i| RawVec::<I, u64>::i64_to_usize(i, len));
+        let to = to.map(|i| RawVec::<I, u64>::i64_to_usize(i, len));
+        Ok(self
+            .collect_range(from, to)?
+            .into_iter()
+            .map(|bytes| serde_json::Value::String(self.encoding.encode(&bytes)))
+            .collect())
+    }
+}